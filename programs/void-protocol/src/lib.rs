@@ -1,4 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use solana_sha256_hasher::hashv;
 
 declare_id!("9wPskrpZiLSb3He3QoLZMEeiBKWJUh7ykGtkb2N7HX9H");
 
@@ -6,22 +15,881 @@ declare_id!("9wPskrpZiLSb3He3QoLZMEeiBKWJUh7ykGtkb2N7HX9H");
 const MAX_NAME_LEN: usize = 64;
 const MAX_DESC_LEN: usize = 256;
 const MAX_SLUG_LEN: usize = 32;
-const MAX_ARWEAVE_HASH_LEN: usize = 64;
+const MAX_HANDLE_LEN: usize = 32;
+const MAX_GUIDELINES_LEN: usize = 200;
+
+// Most encryption keys an org can publish; each tip is sealed to all of them
+const MAX_ORG_KEYS: usize = 3;
+
+// Min lengths that keep placeholder orgs and one-letter slug squatting out of the directory
+const MIN_DESC_LEN: usize = 8;
+const MIN_SLUG_LEN: usize = 3;
+
+// Max length of a storage locator (Arweave tx id, IPFS/Filecoin CID)
+const MAX_LOCATOR_LEN: usize = 128;
+
+// Per-network locator limits, checked against the pointer's declared kind.
+// All fit within MAX_LOCATOR_LEN, so account capacity is unaffected.
+const MAX_ARWEAVE_LOCATOR_LEN: usize = 64;
+const MAX_IPFS_LOCATOR_LEN: usize = 80;
+const MAX_FILECOIN_LOCATOR_LEN: usize = 96;
+
+// Fixed locator capacity of a zero-copy submission (fits Arweave ids and CIDv1s)
+const MAX_ZC_LOCATOR_LEN: usize = 64;
+
+// Largest locator capacity a submission can be grown to with resize_submission_hash
+const MAX_RESIZED_LOCATOR_LEN: usize = 256;
+
+// Max length of a direct message's plaintext label
+const MAX_LABEL_LEN: usize = 32;
+
+// Most attachments a direct message may advertise
+const MAX_ATTACHMENTS: u16 = 16;
+
+// Max string lengths for optional proof metadata
+const MAX_METADATA_URI_LEN: usize = 128;
+const MAX_CONTENT_TYPE_LEN: usize = 32;
+const MAX_TITLE_LEN: usize = 48;
+
+// Longest file digest a proof can store (SHA-512)
+const MAX_DIGEST_LEN: usize = 64;
+// Solana caps each PDA seed at 32 bytes, so longer digests span two seeds
+const MAX_SEED_LEN: usize = 32;
+
+// Deepest Merkle branch accepted by verify_inclusion (enough for u32::MAX leaves)
+const MAX_MERKLE_DEPTH: usize = 32;
+
+// Verifications a proof's VerifyLog remembers before overwriting the oldest
+const MAX_VERIFY_LOG: usize = 16;
+
+// Most inboxes a single send_broadcast may fan out to (compute budget)
+const MAX_BROADCAST_RECIPIENTS: usize = 10;
+
+// Most messages a single burn_messages call may burn (compute budget)
+const MAX_BURN_BATCH: usize = 15;
+
+// Most messages get_message_window may summarize (return data is capped at 1 KiB)
+const MAX_MESSAGE_WINDOW: u16 = 20;
+
+// Most submissions a single purge_organization call may close (compute budget)
+const MAX_PURGE_BATCH: usize = 15;
+
+// Most proofs a single create_proofs call may stamp (compute budget)
+const MAX_BULK_PROOFS: usize = 6;
+
+// Layout of a single-signature ed25519 program instruction
+const ED25519_HEADER_LEN: usize = 16;
+const ED25519_SELF_IX: u16 = u16::MAX;
+
+// Layout version written to `schema_version` when a Proof, Organization, Submission,
+// Inbox or DirectMessage is created, and only rewritten by a `migrate_*` instruction.
+// Bump it with every layout change to one of those accounts so a migration only has
+// to touch accounts stamped with an older version. New `Submission` fields go right
+// after `schema_version`, so every version can read it at the same offset. Other
+// migrations tell layouts apart by account size, so a layout change to those
+// accounts must grow them and add the old size to their `LEGACY_LAYOUTS`.
+//   1: initial versioned layouts
+//   2: `Submission.status_updated_at`
+//   3: `Organization.last_admin_activity` and `liveness_timeout`
+//   4: `Inbox.message_fee`
+//   5: `Proof.title`
+//   6: `Organization` rate-limit window
+//   7: `Organization.encryption_keys` and `key_count`
+//   8: `Organization.guidelines`
+//   9: `Submission.parent_id`
+//   10: `Proof.claim_secret_hash`
+//   11: `Organization` per-IP rate limit
+//   12: `Inbox.allowlist_only`
+//   13: `Organization.index`
+//   14: `Submission.frozen`
+//   15: `Organization.handle`
+//   16: `Submission.rent_recipient` and `DirectMessage.rent_recipient`
+//   17: `Proof.supersedes` and `superseded_by`
+//   18: `Organization.require_proof` and `Submission.proof`
+//   19: `Organization.validator_program`
+//   20: `Submission.read_at`, `replied_at` and `resolved_at`
+//   21: `DirectMessage.ephemeral_key`
+//   22: `Organization.dedup`
+//   23: `Organization.max_hash_len`
+const CURRENT_SCHEMA: u8 = 23;
+
+// Largest share of each tip's lamport fee the protocol may take (10%)
+const MAX_PROTOCOL_FEE_BPS: u16 = 1000;
+
+// Instruction an org's validator program must expose: the first 8 bytes of
+// sha256("global:validate"), matching how Anchor names a `validate` handler
+const VALIDATE_DISCRIMINATOR: [u8; 8] = [60, 252, 90, 66, 246, 253, 232, 139];
+
+/// PDA seed prefixes of every account type. The program derives its addresses
+/// from these, and off-chain Rust clients can import them to derive the same ones.
+pub mod seeds {
+    use super::{digest_seeds, HashAlgo};
+    use anchor_lang::prelude::Pubkey;
+
+    // Protocol
+    pub const CONFIG: &[u8] = b"config";
+    pub const INDEX: &[u8] = b"index";
+    pub const TOMBSTONE: &[u8] = b"tombstone";
+
+    // Proofs
+    pub const PROOF: &[u8] = b"proof";
+    pub const REGISTRY: &[u8] = b"registry";
+    pub const BATCH: &[u8] = b"batch";
+    pub const ESCROW: &[u8] = b"escrow";
+    pub const VERIFY_LOG: &[u8] = b"verify_log";
+
+    // Organizations and tips
+    pub const ORG: &[u8] = b"org";
+    pub const ORG_REGISTRY: &[u8] = b"org_registry";
+    pub const TREASURY: &[u8] = b"treasury";
+    pub const MEMBER: &[u8] = b"member";
+    pub const SPONSOR: &[u8] = b"sponsor";
+    pub const BOUNTY: &[u8] = b"bounty";
+    pub const ALLOW: &[u8] = b"allow";
+    pub const SUBMITTER: &[u8] = b"submitter";
+    pub const IPRATE: &[u8] = b"iprate";
+    pub const SUBDEDUP: &[u8] = b"subdedup";
+    pub const SUBMISSION: &[u8] = b"submission";
+    pub const REPLY: &[u8] = b"reply";
+    pub const REPORT: &[u8] = b"report";
+
+    // Messaging
+    pub const INBOX: &[u8] = b"inbox";
+    pub const DM: &[u8] = b"dm";
+    pub const BLOCK: &[u8] = b"block";
+    pub const INBOX_ALLOW: &[u8] = b"inbox_allow";
+    pub const SSTATS: &[u8] = b"sstats";
+    pub const PENDING_INBOX: &[u8] = b"pending_inbox";
+    pub const PENDING_DM: &[u8] = b"pending_dm";
+
+    // Wallet profiles
+    pub const PROFILE: &[u8] = b"profile";
+    pub const FOLLOW: &[u8] = b"follow";
+    pub const VOUCH: &[u8] = b"vouch";
+
+    // The algorithm seed of each `HashAlgo`, indexed by discriminant
+    static ALGO_SEEDS: [[u8; 1]; 3] = [[0], [1], [2]];
+
+    /// `[PROOF, algo, digest[..32], digest[32..]]`, the seeds of a public proof
+    pub fn proof_seeds(algo: HashAlgo, digest: &[u8]) -> [&[u8]; 4] {
+        let (head, tail) = digest_seeds(digest);
+        [PROOF, &ALGO_SEEDS[algo as usize], head, tail]
+    }
+
+    /// `[ORG, slug]`, the seeds of an organization
+    pub fn org_seeds(slug: &str) -> [&[u8]; 2] {
+        [ORG, slug.as_bytes()]
+    }
+
+    /// `[SUBMISSION, organization, id]`, the seeds of an org's submission, where
+    /// `id` is its little-endian ID
+    pub fn submission_seeds<'a>(organization: &'a Pubkey, id: &'a [u8; 8]) -> [&'a [u8]; 3] {
+        [SUBMISSION, organization.as_ref(), id]
+    }
+
+    /// `[INBOX, owner]`, the seeds of a wallet's inbox
+    pub fn inbox_seeds(owner: &Pubkey) -> [&[u8]; 2] {
+        [INBOX, owner.as_ref()]
+    }
+
+    /// `[DM, recipient, id]`, the seeds of a message, where `id` is its
+    /// little-endian ID in the recipient's inbox
+    pub fn dm_seeds<'a>(recipient: &'a Pubkey, id: &'a [u8; 8]) -> [&'a [u8]; 3] {
+        [DM, recipient.as_ref(), id]
+    }
+
+    /// Address and bump of the public proof of `digest`
+    pub fn proof_address(algo: HashAlgo, digest: &[u8]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&proof_seeds(algo, digest), &crate::ID)
+    }
+
+    /// Address and bump of the organization registered under `slug`
+    pub fn org_address(slug: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&org_seeds(slug), &crate::ID)
+    }
+
+    /// Address and bump of an org's submission `id`; zero-copy submissions share it
+    pub fn submission_address(organization: &Pubkey, id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&submission_seeds(organization, &id.to_le_bytes()), &crate::ID)
+    }
+
+    /// Address and bump of `owner`'s inbox
+    pub fn inbox_address(owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&inbox_seeds(owner), &crate::ID)
+    }
+
+    /// Address and bump of message `id` in `recipient`'s inbox
+    pub fn dm_address(recipient: &Pubkey, id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&dm_seeds(recipient, &id.to_le_bytes()), &crate::ID)
+    }
+}
 
 #[program]
 pub mod void_protocol {
     use super::*;
 
+    // ─── PROTOCOL CONFIG ────────────────────────────────────────
+
+    /// Create the singleton program config. The caller becomes the pause authority.
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.paused = false;
+        config.relayer = Pubkey::default();
+        config.protocol_fee_bps = 0;
+        config.treasury = Pubkey::default();
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Freeze or unfreeze new proofs, tips and messages (config authority only).
+    /// Existing accounts can still be read, closed and burned while paused.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+
+        emit!(PausedChanged {
+            authority: config.authority,
+            paused,
+        });
+        Ok(())
+    }
+
+    /// Register the backend wallet that relays anonymous tips (config authority only).
+    /// Tips signed by it are flagged `via_relay`. `Pubkey::default()` clears it.
+    pub fn set_relayer(ctx: Context<SetRelayer>, relayer: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.relayer = relayer;
+
+        emit!(RelayerChanged {
+            authority: config.authority,
+            relayer,
+        });
+        Ok(())
+    }
+
+    /// Set the protocol's cut of every tip's lamport fee, in basis points (at most
+    /// `MAX_PROTOCOL_FEE_BPS`), and the wallet it is paid to (config authority only).
+    pub fn set_protocol_fee(
+        ctx: Context<SetProtocolFee>,
+        protocol_fee_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, VoidError::FeeTooHigh);
+        let config = &mut ctx.accounts.config;
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.treasury = treasury;
+
+        emit!(ProtocolFeeChanged {
+            authority: config.authority,
+            protocol_fee_bps,
+            treasury,
+        });
+        Ok(())
+    }
+
     // ─── VOID STAMP ─────────────────────────────────────────────
 
-    /// Store a SHA-256 hash on-chain as proof that a file existed at this moment.
-    /// The hash is used as a PDA seed, so each hash can only be registered once.
-    pub fn create_proof(ctx: Context<CreateProof>, hash: [u8; 32]) -> Result<()> {
+    /// Store a file digest on-chain as proof that a file existed at this moment.
+    /// The PDA is seeded by `[b"proof", algo, digest[..32], digest[32..]]`, so each
+    /// digest can only be registered once per hash algorithm.
+    /// Each proof gets the next sequential `id` from the owner's registry.
+    /// An optional metadata URI and MIME content type can be attached for context,
+    /// and an optional `title` (up to `MAX_TITLE_LEN` bytes) for display. All three
+    /// are public.
+    ///
+    /// To attest the digest with a key, pass `signature` and `signer_pubkey` plus the
+    /// instructions sysvar, and put an ed25519 program instruction verifying that
+    /// signature over the raw digest immediately before this one in the transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_proof(
+        ctx: Context<CreateProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        metadata_uri: Option<String>,
+        content_type: Option<String>,
+        title: Option<String>,
+        signature: Option<[u8; 64]>,
+        signer_pubkey: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &digest)?;
+        check_proof_metadata(&metadata_uri, &content_type)?;
+        require!(
+            title.as_ref().is_none_or(|t| t.len() <= MAX_TITLE_LEN),
+            VoidError::TitleTooLong
+        );
+        let attested_by = check_attestation(
+            ctx.accounts.instructions.as_ref(),
+            &digest,
+            signature,
+            signer_pubkey,
+        )?;
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, digest, ctx.accounts.owner.key(), ctx.bumps.proof)?;
+        proof.metadata_uri = metadata_uri;
+        proof.content_type = content_type;
+        proof.signer_pubkey = attested_by;
+        proof.title = title;
+
+        emit!(ProofCreated {
+            proof: proof.key(),
+            algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+            title: proof.title.clone(),
+            timestamp: proof.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Same as `create_proof`, but the PDA is seeded by
+    /// `[b"proof", owner, algo, digest[..32], digest[32..]]`, so every wallet can
+    /// independently stamp the same digest. Clients must derive the address with
+    /// the stamping wallet included; `namespace` records that wallet.
+    pub fn create_namespaced_proof(
+        ctx: Context<CreateNamespacedProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        metadata_uri: Option<String>,
+        content_type: Option<String>,
+        signature: Option<[u8; 64]>,
+        signer_pubkey: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &digest)?;
+        check_proof_metadata(&metadata_uri, &content_type)?;
+        let attested_by = check_attestation(
+            ctx.accounts.instructions.as_ref(),
+            &digest,
+            signature,
+            signer_pubkey,
+        )?;
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, digest, ctx.accounts.owner.key(), ctx.bumps.proof)?;
+        proof.metadata_uri = metadata_uri;
+        proof.content_type = content_type;
+        proof.signer_pubkey = attested_by;
+        proof.namespace = Some(proof.owner);
+
+        emit!(ProofCreated {
+            proof: proof.key(),
+            algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+            title: None,
+            timestamp: proof.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stamp a digest on behalf of an organization (org admin only), so readers can
+    /// verify a published document came from that org. Uses the same global PDA
+    /// as `create_proof`; the admin wallet becomes the proof owner.
+    pub fn create_org_proof(
+        ctx: Context<CreateOrgProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        metadata_uri: Option<String>,
+        content_type: Option<String>,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &digest)?;
+        check_proof_metadata(&metadata_uri, &content_type)?;
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, digest, ctx.accounts.admin.key(), ctx.bumps.proof)?;
+        proof.metadata_uri = metadata_uri;
+        proof.content_type = content_type;
+        proof.organization = Some(ctx.accounts.organization.key());
+
+        emit!(OrgProofCreated {
+            proof: proof.key(),
+            organization: ctx.accounts.organization.key(),
+            algo,
+            digest: proof.digest.clone(),
+            admin: proof.owner,
+            timestamp: proof.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stamp a digest as the next entry in the owner's append-only log.
+    /// `prev_proof` must name an existing proof owned by the same wallet (passed as
+    /// the `prev` account), or be `None` to start a new chain. `chain_hash` mixes
+    /// the previous entry's `chain_hash` with this digest, so walking `prev` back
+    /// and recomputing it detects any entry inserted or removed along the way.
+    pub fn create_linked_proof(
+        ctx: Context<CreateLinkedProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        prev_proof: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &digest)?;
+
+        let owner = ctx.accounts.owner.key();
+        let prev_chain_hash = match (prev_proof, ctx.accounts.prev.as_ref()) {
+            (None, None) => [0; 32],
+            (Some(prev_key), Some(prev)) => {
+                require_keys_eq!(prev.key(), prev_key, VoidError::InvalidPrevProof);
+                require_keys_eq!(prev.owner, owner, VoidError::InvalidPrevProof);
+                prev.chain_hash
+            }
+            _ => return err!(VoidError::InvalidPrevProof),
+        };
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, digest, owner, ctx.bumps.proof)?;
+        proof.chain_hash = chain_hash(&prev_chain_hash, algo, &proof.digest);
+        proof.prev = prev_proof;
+
+        emit!(ProofCreated {
+            proof: proof.key(),
+            algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+            title: None,
+            timestamp: proof.timestamp,
+        });
+        emit!(ProofLinked {
+            proof: proof.key(),
+            prev: prev_proof,
+            chain_hash: proof.chain_hash,
+        });
+        Ok(())
+    }
+
+    /// Stamp a revised document as the successor of `old_proof` (old proof's owner
+    /// only). The new global proof records `supersedes`, and the old one is marked
+    /// `superseded_by`, so a document's versions can be walked in either direction.
+    /// A proof can be superseded once, which keeps each history a single chain.
+    pub fn supersede_proof(
+        ctx: Context<SupersedeProof>,
+        algo: HashAlgo,
+        new_digest: Vec<u8>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &new_digest)?;
+        require!(
+            ctx.accounts.old_proof.superseded_by.is_none(),
+            VoidError::AlreadySuperseded
+        );
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, new_digest, ctx.accounts.owner.key(), ctx.bumps.proof)?;
+        proof.supersedes = Some(ctx.accounts.old_proof.key());
+
+        let old_proof = &mut ctx.accounts.old_proof;
+        old_proof.superseded_by = Some(proof.key());
+
+        emit!(ProofCreated {
+            proof: proof.key(),
+            algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+            title: None,
+            timestamp: proof.timestamp,
+        });
+        emit!(ProofSuperseded {
+            old_proof: old_proof.key(),
+            new_proof: proof.key(),
+            owner: proof.owner,
+            timestamp: proof.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Stamp a digest with `stake` lamports escrowed behind it for `challenge_secs`.
+    /// Until `finalized_at` anyone who believes the timestamp is fraudulent can
+    /// `challenge_proof` by matching the stake; the dispute itself is settled
+    /// off-chain and recorded by the protocol authority with `resolve_challenge`.
+    /// An unchallenged proof is settled with `finalize_proof` once the window ends.
+    pub fn create_challengeable_proof(
+        ctx: Context<CreateChallengeableProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        stake: u64,
+        challenge_secs: i64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &digest)?;
+        require!(stake > 0, VoidError::StakeRequired);
+        require!(challenge_secs > 0, VoidError::InvalidChallengePeriod);
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, digest, ctx.accounts.owner.key(), ctx.bumps.proof)?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.proof = proof.key();
+        escrow.owner = proof.owner;
+        escrow.stake = stake;
+        escrow.finalized_at = now
+            .checked_add(challenge_secs)
+            .ok_or(VoidError::CounterOverflow)?;
+        escrow.challenger = None;
+        escrow.bump = ctx.bumps.escrow;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: escrow.to_account_info(),
+                },
+            ),
+            stake,
+        )?;
+
+        emit!(ProofCreated {
+            proof: proof.key(),
+            algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+            title: None,
+            timestamp: proof.timestamp,
+        });
+        emit!(ProofEscrowed {
+            proof: proof.key(),
+            stake,
+            finalized_at: escrow.finalized_at,
+        });
+        Ok(())
+    }
+
+    /// Dispute a challengeable proof's timestamp by matching its stake.
+    /// Only one challenge per proof, and only before `finalized_at`.
+    pub fn challenge_proof(ctx: Context<ChallengeProof>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < escrow.finalized_at, VoidError::ChallengeWindowClosed);
+        require!(escrow.challenger.is_none(), VoidError::AlreadyChallenged);
+        escrow.challenger = Some(ctx.accounts.challenger.key());
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.challenger.to_account_info(),
+                    to: escrow.to_account_info(),
+                },
+            ),
+            escrow.stake,
+        )?;
+
+        emit!(ProofChallenged {
+            proof: escrow.proof,
+            challenger: ctx.accounts.challenger.key(),
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Release an unchallenged stake and the escrow's rent to the owner once the
+    /// challenge window has passed (owner only).
+    pub fn finalize_proof(ctx: Context<FinalizeProof>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        require!(
+            Clock::get()?.unix_timestamp >= escrow.finalized_at,
+            VoidError::ChallengeWindowOpen
+        );
+        require!(escrow.challenger.is_none(), VoidError::ProofUnderChallenge);
+
+        emit!(ProofFinalized {
+            proof: escrow.proof,
+            owner: escrow.owner,
+            stake: escrow.stake,
+        });
+        Ok(())
+    }
+
+    /// Settle a challenged proof after the off-chain dispute (protocol authority
+    /// only). The winner takes both stakes and the escrow's rent: the challenger
+    /// if the challenge is `upheld`, the owner otherwise.
+    pub fn resolve_challenge(ctx: Context<ResolveChallenge>, upheld: bool) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let winner = if upheld {
+            ctx.accounts.challenger.to_account_info()
+        } else {
+            ctx.accounts.owner.to_account_info()
+        };
+
+        emit!(ChallengeResolved {
+            proof: escrow.proof,
+            winner: winner.key(),
+            upheld,
+        });
+        escrow.close(winner)
+    }
+
+    /// Close a proof and refund its rent to the owner (owner only).
+    /// The PDA is freed, so the same digest can be stamped again afterward.
+    pub fn close_proof(ctx: Context<CloseProof>) -> Result<()> {
+        let proof = &ctx.accounts.proof;
+        emit!(ProofClosed {
+            proof: proof.key(),
+            algo: proof.algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+        });
+        Ok(())
+    }
+
+    /// Hand a proof to a new custodian (current owner only).
+    /// The original timestamp is kept; `transferred_at` records the handoff.
+    pub fn transfer_proof(ctx: Context<TransferProof>, new_owner: Pubkey) -> Result<()> {
+        let proof = &mut ctx.accounts.proof;
+        let previous_owner = proof.owner;
+        proof.owner = new_owner;
+        proof.transferred_at = Clock::get()?.unix_timestamp;
+
+        emit!(ProofTransferred {
+            proof: proof.key(),
+            previous_owner,
+            new_owner,
+            timestamp: proof.transferred_at,
+        });
+        Ok(())
+    }
+
+    /// Upgrade a proof stamped under an older layout to the current one: grow the
+    /// account to `Proof::SPACE`, leave the fields its layout lacks unset and stamp
+    /// `CURRENT_SCHEMA`. Permissionless, so anyone checking a document against an
+    /// old proof can upgrade it; the payer covers the extra rent. Current proofs
+    /// are left untouched, so the call is safe to repeat.
+    pub fn migrate_proof(ctx: Context<MigrateProof>) -> Result<()> {
+        let info = ctx.accounts.proof.to_account_info();
+        if info.data_len() == Proof::SPACE {
+            return Ok(());
+        }
+        let layout = legacy_layout(&Proof::LEGACY_LAYOUTS, info.data_len())?;
+        let mut proof = {
+            let data = info.try_borrow_data()?;
+            require!(data.starts_with(Proof::DISCRIMINATOR), VoidError::NotAProof);
+            Proof::read_legacy(&mut &data[8..], layout)?
+        };
+        require!(proof.schema_version >= layout, VoidError::UnknownLayout);
+        let from_version = proof.schema_version;
+        proof.schema_version = CURRENT_SCHEMA;
+
+        grow_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            Proof::SPACE,
+            0,
+        )?;
+        proof.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(ProofMigrated {
+            proof: info.key(),
+            owner: proof.owner,
+            from_version,
+            to_version: CURRENT_SCHEMA,
+        });
+        Ok(())
+    }
+
+    /// Stamp a digest now and bind it to an identity later. The stamping wallet
+    /// (typically a throwaway) owns the proof until someone calls `claim_proof`
+    /// with the secret whose sha256 is `claim_secret_hash`. Uses the same global
+    /// PDA as `create_proof`.
+    pub fn create_claimable_proof(
+        ctx: Context<CreateClaimableProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        claim_secret_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_digest(algo, &digest)?;
+        let proof_id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+
+        let proof = &mut ctx.accounts.proof;
+        proof.init(proof_id, algo, digest, ctx.accounts.owner.key(), ctx.bumps.proof)?;
+        proof.claim_secret_hash = Some(claim_secret_hash);
+
+        emit!(ProofCreated {
+            proof: proof.key(),
+            algo,
+            digest: proof.digest.clone(),
+            owner: proof.owner,
+            title: None,
+            timestamp: proof.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Take ownership of a claimable proof by revealing the secret behind its
+    /// `claim_secret_hash` (commit-reveal). The commitment is cleared, so a proof
+    /// can only be claimed once. The secret becomes public in the transaction.
+    pub fn claim_proof(ctx: Context<ClaimProof>, secret: [u8; 32]) -> Result<()> {
         let proof = &mut ctx.accounts.proof;
-        proof.hash = hash;
-        proof.owner = ctx.accounts.owner.key();
-        proof.timestamp = Clock::get()?.unix_timestamp;
-        proof.bump = ctx.bumps.proof;
+        let commitment = proof.claim_secret_hash.ok_or(VoidError::NotClaimable)?;
+        require!(
+            hashv(&[&secret]).to_bytes() == commitment,
+            VoidError::InvalidClaimSecret
+        );
+        let previous_owner = proof.owner;
+        proof.owner = ctx.accounts.claimer.key();
+        proof.claim_secret_hash = None;
+        proof.transferred_at = Clock::get()?.unix_timestamp;
+
+        emit!(ProofClaimed {
+            proof: proof.key(),
+            previous_owner,
+            claimer: proof.owner,
+            timestamp: proof.transferred_at,
+        });
+        Ok(())
+    }
+
+    /// Stamp up to `MAX_BULK_PROOFS` 32-byte digests in one transaction, each as its
+    /// own global `Proof` so it can be verified and transferred individually.
+    /// The proof PDAs are passed as writable `remaining_accounts` in the same order
+    /// as `hashes`, and each must match the PDA `create_proof` would derive.
+    /// Proofs are plain: no metadata, title or attestation.
+    pub fn create_proofs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateProofs<'info>>,
+        algo: HashAlgo,
+        hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        require!(!hashes.is_empty(), VoidError::EmptyBatch);
+        require!(hashes.len() <= MAX_BULK_PROOFS, VoidError::TooManyProofs);
+        require!(
+            ctx.remaining_accounts.len() == hashes.len(),
+            VoidError::ProofAccountMismatch
+        );
+
+        let owner = &ctx.accounts.owner;
+        let rent = Rent::get()?.minimum_balance(Proof::SPACE);
+
+        for (hash, proof_info) in hashes.iter().zip(ctx.remaining_accounts) {
+            check_digest(algo, hash)?;
+            let (proof_key, bump) =
+                Pubkey::find_program_address(&seeds::proof_seeds(algo, hash), ctx.program_id);
+            require_keys_eq!(proof_info.key(), proof_key, VoidError::ProofAccountMismatch);
+
+            create_pda(
+                owner,
+                proof_info,
+                &ctx.accounts.system_program,
+                Proof::SPACE,
+                rent,
+                &[seeds::PROOF, &[algo as u8], hash, &[], &[bump]],
+            )?;
+
+            // A freshly allocated account is zeroed, which decodes as an empty proof
+            let mut proof = Proof::try_deserialize_unchecked(&mut &proof_info.try_borrow_data()?[..])?;
+            let id = next_proof_id(&mut ctx.accounts.registry, ctx.bumps.registry)?;
+            proof.init(id, algo, hash.to_vec(), owner.key(), bump)?;
+            proof.try_serialize(&mut &mut proof_info.try_borrow_mut_data()?[..])?;
+
+            emit!(ProofCreated {
+                proof: proof_key,
+                algo,
+                digest: proof.digest,
+                owner: proof.owner,
+                title: None,
+                timestamp: proof.timestamp,
+            });
+        }
+        Ok(())
+    }
+
+    /// Stamp a whole batch of files at once by storing the Merkle root of their hashes.
+    /// Individual files are later proven with `verify_inclusion` instead of
+    /// needing a dedicated account per file.
+    pub fn create_batch_proof(
+        ctx: Context<CreateBatchProof>,
+        merkle_root: [u8; 32],
+        leaf_count: u32,
+    ) -> Result<()> {
+        require!(leaf_count > 0, VoidError::EmptyBatch);
+
+        let batch = &mut ctx.accounts.batch;
+        batch.merkle_root = merkle_root;
+        batch.leaf_count = leaf_count;
+        batch.owner = ctx.accounts.owner.key();
+        batch.timestamp = Clock::get()?.unix_timestamp;
+        batch.bump = ctx.bumps.batch;
+
+        emit!(BatchProofCreated {
+            batch: batch.key(),
+            merkle_root,
+            leaf_count,
+            owner: batch.owner,
+            timestamp: batch.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Check that `leaf` sits at position `index` in a stamped batch.
+    /// `proof` is the list of sibling hashes from the leaf up to the root.
+    /// Fails if the recomputed root doesn't match the stored one.
+    pub fn verify_inclusion(
+        ctx: Context<VerifyInclusion>,
+        leaf: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        index: u32,
+    ) -> Result<()> {
+        let batch = &ctx.accounts.batch;
+        require!(index < batch.leaf_count, VoidError::LeafIndexOutOfRange);
+        require!(proof.len() <= MAX_MERKLE_DEPTH, VoidError::MerkleProofTooLong);
+        require!(
+            compute_merkle_root(leaf, &proof, index) == batch.merkle_root,
+            VoidError::InvalidMerkleProof
+        );
+        Ok(())
+    }
+
+    /// Confirm a digest has a global proof and return who stamped it and when.
+    /// Read-only; call with `.view()`. If the digest was never stamped, account
+    /// resolution fails with `AccountNotInitialized` instead of an RPC "not found".
+    pub fn assert_proof(
+        ctx: Context<AssertProof>,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+    ) -> Result<ProofStatus> {
+        check_digest(algo, &digest)?;
+        let proof = &ctx.accounts.proof;
+        Ok(ProofStatus {
+            owner: proof.owner,
+            timestamp: proof.timestamp,
+        })
+    }
+
+    /// Record that the signer verified `proof`. The proof's `VerifyLog` keeps the
+    /// last `MAX_VERIFY_LOG` verifiers and overwrites the oldest entry once full.
+    /// Opt-in: verifying a proof never requires this, and the first verifier to
+    /// record pays to create the log.
+    pub fn log_verify(ctx: Context<LogVerify>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let verifier = ctx.accounts.verifier.key();
+        let log = &mut ctx.accounts.verify_log;
+        log.proof = ctx.accounts.proof.key();
+        log.bump = ctx.bumps.verify_log;
+        log.entries[(log.total % MAX_VERIFY_LOG as u64) as usize] = VerifyEntry {
+            verifier,
+            timestamp: now,
+        };
+        log.total += 1;
+
+        emit!(ProofVerifyLogged {
+            proof: log.proof,
+            verifier,
+            timestamp: now,
+        });
         Ok(())
     }
 
@@ -31,431 +899,8314 @@ pub mod void_protocol {
     /// The org admin provides a name, description, URL slug, and their ECDH public
     /// key. Anyone can encrypt messages to this public key, but only the admin
     /// (who holds the private key) can decrypt them.
+    /// A non-zero `submission_fee` (lamports) is charged on every tip to deter spam,
+    /// and a non-zero `cooldown_secs` limits how often one wallet can submit.
+    /// Submissions are accepted from `submission_open_at` until `submission_close_at`;
+    /// 0 leaves that side of the window unbounded.
+    /// Slugs are 3-32 chars and descriptions at least 8.
+    /// The slug must already be normalized (lowercase `[a-z0-9-]`, no leading or
+    /// trailing hyphen): it is a PDA seed, so clients must lowercase it before
+    /// deriving the org address.
+    /// `encryption_keys` lists 1-3 keys held by different editors; submitters seal
+    /// each tip to all of them, so no single editor is a decryption bottleneck.
+    /// `key_algo` declares the curve of every key and fixes how many of its
+    /// 65 bytes are used; see `KeyAlgo`.
+    /// `liveness_timeout` arms a dead man's switch: once the admin has been silent
+    /// that many seconds, anyone can deactivate the org with `check_org_liveness`.
+    /// Each org takes the next `index` from the `OrgRegistry` counter, so indexers
+    /// can enumerate orgs in creation order without a program-account scan.
+    /// `max_hash_len` caps the storage locators the org accepts, clamped to
+    /// `MAX_LOCATOR_LEN` (0 = `MAX_LOCATOR_LEN`); submission accounts are allocated
+    /// for exactly that, so orgs on short-ID backends pay less rent per tip.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_organization(
         ctx: Context<CreateOrganization>,
         slug: String,
         name: String,
         description: String,
-        encryption_key: [u8; 65],
+        encryption_keys: Vec<[u8; 65]>,
+        key_algo: KeyAlgo,
+        submission_fee: u64,
+        cooldown_secs: u32,
+        submission_open_at: i64,
+        submission_close_at: i64,
+        liveness_timeout: u32,
+        max_hash_len: u16,
     ) -> Result<()> {
         require!(slug.len() <= MAX_SLUG_LEN, VoidError::SlugTooLong);
         require!(name.len() <= MAX_NAME_LEN, VoidError::NameTooLong);
         require!(description.len() <= MAX_DESC_LEN, VoidError::DescriptionTooLong);
+        require!(description.len() >= MIN_DESC_LEN, VoidError::DescriptionTooShort);
         require!(!slug.is_empty(), VoidError::SlugEmpty);
+        require!(slug.len() >= MIN_SLUG_LEN, VoidError::SlugTooShort);
+        check_slug(&slug)?;
+        check_submission_window(submission_open_at, submission_close_at)?;
 
         let org = &mut ctx.accounts.organization;
+        org.handle = slug.clone();
         org.slug = slug;
         org.name = name;
         org.description = description;
-        org.encryption_key = encryption_key;
+        org.guidelines = None;
+        org.key_algo = key_algo;
+        set_org_keys(org, &encryption_keys)?;
+        org.key_version = 0;
+        org.key_rotated_at = 0;
         org.admin = ctx.accounts.admin.key();
+        org.pending_admin = None;
+        org.submission_fee = submission_fee;
+        org.fee_mint = None;
+        org.fee_amount = 0;
+        org.cooldown_secs = cooldown_secs;
+        org.window_secs = 0;
+        org.window_limit = 0;
+        org.window_start = 0;
+        org.window_count = 0;
+        org.ip_window_secs = 0;
+        org.ip_window_limit = 0;
+        org.allowlist_only = false;
+        org.require_proof = false;
+        org.validator_program = None;
+        org.dedup = false;
+        org.max_hash_len = match max_hash_len {
+            0 => MAX_LOCATOR_LEN as u16,
+            len => len.min(MAX_LOCATOR_LEN as u16),
+        };
+        org.max_submissions = 0;
+        org.submission_open_at = submission_open_at;
+        org.submission_close_at = submission_close_at;
         org.submission_count = 0;
+        org.open_submissions = 0;
+        org.total_paid = 0;
+        let registry = &mut ctx.accounts.org_registry;
+        registry.bump = ctx.bumps.org_registry;
+        org.index = registry.org_count;
+        registry.org_count = registry
+            .org_count
+            .checked_add(1)
+            .ok_or(VoidError::CounterOverflow)?;
         org.created_at = Clock::get()?.unix_timestamp;
+        org.last_admin_activity = org.created_at;
+        org.liveness_timeout = liveness_timeout;
         org.active = true;
+        org.verified = false;
+        org.categories = [u8::MAX; 8];
+        org.schema_version = CURRENT_SCHEMA;
         org.bump = ctx.bumps.organization;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.organization = org.key();
+        treasury.bump = ctx.bumps.treasury;
+
+        emit!(OrganizationCreated {
+            organization: org.key(),
+            index: org.index,
+            slug: org.slug.clone(),
+            admin: org.admin,
+            key_fingerprint: org.key_fingerprint,
+            timestamp: org.created_at,
+        });
         Ok(())
     }
 
     /// Submit an encrypted tip to an organization.
-    /// The arweave_hash points to the encrypted payload stored on Arweave.
+    /// `storage` points to the encrypted payload on Arweave, IPFS or Filecoin.
     /// The submitter can be a throwaway wallet or our backend wallet (for anonymous subs).
-    pub fn submit_tip(
-        ctx: Context<SubmitTip>,
-        arweave_hash: String,
+    /// If the org charges a submission fee it is paid into the org treasury in the
+    /// same instruction, so a failed payment rolls back the whole tip.
+    /// Orgs with a `fee_mint` also collect `fee_amount` tokens from the submitter's
+    /// token account; the token accounts can be omitted for orgs without one.
+    /// Each wallet must wait `cooldown_secs` between tips to the same org.
+    /// Allowlist-only orgs also require the submitter's `allowlist` PDA.
+    /// With `expires_at` set, anyone can delete the submission once that time
+    /// passes, so the org must copy the content elsewhere before then to keep it.
+    /// `category` (0-63) must be one the org accepts in its `categories` bitmask.
+    /// `content_hash` is the SHA-256 of the encrypted payload; the org recomputes it
+    /// after download and rejects content that doesn't match.
+    /// When the relayer submits to an org with a per-IP limit, it must pass `ip_hash`
+    /// (a salted hash of the source's IP) and the matching `ip_rate_state` PDA.
+    /// A `proof` of the evidence (stamped by anyone) can be attached and is recorded
+    /// on the submission; orgs with `require_proof` reject tips without one.
+    /// Orgs with a `validator_program` have it approve the tip first; pass that
+    /// program as `validator_program` and whatever accounts it reads as
+    /// `remaining_accounts`. See `call_validator` for the interface.
+    /// Orgs with `dedup` on need `submission_dedup` = PDA of
+    /// `[b"subdedup", organization, sha256(storage.locator)]`; a locator the org
+    /// has already received fails with `DuplicateSubmission`.
+    pub fn submit_tip<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTip<'info>>,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+        expires_at: Option<i64>,
+        category: u8,
+        ip_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        require!(arweave_hash.len() <= MAX_ARWEAVE_HASH_LEN, VoidError::ArweaveHashTooLong);
-
-        let org = &mut ctx.accounts.organization;
-        require!(org.active, VoidError::OrgInactive);
+        check_locator(&storage)?;
+        check_org_locator(&ctx.accounts.organization, &storage)?;
+        let validated = validate_tip(
+            &ctx.accounts.organization,
+            ctx.accounts.validator_program.as_ref(),
+            &ctx.accounts.submitter.to_account_info(),
+            ctx.remaining_accounts,
+            content_hash,
+            category,
+        )?;
+        let (submission_id, now) = admit_tip(
+            TipAccounts {
+                config: &ctx.accounts.config,
+                organization: &mut ctx.accounts.organization,
+                treasury: &ctx.accounts.treasury,
+                submitter_state: &mut ctx.accounts.submitter_state,
+                global_index: &mut ctx.accounts.global_index,
+                allowlisted: ctx.accounts.allowlist.is_some(),
+                has_proof: ctx.accounts.proof.is_some(),
+                validated,
+                deduplicated: ctx.accounts.submission_dedup.is_some(),
+                payer: Some(&ctx.accounts.submitter),
+                fee_mint: ctx.accounts.fee_mint.as_ref(),
+                submitter_token_account: ctx.accounts.submitter_token_account.as_ref(),
+                org_fee_account: ctx.accounts.org_fee_account.as_ref(),
+                token_program: ctx.accounts.token_program.as_ref(),
+                protocol_treasury: ctx.accounts.protocol_treasury.as_ref(),
+                system_program: &ctx.accounts.system_program,
+            },
+            ctx.bumps.submitter_state,
+            ctx.bumps.global_index,
+            category,
+        )?;
+        let org = &ctx.accounts.organization;
+        if ctx.accounts.submitter.key() == ctx.accounts.config.relayer {
+            admit_ip(org, ctx.accounts.ip_rate_state.as_mut(), ip_hash, ctx.bumps.ip_rate_state, now)?;
+        }
 
-        let submission_id = org.submission_count;
-        org.submission_count += 1;
+        record_dedup(
+            org,
+            ctx.accounts.submission_dedup.as_mut(),
+            submission_id,
+            ctx.bumps.submission_dedup,
+        )?;
 
         let sub = &mut ctx.accounts.submission;
         sub.id = submission_id;
         sub.organization = org.key();
-        sub.arweave_hash = arweave_hash;
+        sub.storage = storage;
+        sub.content_hash = content_hash;
+        sub.key_version = org.key_version;
         sub.submitter = ctx.accounts.submitter.key();
-        sub.timestamp = Clock::get()?.unix_timestamp;
+        sub.via_relay = sub.submitter == ctx.accounts.config.relayer;
+        sub.timestamp = now;
+        sub.expires_at = expires_at;
+        sub.category = category;
+        sub.status = SubmissionStatus::New;
+        sub.schema_version = CURRENT_SCHEMA;
+        sub.parent_id = None;
+        sub.frozen = false;
+        sub.rent_recipient = Some(ctx.accounts.submitter.key());
+        sub.proof = ctx.accounts.proof.as_ref().map(|proof| proof.key());
+        sub.read_at = None;
+        sub.replied_at = None;
+        sub.resolved_at = None;
         sub.bump = ctx.bumps.submission;
+
+        emit!(TipSubmitted {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+            submitter: sub.submitter,
+            timestamp: sub.timestamp,
+        });
         Ok(())
     }
 
-    /// Deactivate an organization (admin only). Prevents new submissions.
-    pub fn deactivate_organization(ctx: Context<DeactivateOrganization>) -> Result<()> {
-        ctx.accounts.organization.active = false;
+    /// Submit a tip authored by `source_pubkey` but paid for and sent by a relayer.
+    /// The source signs
+    /// `organization || id || storage.kind || category || content_hash || proof || storage.locator`
+    /// off-chain, where `id` is the org's next submission ID as a little-endian u64
+    /// and `proof` is the attached `Proof` account's key (32 zero bytes for none);
+    /// the relayer puts an ed25519 program instruction verifying that signature
+    /// immediately before this one. Signing the ID binds the signature to a single
+    /// submission, so a relayer can't replay it. The submission records the source
+    /// as its submitter, and cooldowns and allowlists apply to the source, so the
+    /// source never needs SOL. Delegated tips are always relayed, so orgs with a
+    /// per-IP limit also need `ip_hash` and its `ip_rate_state`, as in `submit_tip`.
+    /// An org's validator program sees the relayer as the signer.
+    pub fn submit_tip_delegated<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTipDelegated<'info>>,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+        category: u8,
+        source_sig: [u8; 64],
+        source_pubkey: [u8; 32],
+        ip_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        check_locator(&storage)?;
+        check_org_locator(&ctx.accounts.organization, &storage)?;
+        let org = &ctx.accounts.organization;
+        let proof = ctx.accounts.proof.as_ref().map(|proof| proof.key());
+        let message = [
+            org.key().as_ref(),
+            &org.submission_count.to_le_bytes(),
+            &[storage.kind as u8, category],
+            &content_hash,
+            proof.unwrap_or_default().as_ref(),
+            storage.locator.as_bytes(),
+        ]
+        .concat();
+        verify_ed25519_ix(&ctx.accounts.instructions, &source_pubkey, &source_sig, &message)
+            .map_err(|_| error!(VoidError::InvalidDelegateSignature))?;
+        let validated = validate_tip(
+            &ctx.accounts.organization,
+            ctx.accounts.validator_program.as_ref(),
+            &ctx.accounts.relayer.to_account_info(),
+            ctx.remaining_accounts,
+            content_hash,
+            category,
+        )?;
+
+        let (submission_id, now) = admit_tip(
+            TipAccounts {
+                config: &ctx.accounts.config,
+                organization: &mut ctx.accounts.organization,
+                treasury: &ctx.accounts.treasury,
+                submitter_state: &mut ctx.accounts.submitter_state,
+                global_index: &mut ctx.accounts.global_index,
+                allowlisted: ctx.accounts.allowlist.is_some(),
+                has_proof: proof.is_some(),
+                validated,
+                deduplicated: ctx.accounts.submission_dedup.is_some(),
+                payer: Some(&ctx.accounts.relayer),
+                fee_mint: ctx.accounts.fee_mint.as_ref(),
+                submitter_token_account: ctx.accounts.relayer_token_account.as_ref(),
+                org_fee_account: ctx.accounts.org_fee_account.as_ref(),
+                token_program: ctx.accounts.token_program.as_ref(),
+                protocol_treasury: ctx.accounts.protocol_treasury.as_ref(),
+                system_program: &ctx.accounts.system_program,
+            },
+            ctx.bumps.submitter_state,
+            ctx.bumps.global_index,
+            category,
+        )?;
+        let org = &ctx.accounts.organization;
+        admit_ip(org, ctx.accounts.ip_rate_state.as_mut(), ip_hash, ctx.bumps.ip_rate_state, now)?;
+        record_dedup(
+            org,
+            ctx.accounts.submission_dedup.as_mut(),
+            submission_id,
+            ctx.bumps.submission_dedup,
+        )?;
+
+        let sub = &mut ctx.accounts.submission;
+        sub.id = submission_id;
+        sub.organization = org.key();
+        sub.storage = storage;
+        sub.content_hash = content_hash;
+        sub.key_version = org.key_version;
+        sub.submitter = Pubkey::new_from_array(source_pubkey);
+        sub.via_relay = true;
+        sub.timestamp = now;
+        sub.expires_at = None;
+        sub.category = category;
+        sub.status = SubmissionStatus::New;
+        sub.schema_version = CURRENT_SCHEMA;
+        sub.parent_id = None;
+        sub.frozen = false;
+        sub.rent_recipient = Some(ctx.accounts.relayer.key());
+        sub.proof = proof;
+        sub.read_at = None;
+        sub.replied_at = None;
+        sub.resolved_at = None;
+        sub.bump = ctx.bumps.submission;
+
+        emit!(TipSubmitted {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+            submitter: sub.submitter,
+            timestamp: sub.timestamp,
+        });
         Ok(())
     }
 
-    // ─── VOID BURN ───────────────────────────────────────────────
-
-    /// Activate an inbox for wallet-to-wallet encrypted messaging.
-    /// The user provides their derived encryption public key (65 bytes).
-    /// This key is derived client-side from a wallet signature.
-    pub fn activate_inbox(
-        ctx: Context<ActivateInbox>,
-        encryption_key: [u8; 65],
+    /// Submit a tip to a verified org that pays for it. The protocol relayer signs
+    /// as fee payer and fronts the rent, and is reimbursed from the org's
+    /// `SponsorVault`; the submitter only signs, so it can be a fresh keypair with
+    /// no SOL. Sponsored tips skip the org's submission and token fees, and the
+    /// reimbursed rent counts towards the vault's `cap`. They are always relayed,
+    /// so orgs with a per-IP limit also need `ip_hash` and its `ip_rate_state`.
+    pub fn submit_tip_sponsored<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTipSponsored<'info>>,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+        category: u8,
+        ip_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        let inbox = &mut ctx.accounts.inbox;
-        inbox.owner = ctx.accounts.owner.key();
-        inbox.encryption_key = encryption_key;
-        inbox.message_count = 0;
-        inbox.created_at = Clock::get()?.unix_timestamp;
-        inbox.bump = ctx.bumps.inbox;
+        check_locator(&storage)?;
+        check_org_locator(&ctx.accounts.organization, &storage)?;
+        require!(ctx.accounts.organization.verified, VoidError::OrgNotVerified);
+        let validated = validate_tip(
+            &ctx.accounts.organization,
+            ctx.accounts.validator_program.as_ref(),
+            &ctx.accounts.submitter.to_account_info(),
+            ctx.remaining_accounts,
+            content_hash,
+            category,
+        )?;
+        let first_tip = ctx.accounts.submitter_state.last_submission_ts == 0;
+        let first_ip_tip = ctx
+            .accounts
+            .ip_rate_state
+            .as_ref()
+            .is_some_and(|state| state.window_start == 0);
+        let first_dedup = ctx
+            .accounts
+            .submission_dedup
+            .as_ref()
+            .is_some_and(|record| record.organization == Pubkey::default());
+        let (submission_id, now) = admit_tip(
+            TipAccounts {
+                config: &ctx.accounts.config,
+                organization: &mut ctx.accounts.organization,
+                treasury: &ctx.accounts.treasury,
+                submitter_state: &mut ctx.accounts.submitter_state,
+                global_index: &mut ctx.accounts.global_index,
+                allowlisted: ctx.accounts.allowlist.is_some(),
+                has_proof: ctx.accounts.proof.is_some(),
+                validated,
+                deduplicated: ctx.accounts.submission_dedup.is_some(),
+                payer: None,
+                fee_mint: None,
+                submitter_token_account: None,
+                org_fee_account: None,
+                token_program: None,
+                protocol_treasury: None,
+                system_program: &ctx.accounts.system_program,
+            },
+            ctx.bumps.submitter_state,
+            ctx.bumps.global_index,
+            category,
+        )?;
+        admit_ip(
+            &ctx.accounts.organization,
+            ctx.accounts.ip_rate_state.as_mut(),
+            ip_hash,
+            ctx.bumps.ip_rate_state,
+            now,
+        )?;
+        record_dedup(
+            &ctx.accounts.organization,
+            ctx.accounts.submission_dedup.as_mut(),
+            submission_id,
+            ctx.bumps.submission_dedup,
+        )?;
+
+        // Refund the rent the relayer fronted, including the submitter's cooldown
+        // record on their first tip to this org, the source IP's window record and
+        // the locator's dedup record
+        let rent = Rent::get()?;
+        let mut sponsored = rent.minimum_balance(Submission::space(usize::from(
+            ctx.accounts.organization.max_hash_len,
+        )));
+        if first_tip {
+            sponsored += rent.minimum_balance(SubmitterState::SPACE);
+        }
+        if first_ip_tip && ctx.accounts.organization.ip_window_limit > 0 {
+            sponsored += rent.minimum_balance(IpRateState::SPACE);
+        }
+        if first_dedup && ctx.accounts.organization.dedup {
+            sponsored += rent.minimum_balance(SubmissionDedup::SPACE);
+        }
+        let sponsor = &mut ctx.accounts.sponsor;
+        let spent = sponsor
+            .spent
+            .checked_add(sponsored)
+            .ok_or(VoidError::CounterOverflow)?;
+        require!(
+            sponsor.cap == 0 || spent <= sponsor.cap,
+            VoidError::SponsorCapReached
+        );
+        let rent_floor = rent.minimum_balance(sponsor.to_account_info().data_len());
+        require!(
+            sponsored <= sponsor.get_lamports().saturating_sub(rent_floor),
+            VoidError::InsufficientSponsorFunds
+        );
+        sponsor.spent = spent;
+        sponsor.sub_lamports(sponsored)?;
+        ctx.accounts.relayer.add_lamports(sponsored)?;
+
+        let org = &ctx.accounts.organization;
+        let sub = &mut ctx.accounts.submission;
+        sub.id = submission_id;
+        sub.organization = org.key();
+        sub.storage = storage;
+        sub.content_hash = content_hash;
+        sub.key_version = org.key_version;
+        sub.submitter = ctx.accounts.submitter.key();
+        sub.via_relay = true;
+        sub.timestamp = now;
+        sub.expires_at = None;
+        sub.category = category;
+        sub.status = SubmissionStatus::New;
+        sub.schema_version = CURRENT_SCHEMA;
+        sub.parent_id = None;
+        sub.frozen = false;
+        sub.rent_recipient = Some(ctx.accounts.sponsor.key());
+        sub.proof = ctx.accounts.proof.as_ref().map(|proof| proof.key());
+        sub.read_at = None;
+        sub.replied_at = None;
+        sub.resolved_at = None;
+        sub.bump = ctx.bumps.submission;
+
+        emit!(TipSubmitted {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+            submitter: sub.submitter,
+            timestamp: sub.timestamp,
+        });
+        emit!(TipSponsored {
+            organization: sub.organization,
+            submission: sub.key(),
+            amount: sponsored,
+        });
         Ok(())
     }
 
-    /// Send an encrypted direct message to another wallet.
-    /// Recipient must have an activated inbox.
-    pub fn send_direct_message(
-        ctx: Context<SendDirectMessage>,
-        arweave_hash: String,
-        burn_after_reading: bool,
+    /// Submit a tip into the fixed-size `SubmissionZC` layout.
+    /// Same rules, fees and ID sequence as `submit_tip`, but the account is written
+    /// in place instead of Borsh-serialized, which costs fewer compute units for
+    /// high-volume orgs. Locators are capped at `MAX_ZC_LOCATOR_LEN` bytes. No
+    /// `content_hash` is stored, so a swapped upload can't be detected on-chain;
+    /// orgs that need that should have sources use `submit_tip`. Relayed tips take
+    /// `ip_hash` and its `ip_rate_state` as in `submit_tip`. A `proof` satisfies an
+    /// org's `require_proof`, but the fixed layout has no room to record it.
+    pub fn submit_tip_zc<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTipZc<'info>>,
+        storage: StoragePointer,
+        category: u8,
+        ip_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        require!(arweave_hash.len() <= MAX_ARWEAVE_HASH_LEN, VoidError::ArweaveHashTooLong);
+        check_locator(&storage)?;
+        check_org_locator(&ctx.accounts.organization, &storage)?;
+        let locator_len = storage.locator.len();
+        require!(
+            locator_len <= MAX_ZC_LOCATOR_LEN,
+            VoidError::ZcLocatorTooLong
+        );
+        let validated = validate_tip(
+            &ctx.accounts.organization,
+            ctx.accounts.validator_program.as_ref(),
+            &ctx.accounts.submitter.to_account_info(),
+            ctx.remaining_accounts,
+            [0; 32],
+            category,
+        )?;
+        let (submission_id, now) = admit_tip(
+            TipAccounts {
+                config: &ctx.accounts.config,
+                organization: &mut ctx.accounts.organization,
+                treasury: &ctx.accounts.treasury,
+                submitter_state: &mut ctx.accounts.submitter_state,
+                global_index: &mut ctx.accounts.global_index,
+                allowlisted: ctx.accounts.allowlist.is_some(),
+                has_proof: ctx.accounts.proof.is_some(),
+                validated,
+                deduplicated: ctx.accounts.submission_dedup.is_some(),
+                payer: Some(&ctx.accounts.submitter),
+                fee_mint: ctx.accounts.fee_mint.as_ref(),
+                submitter_token_account: ctx.accounts.submitter_token_account.as_ref(),
+                org_fee_account: ctx.accounts.org_fee_account.as_ref(),
+                token_program: ctx.accounts.token_program.as_ref(),
+                protocol_treasury: ctx.accounts.protocol_treasury.as_ref(),
+                system_program: &ctx.accounts.system_program,
+            },
+            ctx.bumps.submitter_state,
+            ctx.bumps.global_index,
+            category,
+        )?;
+        let org = &ctx.accounts.organization;
+        if ctx.accounts.submitter.key() == ctx.accounts.config.relayer {
+            admit_ip(org, ctx.accounts.ip_rate_state.as_mut(), ip_hash, ctx.bumps.ip_rate_state, now)?;
+        }
 
-        let recipient_inbox = &mut ctx.accounts.recipient_inbox;
-        let message_id = recipient_inbox.message_count;
-        recipient_inbox.message_count += 1;
+        record_dedup(
+            org,
+            ctx.accounts.submission_dedup.as_mut(),
+            submission_id,
+            ctx.bumps.submission_dedup,
+        )?;
 
-        let msg = &mut ctx.accounts.message;
-        msg.id = message_id;
-        msg.sender = ctx.accounts.sender.key();
-        msg.recipient = recipient_inbox.owner;
-        msg.arweave_hash = arweave_hash;
-        msg.burn_after_reading = burn_after_reading;
-        msg.burned = false;
-        msg.timestamp = Clock::get()?.unix_timestamp;
-        msg.bump = ctx.bumps.message;
+        let mut sub = ctx.accounts.submission.load_init()?;
+        sub.id = submission_id;
+        sub.timestamp = now;
+        sub.organization = org.key();
+        sub.submitter = ctx.accounts.submitter.key();
+        sub.via_relay = u8::from(sub.submitter == ctx.accounts.config.relayer);
+        sub.locator[..locator_len].copy_from_slice(storage.locator.as_bytes());
+        sub.key_version = org.key_version;
+        sub.locator_len = locator_len as u8;
+        sub.storage_kind = storage.kind as u8;
+        sub.status = SubmissionStatus::New as u8;
+        sub.category = category;
+        sub.bump = ctx.bumps.submission;
+
+        emit!(TipSubmitted {
+            submission: ctx.accounts.submission.key(),
+            organization: sub.organization,
+            id: sub.id,
+            submitter: sub.submitter,
+            timestamp: sub.timestamp,
+        });
         Ok(())
     }
 
-    /// Mark a message as burned (recipient only).
-    /// Once burned, the message reference is flagged and cannot be "unburned".
-    pub fn burn_message(ctx: Context<BurnMessage>) -> Result<()> {
-        let msg = &mut ctx.accounts.message;
-        require!(!msg.burned, VoidError::AlreadyBurned);
-        msg.burned = true;
-        Ok(())
+    /// Close a processed submission (admin only), refunding its rent to its
+    /// `rent_recipient`, or to the admin for submissions that predate one.
+    /// `submission_count` is left untouched, so submission IDs are never reused and
+    /// the closed PDA cannot be re-created by a later tip. Frozen submissions
+    /// must be thawed first.
+    pub fn close_submission(ctx: Context<CloseSubmission>) -> Result<()> {
+        require!(!ctx.accounts.submission.frozen, VoidError::SubmissionFrozen);
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        release_submission(&mut ctx.accounts.organization)?;
+        let sub = &ctx.accounts.submission;
+        emit!(SubmissionClosed {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+        });
+        sub.close(rent_destination(
+            sub.rent_recipient,
+            ctx.accounts.admin.to_account_info(),
+            ctx.accounts.rent_recipient.as_deref(),
+        )?)
     }
 
-    // ─── VOID FEED (VOUCH) ─────────────────────────────────────────
+    /// Withdraw a submission the org hasn't triaged yet (submitter only), refunding
+    /// its rent to its `rent_recipient`, or to the submitter for submissions that
+    /// predate one. Once the org moves it out of `New` it stays.
+    pub fn cancel_submission(ctx: Context<CancelSubmission>) -> Result<()> {
+        let sub = &ctx.accounts.submission;
+        require!(!sub.frozen, VoidError::SubmissionFrozen);
+        require!(
+            sub.status == SubmissionStatus::New,
+            VoidError::SubmissionAlreadyProcessed
+        );
+        release_submission(&mut ctx.accounts.organization)?;
 
-    /// Vouch for an article. The content_hash is a SHA-256 of the article URL.
-    /// Each wallet can only vouch once per article (enforced by PDA).
-    pub fn vouch(ctx: Context<CreateVouch>, content_hash: [u8; 32]) -> Result<()> {
-        let vouch = &mut ctx.accounts.vouch;
-        vouch.voucher = ctx.accounts.voucher.key();
-        vouch.content_hash = content_hash;
-        vouch.timestamp = Clock::get()?.unix_timestamp;
-        vouch.bump = ctx.bumps.vouch;
+        emit!(SubmissionCancelled {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+        });
+        sub.close(rent_destination(
+            sub.rent_recipient,
+            ctx.accounts.submitter.to_account_info(),
+            ctx.accounts.rent_recipient.as_deref(),
+        )?)
+    }
+
+    /// Delete an expired submission, refunding its rent to its `rent_recipient`, or
+    /// to the org treasury for submissions that predate one.
+    /// Permissionless, so tips self-destruct on schedule even if the org never acts,
+    /// unless the org has frozen the submission under a legal hold.
+    /// With `tombstone` set, the caller also pays for a `Tombstone` recording that
+    /// the submission existed and when it expired.
+    pub fn expire_submission(ctx: Context<ExpireSubmission>, tombstone: bool) -> Result<()> {
+        let sub = &ctx.accounts.submission;
+        require!(!sub.frozen, VoidError::SubmissionFrozen);
+        let expires_at = sub.expires_at.ok_or(VoidError::NoExpiry)?;
+        require!(
+            Clock::get()?.unix_timestamp >= expires_at,
+            VoidError::SubmissionNotExpired
+        );
+        release_submission(&mut ctx.accounts.organization)?;
+        write_tombstone(
+            ctx.accounts.tombstone.as_mut(),
+            ctx.bumps.tombstone,
+            tombstone,
+            TombstoneKind::Submission,
+            sub.id,
+        )?;
+
+        emit!(SubmissionExpired {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+        });
+        sub.close(rent_destination(
+            sub.rent_recipient,
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.rent_recipient.as_deref(),
+        )?)
+    }
+
+    /// Set a submission's triage status (admin only). Workflow metadata only.
+    /// The first move away from `New` stamps `read_at`; every move to `Resolved`
+    /// stamps `resolved_at`.
+    pub fn set_submission_status(
+        ctx: Context<SetSubmissionStatus>,
+        status: SubmissionStatus,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let now = Clock::get()?.unix_timestamp;
+        let sub = &mut ctx.accounts.submission;
+        sub.status = status;
+        sub.status_updated_at = now;
+        if status != SubmissionStatus::New && sub.read_at.is_none() {
+            sub.read_at = Some(now);
+        }
+        if status == SubmissionStatus::Resolved {
+            sub.resolved_at = Some(now);
+        }
+
+        emit!(SubmissionStatusChanged {
+            submission: sub.key(),
+            organization: sub.organization,
+            status,
+            timestamp: now,
+            read_at: sub.read_at,
+            resolved_at: sub.resolved_at,
+        });
         Ok(())
     }
 
-    /// Remove a vouch (only the voucher can remove their own vouch).
-    pub fn unvouch(_ctx: Context<RemoveVouch>) -> Result<()> {
-        // Account will be closed and rent returned to voucher
+    /// Emit a submission's full record as a `SubmissionExported` event (admin only),
+    /// so an indexer keeps its history after `close_submission` reclaims the
+    /// account. Export, then close.
+    pub fn export_submission(ctx: Context<ExportSubmission>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let sub = &ctx.accounts.submission;
+
+        emit!(SubmissionExported {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+            storage: sub.storage.clone(),
+            content_hash: sub.content_hash,
+            key_version: sub.key_version,
+            submitter: sub.submitter,
+            via_relay: sub.via_relay,
+            timestamp: sub.timestamp,
+            expires_at: sub.expires_at,
+            category: sub.category,
+            status: sub.status,
+            status_updated_at: sub.status_updated_at,
+            parent_id: sub.parent_id,
+            frozen: sub.frozen,
+            rent_recipient: sub.rent_recipient,
+            proof: sub.proof,
+            read_at: sub.read_at,
+            replied_at: sub.replied_at,
+            resolved_at: sub.resolved_at,
+            schema_version: sub.schema_version,
+            exported_at: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
-    /// Create a wallet profile (opt-in to social features).
-    /// By default, reputation is hidden and followers are not allowed.
-    pub fn create_profile(ctx: Context<CreateProfile>) -> Result<()> {
-        let profile = &mut ctx.accounts.profile;
-        profile.wallet = ctx.accounts.wallet.key();
-        profile.reputation_visible = false;
-        profile.allow_followers = false;
-        profile.total_vouches = 0;
-        profile.follower_count = 0;
-        profile.following_count = 0;
-        profile.created_at = Clock::get()?.unix_timestamp;
-        profile.bump = ctx.bumps.profile;
+    /// `export_submission` for a zero-copy submission (admin only), emitting its
+    /// full record as a `SubmissionZcExported` event before `purge_organization`
+    /// reclaims the account.
+    pub fn export_submission_zc(ctx: Context<ExportSubmissionZc>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let sub = ctx.accounts.submission.load()?;
+        require_keys_eq!(
+            sub.organization,
+            ctx.accounts.organization.key(),
+            VoidError::SubmissionNotInOrg
+        );
+
+        emit!(SubmissionZcExported {
+            submission: ctx.accounts.submission.key(),
+            organization: sub.organization,
+            id: sub.id,
+            storage_kind: sub.storage_kind,
+            locator: String::from_utf8_lossy(&sub.locator[..usize::from(sub.locator_len)])
+                .into_owned(),
+            key_version: sub.key_version,
+            submitter: sub.submitter,
+            via_relay: sub.via_relay != 0,
+            timestamp: sub.timestamp,
+            category: sub.category,
+            status: sub.status,
+            exported_at: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
-    /// Update profile settings (toggle visibility and follower permissions).
-    pub fn update_profile(
-        ctx: Context<UpdateProfile>,
-        reputation_visible: bool,
-        allow_followers: bool,
+    /// Place a submission under a legal hold (admin only). A frozen submission
+    /// cannot be closed, cancelled, expired or purged until it is thawed.
+    pub fn freeze_submission(ctx: Context<SetSubmissionFrozen>) -> Result<()> {
+        set_submission_frozen(ctx, true)
+    }
+
+    /// Lift a legal hold placed with `freeze_submission` (admin only).
+    pub fn thaw_submission(ctx: Context<SetSubmissionFrozen>) -> Result<()> {
+        set_submission_frozen(ctx, false)
+    }
+
+    /// Resize a submission account so its storage locator can hold `new_max` bytes
+    /// (admin only). Lets pointers for future storage backends outgrow
+    /// `MAX_LOCATOR_LEN` without a new account layout. Growing tops up rent from
+    /// the admin; shrinking refunds the excess rent to the submission's
+    /// `rent_recipient`, passed as the optional `rent_recipient` account when it
+    /// isn't the admin. The capacity can never drop below the locator already
+    /// stored, or exceed `MAX_RESIZED_LOCATOR_LEN`.
+    pub fn resize_submission_hash(ctx: Context<ResizeSubmissionHash>, new_max: u16) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let sub = &ctx.accounts.submission;
+        require!(
+            (new_max as usize) <= MAX_RESIZED_LOCATOR_LEN,
+            VoidError::LocatorCapacityTooLarge
+        );
+        require!(
+            (new_max as usize) >= sub.storage.locator.len(),
+            VoidError::LocatorCapacityTooSmall
+        );
+
+        let info = sub.to_account_info();
+        let new_len = Submission::space(new_max as usize);
+        if new_len > info.data_len() {
+            grow_account(
+                &info,
+                &ctx.accounts.admin,
+                &ctx.accounts.system_program,
+                new_len,
+                0,
+            )?;
+        } else if new_len < info.data_len() {
+            info.resize(new_len)?;
+            let excess = info
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(new_len));
+            let destination = rent_destination(
+                sub.rent_recipient,
+                ctx.accounts.admin.to_account_info(),
+                ctx.accounts.rent_recipient.as_deref(),
+            )?;
+            info.sub_lamports(excess)?;
+            destination.add_lamports(excess)?;
+        }
+
+        emit!(SubmissionResized {
+            submission: sub.key(),
+            organization: sub.organization,
+            locator_capacity: new_max,
+        });
+        Ok(())
+    }
+
+    /// Upgrade an organization written under an older layout to the current one
+    /// (admin only): grow the account to `Organization::SPACE`, give the fields its
+    /// layout lacks the defaults `create_organization` would, and stamp
+    /// `CURRENT_SCHEMA`. Orgs from before creation indexes take the next `index`.
+    /// The admin pays the extra rent. Current orgs are left untouched, so the call
+    /// is safe to repeat. Old orgs can't be loaded by any other instruction, so
+    /// this has to run before anything else touches them, `migrate_submission`
+    /// included.
+    pub fn migrate_organization(ctx: Context<MigrateOrganization>) -> Result<()> {
+        let info = ctx.accounts.organization.to_account_info();
+        if info.data_len() == Organization::SPACE {
+            return Ok(());
+        }
+        let layout = legacy_layout(&Organization::LEGACY_LAYOUTS, info.data_len())?;
+        let mut org = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.starts_with(Organization::DISCRIMINATOR),
+                VoidError::NotAnOrganization
+            );
+            Organization::read_legacy(&mut &data[8..], layout)?
+        };
+        require!(org.schema_version >= layout, VoidError::UnknownLayout);
+        require_keys_eq!(
+            org.admin,
+            ctx.accounts.admin.key(),
+            VoidError::InsufficientRole
+        );
+
+        // Schema 13 added `index`
+        if layout < 13 {
+            let registry = &mut ctx.accounts.org_registry;
+            registry.bump = ctx.bumps.org_registry;
+            org.index = registry.org_count;
+            registry.org_count = registry
+                .org_count
+                .checked_add(1)
+                .ok_or(VoidError::CounterOverflow)?;
+        }
+        record_admin_activity(&mut org)?;
+        let from_version = org.schema_version;
+        org.schema_version = CURRENT_SCHEMA;
+
+        grow_account(
+            &info,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            Organization::SPACE,
+            0,
+        )?;
+        org.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(OrganizationMigrated {
+            organization: info.key(),
+            index: org.index,
+            from_version,
+            to_version: CURRENT_SCHEMA,
+        });
+        Ok(())
+    }
+
+    /// Upgrade an older submission to the current layout (admin only): grow the
+    /// account by the fields its schema lacks, fill them with defaults and stamp
+    /// `CURRENT_SCHEMA`. The admin pays the extra rent. Already-current
+    /// submissions are left untouched, so the call is safe to repeat.
+    pub fn migrate_submission(ctx: Context<MigrateSubmission>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let info = ctx.accounts.submission.to_account_info();
+        let old = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.starts_with(Submission::DISCRIMINATOR),
+                VoidError::NotASubmission
+            );
+            // `schema_version` sits at the same offset in every layout
+            let v1 = SubmissionV1::deserialize(&mut &data[8..])?;
+            if v1.schema_version >= 18 {
+                SubmissionV6::deserialize(&mut &data[8..])?
+            } else if v1.schema_version >= 16 {
+                SubmissionV5::deserialize(&mut &data[8..])?.into()
+            } else if v1.schema_version >= 14 {
+                SubmissionV5::from(SubmissionV4::deserialize(&mut &data[8..])?).into()
+            } else if v1.schema_version >= 9 {
+                SubmissionV5::from(SubmissionV4::from(SubmissionV3::deserialize(&mut &data[8..])?)).into()
+            } else if v1.schema_version >= 2 {
+                SubmissionV5::from(SubmissionV4::from(SubmissionV3::from(SubmissionV2::deserialize(
+                    &mut &data[8..],
+                )?)))
+                .into()
+            } else {
+                SubmissionV5::from(SubmissionV4::from(SubmissionV3::from(SubmissionV2::from(v1)))).into()
+            }
+        };
+        require_keys_eq!(
+            old.organization,
+            ctx.accounts.organization.key(),
+            VoidError::SubmissionNotInOrg
+        );
+        // Schema 20 added the last fields this migration fills in
+        if old.schema_version >= 20 {
+            return Ok(());
+        }
+
+        // Keep whatever locator capacity the account was resized to: schema 1
+        // lacks `status_updated_at`, schemas before 9 lack `parent_id`, schemas
+        // before 14 lack `frozen`, schemas before 16 lack `rent_recipient`,
+        // schemas before 18 lack `proof`, and every older schema lacks the
+        // lifecycle timestamps
+        let missing = if old.schema_version >= 2 { 0 } else { 8 }
+            + if old.schema_version >= 9 { 0 } else { 1 + 8 }
+            + if old.schema_version >= 14 { 0 } else { 1 }
+            + if old.schema_version >= 16 { 0 } else { 1 + 32 }
+            + if old.schema_version >= 18 { 0 } else { 1 + 32 }
+            + 3 * (1 + 8);
+        grow_account(
+            &info,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            info.data_len() + missing,
+            0,
+        )?;
+
+        let sub = Submission {
+            id: old.id,
+            organization: old.organization,
+            storage: old.storage,
+            content_hash: old.content_hash,
+            key_version: old.key_version,
+            submitter: old.submitter,
+            via_relay: old.via_relay,
+            timestamp: old.timestamp,
+            expires_at: old.expires_at,
+            category: old.category,
+            status: old.status,
+            schema_version: CURRENT_SCHEMA,
+            status_updated_at: old.status_updated_at,
+            parent_id: old.parent_id,
+            frozen: old.frozen,
+            rent_recipient: old.rent_recipient,
+            proof: old.proof,
+            read_at: None,
+            replied_at: None,
+            resolved_at: None,
+            bump: old.bump,
+        };
+        sub.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(SubmissionMigrated {
+            submission: info.key(),
+            organization: sub.organization,
+            from_version: old.schema_version,
+            to_version: CURRENT_SCHEMA,
+        });
+        Ok(())
+    }
+
+    /// Post an encrypted reply to a submission (admin or Editor member).
+    /// The reply PDA is derived from the submission, so the submitter can find it
+    /// and decrypt it with the ephemeral key they submitted with. Stamps the
+    /// submission's `replied_at`, and its `read_at` if it was still unread.
+    pub fn reply_to_submission(
+        ctx: Context<ReplyToSubmission>,
+        storage: StoragePointer,
     ) -> Result<()> {
-        let profile = &mut ctx.accounts.profile;
-        profile.reputation_visible = reputation_visible;
-        profile.allow_followers = allow_followers;
+        check_org_role(
+            &ctx.accounts.organization,
+            &ctx.accounts.authority,
+            ctx.accounts.member.as_ref(),
+            Role::Editor,
+        )?;
+        if ctx.accounts.authority.key() == ctx.accounts.organization.admin {
+            record_admin_activity(&mut ctx.accounts.organization)?;
+        }
+        check_locator(&storage)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let sub = &mut ctx.accounts.submission;
+        sub.replied_at = Some(now);
+        if sub.read_at.is_none() {
+            sub.read_at = Some(now);
+        }
+
+        let reply = &mut ctx.accounts.reply;
+        reply.submission = sub.key();
+        reply.storage = storage;
+        reply.timestamp = now;
+        reply.bump = ctx.bumps.reply;
+
+        emit!(ReplyPosted {
+            reply: reply.key(),
+            submission: reply.submission,
+            organization: ctx.accounts.organization.key(),
+            timestamp: reply.timestamp,
+            read_at: sub.read_at,
+        });
         Ok(())
     }
 
-    /// Follow another wallet. Target must allow followers.
-    pub fn follow(ctx: Context<CreateFollow>) -> Result<()> {
+    /// Answer the org within a submission thread (original submitter only).
+    /// Creates a new submission whose `parent_id` is `parent_submission`, which must
+    /// be an earlier submission of this org by the same submitter, so a whole
+    /// source-org conversation lives under one org. Follow-ups go through the same
+    /// gates and fees as a new tip and inherit the parent's category. Relayed
+    /// follow-ups take `ip_hash` and its `ip_rate_state` as in `submit_tip`.
+    pub fn follow_up_submission<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FollowUpSubmission<'info>>,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+        parent_submission: u64,
+        ip_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        check_locator(&storage)?;
+        check_org_locator(&ctx.accounts.organization, &storage)?;
+        let category = ctx.accounts.parent.category;
+        let validated = validate_tip(
+            &ctx.accounts.organization,
+            ctx.accounts.validator_program.as_ref(),
+            &ctx.accounts.submitter.to_account_info(),
+            ctx.remaining_accounts,
+            content_hash,
+            category,
+        )?;
+        let (submission_id, now) = admit_tip(
+            TipAccounts {
+                config: &ctx.accounts.config,
+                organization: &mut ctx.accounts.organization,
+                treasury: &ctx.accounts.treasury,
+                submitter_state: &mut ctx.accounts.submitter_state,
+                global_index: &mut ctx.accounts.global_index,
+                allowlisted: ctx.accounts.allowlist.is_some(),
+                has_proof: ctx.accounts.proof.is_some(),
+                validated,
+                deduplicated: ctx.accounts.submission_dedup.is_some(),
+                payer: Some(&ctx.accounts.submitter),
+                fee_mint: ctx.accounts.fee_mint.as_ref(),
+                submitter_token_account: ctx.accounts.submitter_token_account.as_ref(),
+                org_fee_account: ctx.accounts.org_fee_account.as_ref(),
+                token_program: ctx.accounts.token_program.as_ref(),
+                protocol_treasury: ctx.accounts.protocol_treasury.as_ref(),
+                system_program: &ctx.accounts.system_program,
+            },
+            ctx.bumps.submitter_state,
+            ctx.bumps.global_index,
+            category,
+        )?;
+        let org = &ctx.accounts.organization;
+        if ctx.accounts.submitter.key() == ctx.accounts.config.relayer {
+            admit_ip(org, ctx.accounts.ip_rate_state.as_mut(), ip_hash, ctx.bumps.ip_rate_state, now)?;
+        }
+
+        record_dedup(
+            org,
+            ctx.accounts.submission_dedup.as_mut(),
+            submission_id,
+            ctx.bumps.submission_dedup,
+        )?;
+
+        let sub = &mut ctx.accounts.submission;
+        sub.id = submission_id;
+        sub.organization = org.key();
+        sub.storage = storage;
+        sub.content_hash = content_hash;
+        sub.key_version = org.key_version;
+        sub.submitter = ctx.accounts.submitter.key();
+        sub.via_relay = sub.submitter == ctx.accounts.config.relayer;
+        sub.timestamp = now;
+        sub.expires_at = None;
+        sub.category = category;
+        sub.status = SubmissionStatus::New;
+        sub.schema_version = CURRENT_SCHEMA;
+        sub.parent_id = Some(parent_submission);
+        sub.frozen = false;
+        sub.rent_recipient = Some(ctx.accounts.submitter.key());
+        sub.proof = ctx.accounts.proof.as_ref().map(|proof| proof.key());
+        sub.read_at = None;
+        sub.replied_at = None;
+        sub.resolved_at = None;
+        sub.bump = ctx.bumps.submission;
+
+        emit!(FollowUpSubmitted {
+            submission: sub.key(),
+            organization: sub.organization,
+            id: sub.id,
+            parent_id: parent_submission,
+            timestamp: sub.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Update an organization's name and/or description (admin or Editor member).
+    /// Only the `Some` fields are changed. The slug is a PDA seed and stays fixed.
+    pub fn update_organization(
+        ctx: Context<UpdateOrganization>,
+        name: Option<String>,
+        description: Option<String>,
+    ) -> Result<()> {
+        check_org_role(
+            &ctx.accounts.organization,
+            &ctx.accounts.authority,
+            ctx.accounts.member.as_ref(),
+            Role::Editor,
+        )?;
+        if ctx.accounts.authority.key() == ctx.accounts.organization.admin {
+            record_admin_activity(&mut ctx.accounts.organization)?;
+        }
+
+        let org = &mut ctx.accounts.organization;
+        if let Some(name) = name {
+            require!(name.len() <= MAX_NAME_LEN, VoidError::NameTooLong);
+            org.name = name;
+        }
+        if let Some(description) = description {
+            require!(description.len() <= MAX_DESC_LEN, VoidError::DescriptionTooLong);
+            require!(description.len() >= MIN_DESC_LEN, VoidError::DescriptionTooShort);
+            org.description = description;
+        }
+
+        emit!(OrganizationUpdated {
+            organization: org.key(),
+            name: org.name.clone(),
+            description: org.description.clone(),
+        });
+        Ok(())
+    }
+
+    /// Change the per-tip submission fee in lamports (admin only). 0 disables it.
+    pub fn update_submission_fee(
+        ctx: Context<UpdateSubmissionFee>,
+        submission_fee: u64,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.submission_fee = submission_fee;
+
+        emit!(SubmissionFeeUpdated {
+            organization: org.key(),
+            submission_fee,
+        });
+        Ok(())
+    }
+
+    /// Replace the bitmask of tip categories the org accepts (admin only).
+    /// Bit `n` of the 64-bit mask (byte `n / 8`, bit `n % 8`) enables category `n`.
+    pub fn update_categories(ctx: Context<UpdateCategories>, categories: [u8; 8]) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.categories = categories;
+
+        emit!(CategoriesUpdated {
+            organization: org.key(),
+            categories,
+        });
+        Ok(())
+    }
+
+    /// Set or clear the org's submission guidelines (admin only): the longer
+    /// "what we accept" text shown on the submission form, kept apart from the
+    /// short directory `description`.
+    pub fn set_guidelines(ctx: Context<SetGuidelines>, guidelines: Option<String>) -> Result<()> {
+        if let Some(text) = &guidelines {
+            require!(text.len() <= MAX_GUIDELINES_LEN, VoidError::GuidelinesTooLong);
+        }
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.guidelines = guidelines;
+
+        emit!(GuidelinesUpdated {
+            organization: org.key(),
+            guidelines: org.guidelines.clone(),
+        });
+        Ok(())
+    }
+
+    /// Change the org's display handle (admin only), e.g. after a rebrand. The
+    /// handle follows the slug's `[a-z0-9-]` rules but may be 1-32 chars; the slug
+    /// stays the org's PDA seed, so frontends map handles to slugs off-chain.
+    pub fn set_handle(ctx: Context<SetHandle>, handle: String) -> Result<()> {
         require!(
-            ctx.accounts.target_profile.allow_followers,
-            VoidError::FollowersNotAllowed
+            !handle.is_empty() && handle.len() <= MAX_HANDLE_LEN,
+            VoidError::InvalidHandleLength
         );
+        check_slug(&handle)?;
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.handle = handle;
 
-        let follow = &mut ctx.accounts.follow;
-        follow.follower = ctx.accounts.follower.key();
-        follow.following = ctx.accounts.target_profile.wallet;
-        follow.timestamp = Clock::get()?.unix_timestamp;
-        follow.bump = ctx.bumps.follow;
+        emit!(HandleUpdated {
+            organization: org.key(),
+            slug: org.slug.clone(),
+            handle: org.handle.clone(),
+        });
+        Ok(())
+    }
 
-        // Update counts
-        let follower_profile = &mut ctx.accounts.follower_profile;
-        follower_profile.following_count += 1;
+    /// Reschedule when the org accepts submissions (admin only). 0 = unbounded side.
+    pub fn update_submission_window(
+        ctx: Context<UpdateSubmissionWindow>,
+        submission_open_at: i64,
+        submission_close_at: i64,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        check_submission_window(submission_open_at, submission_close_at)?;
 
-        let target_profile = &mut ctx.accounts.target_profile;
-        target_profile.follower_count += 1;
+        let org = &mut ctx.accounts.organization;
+        org.submission_open_at = submission_open_at;
+        org.submission_close_at = submission_close_at;
 
+        emit!(SubmissionWindowUpdated {
+            organization: org.key(),
+            submission_open_at,
+            submission_close_at,
+        });
         Ok(())
     }
 
-    /// Unfollow a wallet.
-    pub fn unfollow(ctx: Context<RemoveFollow>) -> Result<()> {
-        // Update counts
-        let follower_profile = &mut ctx.accounts.follower_profile;
-        follower_profile.following_count = follower_profile.following_count.saturating_sub(1);
+    /// Cap how many submissions the org will ever accept (admin only). 0 = unlimited.
+    /// The cap counts every tip received, including ones since closed.
+    pub fn update_max_submissions(
+        ctx: Context<UpdateMaxSubmissions>,
+        max_submissions: u64,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.max_submissions = max_submissions;
 
-        let target_profile = &mut ctx.accounts.target_profile;
-        target_profile.follower_count = target_profile.follower_count.saturating_sub(1);
+        emit!(MaxSubmissionsUpdated {
+            organization: org.key(),
+            max_submissions,
+        });
+        Ok(())
+    }
 
-        // Account will be closed
+    /// Restrict submissions to allowlisted wallets, or reopen to everyone (admin only).
+    pub fn set_allowlist_only(ctx: Context<SetAllowlistOnly>, allowlist_only: bool) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.allowlist_only = allowlist_only;
+
+        emit!(AllowlistModeChanged {
+            organization: org.key(),
+            allowlist_only,
+        });
         Ok(())
     }
-}
 
-// ─── ERRORS ─────────────────────────────────────────────────────
+    /// Require every tip to reference a `Proof` of its evidence, or drop the
+    /// requirement (admin only). Every tip path takes the optional `proof` account.
+    pub fn set_require_proof(ctx: Context<SetRequireProof>, require_proof: bool) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.require_proof = require_proof;
 
-#[error_code]
-pub enum VoidError {
-    #[msg("Organization slug too long (max 32 chars)")]
-    SlugTooLong,
-    #[msg("Organization name too long (max 64 chars)")]
-    NameTooLong,
-    #[msg("Organization description too long (max 256 chars)")]
-    DescriptionTooLong,
-    #[msg("Slug cannot be empty")]
-    SlugEmpty,
-    #[msg("Arweave hash too long (max 64 chars)")]
-    ArweaveHashTooLong,
-    #[msg("Organization is inactive")]
-    OrgInactive,
-    #[msg("Message has already been burned")]
-    AlreadyBurned,
-    #[msg("This wallet does not allow followers")]
-    FollowersNotAllowed,
+        emit!(RequireProofChanged {
+            organization: org.key(),
+            require_proof,
+        });
+        Ok(())
+    }
+
+    /// Turn duplicate-locator rejection on or off (admin only). While on, each tip
+    /// records its locator in a `SubmissionDedup` PDA, which outlives the
+    /// submission, so a locator can never be resubmitted to the org. Every tip
+    /// path takes the record as `submission_dedup`.
+    pub fn set_dedup(ctx: Context<SetDedup>, dedup: bool) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.dedup = dedup;
+
+        emit!(DedupModeChanged {
+            organization: org.key(),
+            dedup,
+        });
+        Ok(())
+    }
+
+    /// Set or clear the program that must approve every tip (admin only); see
+    /// `call_validator` for the interface it implements. Every tip path calls it,
+    /// so each takes the program as `validator_program` plus its accounts as
+    /// `remaining_accounts`.
+    pub fn set_validator_program(
+        ctx: Context<SetValidatorProgram>,
+        validator_program: Option<Pubkey>,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.validator_program = validator_program;
+
+        emit!(ValidatorProgramUpdated {
+            organization: org.key(),
+            validator_program,
+        });
+        Ok(())
+    }
+
+    /// Allow a wallet to submit to an allowlist-only org (admin only).
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, submitter: Pubkey) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let entry = &mut ctx.accounts.allowlist;
+        entry.organization = ctx.accounts.organization.key();
+        entry.submitter = submitter;
+        entry.bump = ctx.bumps.allowlist;
+
+        emit!(AllowlistChanged {
+            organization: entry.organization,
+            submitter,
+            allowed: true,
+        });
+        Ok(())
+    }
+
+    /// Revoke a wallet's allowlist entry and refund its rent (admin only).
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let entry = &ctx.accounts.allowlist;
+        emit!(AllowlistChanged {
+            organization: entry.organization,
+            submitter: entry.submitter,
+            allowed: false,
+        });
+        Ok(())
+    }
+
+    /// Change the per-wallet wait between submissions in seconds (admin only). 0 disables it.
+    pub fn update_cooldown(ctx: Context<UpdateCooldown>, cooldown_secs: u32) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.cooldown_secs = cooldown_secs;
+
+        emit!(CooldownUpdated {
+            organization: org.key(),
+            cooldown_secs,
+        });
+        Ok(())
+    }
+
+    /// Cap the tips the org accepts across all submitters to `window_limit` per
+    /// `window_secs` (admin only), on top of the per-wallet cooldown. The window
+    /// restarts with the first tip after it lapses. A `window_limit` of 0 disables it.
+    pub fn update_rate_limit(
+        ctx: Context<UpdateRateLimit>,
+        window_secs: u32,
+        window_limit: u32,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        require!(
+            window_limit == 0 || window_secs > 0,
+            VoidError::InvalidRateLimit
+        );
+        let org = &mut ctx.accounts.organization;
+        org.window_secs = window_secs;
+        org.window_limit = window_limit;
+        org.window_start = 0;
+        org.window_count = 0;
+
+        emit!(RateLimitUpdated {
+            organization: org.key(),
+            window_secs,
+            window_limit,
+        });
+        Ok(())
+    }
+
+    /// Cap relayed tips per source IP to `ip_window_limit` per `ip_window_secs`
+    /// (admin only). The relayer identifies sources by a salted `ip_hash`, so IPs
+    /// stay off-chain. An `ip_window_limit` of 0 disables it.
+    pub fn update_ip_rate_limit(
+        ctx: Context<UpdateIpRateLimit>,
+        ip_window_secs: u32,
+        ip_window_limit: u32,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        require!(
+            ip_window_limit == 0 || ip_window_secs > 0,
+            VoidError::InvalidRateLimit
+        );
+        let org = &mut ctx.accounts.organization;
+        org.ip_window_secs = ip_window_secs;
+        org.ip_window_limit = ip_window_limit;
+
+        emit!(IpRateLimitUpdated {
+            organization: org.key(),
+            ip_window_secs,
+            ip_window_limit,
+        });
+        Ok(())
+    }
+
+    /// Charge submissions in an SPL token instead of (or on top of) lamports (admin only).
+    /// Fees go straight to the admin's token account for `fee_mint`; `None` disables it.
+    pub fn update_token_fee(
+        ctx: Context<UpdateTokenFee>,
+        fee_mint: Option<Pubkey>,
+        fee_amount: u64,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.fee_mint = fee_mint;
+        org.fee_amount = if fee_mint.is_some() { fee_amount } else { 0 };
+
+        emit!(TokenFeeUpdated {
+            organization: org.key(),
+            fee_mint: org.fee_mint,
+            fee_amount: org.fee_amount,
+        });
+        Ok(())
+    }
+
+    /// Sweep collected submission fees from the org treasury to the admin.
+    /// The treasury keeps its rent-exempt minimum so the account stays alive.
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let treasury = &ctx.accounts.treasury;
+        let rent_floor = Rent::get()?.minimum_balance(treasury.to_account_info().data_len());
+        let amount = treasury.get_lamports().saturating_sub(rent_floor);
+        require!(amount > 0, VoidError::NothingToWithdraw);
+
+        treasury.sub_lamports(amount)?;
+        ctx.accounts.admin.add_lamports(amount)?;
+
+        emit!(FeesWithdrawn {
+            organization: ctx.accounts.organization.key(),
+            admin: ctx.accounts.admin.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Add lamports to the org's bounty pool. Anyone can fund it; the vault PDA is
+    /// created on first deposit.
+    pub fn fund_bounty(ctx: Context<FundBounty>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.organization = ctx.accounts.organization.key();
+        vault.bump = ctx.bumps.vault;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(BountyFunded {
+            organization: ctx.accounts.organization.key(),
+            funder: ctx.accounts.funder.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Pay a bounty from the pool to the wallet behind an accepted submission
+    /// (admin only). The vault keeps its rent-exempt minimum, so payouts are capped
+    /// at the balance above it.
+    pub fn pay_bounty(ctx: Context<PayBounty>, amount: u64) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let vault = &ctx.accounts.vault;
+        let rent_floor = Rent::get()?.minimum_balance(vault.to_account_info().data_len());
+        require!(
+            amount <= vault.get_lamports().saturating_sub(rent_floor),
+            VoidError::InsufficientBounty
+        );
+
+        vault.sub_lamports(amount)?;
+        ctx.accounts.submitter.add_lamports(amount)?;
+
+        let org = &mut ctx.accounts.organization;
+        org.total_paid = org
+            .total_paid
+            .checked_add(amount)
+            .ok_or(VoidError::CounterOverflow)?;
+
+        emit!(BountyPaid {
+            organization: org.key(),
+            submission: ctx.accounts.submission.key(),
+            submitter: ctx.accounts.submitter.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Add lamports to the org's sponsorship pool, which reimburses the relayer for
+    /// sponsored tips. Anyone can fund it; the vault PDA is created on first deposit.
+    pub fn fund_sponsorship(ctx: Context<FundSponsorship>, amount: u64) -> Result<()> {
+        let sponsor = &mut ctx.accounts.sponsor;
+        sponsor.organization = ctx.accounts.organization.key();
+        sponsor.bump = ctx.bumps.sponsor;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.sponsor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(SponsorshipFunded {
+            organization: ctx.accounts.organization.key(),
+            funder: ctx.accounts.funder.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Cap the total lamports the sponsorship pool may pay out (admin only).
+    /// 0 removes the cap. Compared against the running `spent` total.
+    pub fn set_sponsor_cap(ctx: Context<SetSponsorCap>, cap: u64) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let sponsor = &mut ctx.accounts.sponsor;
+        sponsor.cap = cap;
+
+        emit!(SponsorCapUpdated {
+            organization: sponsor.organization,
+            cap,
+            spent: sponsor.spent,
+        });
+        Ok(())
+    }
+
+    /// Replace the org's ECDH public keys (admin only), e.g. after a suspected compromise
+    /// or when an editor joins or leaves. Takes 1-3 keys, like `create_organization`.
+    /// `key_version` is bumped so each submission records which keys it was encrypted to.
+    /// The new keys must use the org's existing `key_algo`.
+    pub fn rotate_org_key(ctx: Context<RotateOrgKey>, new_keys: Vec<[u8; 65]>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        set_org_keys(org, &new_keys)?;
+        org.key_version = org
+            .key_version
+            .checked_add(1)
+            .ok_or(VoidError::CounterOverflow)?;
+        org.key_rotated_at = Clock::get()?.unix_timestamp;
+
+        emit!(OrgKeyRotated {
+            organization: org.key(),
+            key_version: org.key_version,
+            key_fingerprint: org.key_fingerprint,
+            timestamp: org.key_rotated_at,
+        });
+        Ok(())
+    }
+
+    /// Start handing the org to a new admin (admin only).
+    /// Nothing changes until `new_admin` signs `accept_admin_transfer`.
+    pub fn propose_admin_transfer(
+        ctx: Context<ProposeAdminTransfer>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.pending_admin = Some(new_admin);
+
+        emit!(AdminTransferProposed {
+            organization: org.key(),
+            admin: org.admin,
+            pending_admin: new_admin,
+        });
+        Ok(())
+    }
+
+    /// Complete an admin handoff. Must be signed by the proposed admin.
+    pub fn accept_admin_transfer(ctx: Context<AcceptAdminTransfer>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        let previous_admin = org.admin;
+        org.admin = ctx.accounts.new_admin.key();
+        org.pending_admin = None;
+
+        emit!(AdminTransferAccepted {
+            organization: org.key(),
+            previous_admin,
+            new_admin: org.admin,
+        });
+        Ok(())
+    }
+
+    /// Withdraw a pending admin handoff (current admin only).
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        let pending_admin = org.pending_admin.take().ok_or(VoidError::NoPendingAdmin)?;
+
+        emit!(AdminTransferCancelled {
+            organization: org.key(),
+            admin: org.admin,
+            pending_admin,
+        });
+        Ok(())
+    }
+
+    /// Deactivate an organization (admin or Admin member). Prevents new submissions.
+    pub fn deactivate_organization(ctx: Context<DeactivateOrganization>) -> Result<()> {
+        check_org_role(
+            &ctx.accounts.organization,
+            &ctx.accounts.authority,
+            ctx.accounts.member.as_ref(),
+            Role::Admin,
+        )?;
+        if ctx.accounts.authority.key() == ctx.accounts.organization.admin {
+            record_admin_activity(&mut ctx.accounts.organization)?;
+        }
+
+        let org = &mut ctx.accounts.organization;
+        org.active = false;
+
+        emit!(OrganizationActiveChanged {
+            organization: org.key(),
+            active: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Give a wallet a role in the org (admin or Admin member).
+    /// Members act through their `[b"member", org, wallet]` PDA; they never get the
+    /// org's encryption key from the program, so roles don't share decryption.
+    pub fn add_member(ctx: Context<AddMember>, member: Pubkey, role: Role) -> Result<()> {
+        check_org_role(
+            &ctx.accounts.organization,
+            &ctx.accounts.authority,
+            ctx.accounts.authority_member.as_ref(),
+            Role::Admin,
+        )?;
+        if ctx.accounts.authority.key() == ctx.accounts.organization.admin {
+            record_admin_activity(&mut ctx.accounts.organization)?;
+        }
+
+        let entry = &mut ctx.accounts.member;
+        entry.organization = ctx.accounts.organization.key();
+        entry.member = member;
+        entry.role = role;
+        entry.added_at = Clock::get()?.unix_timestamp;
+        entry.bump = ctx.bumps.member;
+
+        emit!(MemberChanged {
+            organization: entry.organization,
+            member,
+            role: Some(role),
+        });
+        Ok(())
+    }
+
+    /// Revoke a member's role and refund the PDA's rent to the caller
+    /// (admin or Admin member).
+    pub fn remove_member(ctx: Context<RemoveMember>) -> Result<()> {
+        check_org_role(
+            &ctx.accounts.organization,
+            &ctx.accounts.authority,
+            ctx.accounts.authority_member.as_ref(),
+            Role::Admin,
+        )?;
+        if ctx.accounts.authority.key() == ctx.accounts.organization.admin {
+            record_admin_activity(&mut ctx.accounts.organization)?;
+        }
+
+        emit!(MemberChanged {
+            organization: ctx.accounts.organization.key(),
+            member: ctx.accounts.member.member,
+            role: None,
+        });
+        Ok(())
+    }
+
+    /// Tear down a deactivated org (admin only): close the `Organization`, its
+    /// treasury and, if passed, its bounty vault, refunding everything to the admin.
+    /// Submissions still open can be passed as writable `remaining_accounts` (either
+    /// layout, at most `MAX_PURGE_BATCH`) to close them in the same call. Each
+    /// refunds its `rent_recipient`, or its submitter for zero-copy submissions;
+    /// any such wallet other than the admin is passed, writable, alongside them in
+    /// `remaining_accounts`. The purge fails while any submissions remain. Member
+    /// and allowlist PDAs are left for their own close paths.
+    pub fn purge_organization<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PurgeOrganization<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.organization.active, VoidError::OrgStillActive);
+        let (submissions, recipients): (Vec<_>, Vec<_>) =
+            ctx.remaining_accounts.iter().partition(|info| is_submission(info));
+        require!(
+            submissions.len() <= MAX_PURGE_BATCH,
+            VoidError::TooManySubmissions
+        );
+        let passed = |key: Pubkey| recipients.iter().copied().find(|info| info.key() == key);
+
+        let org_key = ctx.accounts.organization.key();
+        let admin = ctx.accounts.admin.to_account_info();
+        for info in submissions {
+            if info.try_borrow_data()?.starts_with(SubmissionZC::DISCRIMINATOR) {
+                let sub = AccountLoader::<SubmissionZC>::try_from(info)?;
+                let (organization, submitter) = {
+                    let sub = sub.load()?;
+                    (sub.organization, sub.submitter)
+                };
+                require_keys_eq!(organization, org_key, VoidError::SubmissionNotInOrg);
+                sub.close(rent_destination(Some(submitter), admin.clone(), passed(submitter))?)?;
+            } else {
+                let sub = Account::<Submission>::try_from(info)?;
+                require_keys_eq!(sub.organization, org_key, VoidError::SubmissionNotInOrg);
+                require!(!sub.frozen, VoidError::SubmissionFrozen);
+                let destination = rent_destination(
+                    sub.rent_recipient,
+                    admin.clone(),
+                    sub.rent_recipient.and_then(passed),
+                )?;
+                sub.close(destination)?;
+            }
+            release_submission(&mut ctx.accounts.organization)?;
+        }
+        require!(
+            ctx.accounts.organization.open_submissions == 0,
+            VoidError::SubmissionsRemaining
+        );
+
+        emit!(OrganizationPurged {
+            organization: org_key,
+            admin: admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Reopen a deactivated organization to new submissions (admin only).
+    pub fn reactivate_organization(ctx: Context<ReactivateOrganization>) -> Result<()> {
+        record_admin_activity(&mut ctx.accounts.organization)?;
+        let org = &mut ctx.accounts.organization;
+        org.active = true;
+
+        emit!(OrganizationActiveChanged {
+            organization: org.key(),
+            active: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Dead man's switch: deactivate an org whose admin has been silent for longer
+    /// than its `liveness_timeout`, so sources stop sending tips to a key that may
+    /// have been seized. Permissionless; only the admin can reactivate it.
+    pub fn check_org_liveness(ctx: Context<CheckOrgLiveness>) -> Result<()> {
+        let org = &mut ctx.accounts.organization;
+        require!(org.active, VoidError::OrgInactive);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            org.liveness_timeout > 0
+                && now.saturating_sub(org.last_admin_activity) > i64::from(org.liveness_timeout),
+            VoidError::AdminStillActive
+        );
+        org.active = false;
+
+        emit!(OrganizationLapsed {
+            organization: org.key(),
+            last_admin_activity: org.last_admin_activity,
+            timestamp: now,
+        });
+        emit!(OrganizationActiveChanged {
+            organization: org.key(),
+            active: false,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Read-only summary of an org for clients that want a typed result without
+    /// decoding the full account. Returned via Anchor return data (`.view()`).
+    pub fn get_org_stats(ctx: Context<GetOrgStats>) -> Result<OrgStats> {
+        let org = &ctx.accounts.organization;
+        Ok(OrgStats {
+            submission_count: org.submission_count,
+            created_at: org.created_at,
+            active: org.active,
+            verified: org.verified,
+        })
+    }
+
+    /// Grant or revoke the org's verification badge (protocol authority only).
+    /// Lets frontends tell a real org from one squatting a look-alike slug.
+    pub fn verify_organization(ctx: Context<VerifyOrganization>, verified: bool) -> Result<()> {
+        let org = &mut ctx.accounts.organization;
+        org.verified = verified;
+
+        emit!(OrganizationVerified {
+            organization: org.key(),
+            verified,
+        });
+        Ok(())
+    }
+
+    /// Flag an organization as abusive. Each wallet can report a given org once
+    /// (the report PDA is keyed by target and reporter); nothing is moderated
+    /// on-chain, the reports are an audit trail the protocol authority tallies off-chain.
+    pub fn report_organization(ctx: Context<ReportOrganization>, reason_code: u8) -> Result<()> {
+        let report = &mut ctx.accounts.report;
+        report.reporter = ctx.accounts.reporter.key();
+        report.target = ctx.accounts.organization.key();
+        report.reason_code = reason_code;
+        report.timestamp = Clock::get()?.unix_timestamp;
+        report.bump = ctx.bumps.report;
+
+        emit!(ReportFiled {
+            report: report.key(),
+            target: report.target,
+            reporter: report.reporter,
+            reason_code,
+            timestamp: report.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Flag a single submission as abusive. Same one-report-per-wallet rule as
+    /// `report_organization`.
+    pub fn report_submission(ctx: Context<ReportSubmission>, reason_code: u8) -> Result<()> {
+        let report = &mut ctx.accounts.report;
+        report.reporter = ctx.accounts.reporter.key();
+        report.target = ctx.accounts.submission.key();
+        report.reason_code = reason_code;
+        report.timestamp = Clock::get()?.unix_timestamp;
+        report.bump = ctx.bumps.report;
+
+        emit!(ReportFiled {
+            report: report.key(),
+            target: report.target,
+            reporter: report.reporter,
+            reason_code,
+            timestamp: report.timestamp,
+        });
+        Ok(())
+    }
+
+    // ─── VOID BURN ───────────────────────────────────────────────
+
+    /// Activate an inbox for wallet-to-wallet encrypted messaging.
+    /// The user provides their derived encryption public key (65 bytes).
+    /// This key is derived client-side from a wallet signature.
+    /// `key_algo` declares the curve of `encryption_key`, as for organizations.
+    /// With `default_burn` set, every message to the inbox is flagged
+    /// `burn_after_reading` whatever the sender asked for.
+    pub fn activate_inbox(
+        ctx: Context<ActivateInbox>,
+        encryption_key: [u8; 65],
+        key_algo: KeyAlgo,
+        default_burn: bool,
+    ) -> Result<()> {
+        check_encryption_key(key_algo, &encryption_key)?;
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.owner = ctx.accounts.owner.key();
+        inbox.encryption_key = encryption_key;
+        inbox.key_algo = key_algo;
+        inbox.key_version = 0;
+        inbox.key_rotated_at = 0;
+        inbox.message_count = 0;
+        inbox.min_stake = 0;
+        inbox.message_fee = 0;
+        inbox.default_burn = default_burn;
+        inbox.allowlist_only = false;
+        inbox.created_at = Clock::get()?.unix_timestamp;
+        inbox.active = true;
+        inbox.schema_version = CURRENT_SCHEMA;
+        inbox.bump = ctx.bumps.inbox;
+
+        emit!(InboxActivated {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+            timestamp: inbox.created_at,
+        });
+        Ok(())
+    }
+
+    /// Upgrade an inbox activated under an older layout to the current one (owner
+    /// only): grow the account, give the fields its layout lacks the defaults
+    /// `activate_inbox` would, and stamp `CURRENT_SCHEMA`. The owner pays the extra
+    /// rent. Current inboxes are left untouched, so the call is safe to repeat.
+    pub fn migrate_inbox(ctx: Context<MigrateInbox>) -> Result<()> {
+        let info = ctx.accounts.inbox.to_account_info();
+        if info.data_len() == Inbox::SPACE {
+            return Ok(());
+        }
+        let layout = legacy_layout(&Inbox::LEGACY_LAYOUTS, info.data_len())?;
+        let mut inbox = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.starts_with(Inbox::DISCRIMINATOR),
+                VoidError::NotAnInbox
+            );
+            Inbox::read_legacy(&mut &data[8..], layout)?
+        };
+        require!(inbox.schema_version >= layout, VoidError::UnknownLayout);
+        let from_version = inbox.schema_version;
+        inbox.schema_version = CURRENT_SCHEMA;
+
+        grow_account(
+            &info,
+            &ctx.accounts.owner,
+            &ctx.accounts.system_program,
+            Inbox::SPACE,
+            0,
+        )?;
+        inbox.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(InboxMigrated {
+            inbox: info.key(),
+            owner: inbox.owner,
+            from_version,
+            to_version: CURRENT_SCHEMA,
+        });
+        Ok(())
+    }
+
+    /// Replace the inbox's encryption key (owner only), e.g. after a device compromise.
+    /// Messages encrypted to an older `key_version` stay on-chain but can no longer
+    /// be decrypted with the new key. The new key must use the inbox's `key_algo`.
+    pub fn rotate_inbox_key(ctx: Context<RotateInboxKey>, new_key: [u8; 65]) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        check_encryption_key(inbox.key_algo, &new_key)?;
+        inbox.encryption_key = new_key;
+        inbox.key_version = inbox
+            .key_version
+            .checked_add(1)
+            .ok_or(VoidError::CounterOverflow)?;
+        inbox.key_rotated_at = Clock::get()?.unix_timestamp;
+
+        emit!(InboxKeyRotated {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+            key_version: inbox.key_version,
+            timestamp: inbox.key_rotated_at,
+        });
+        Ok(())
+    }
+
+    /// Stop receiving new messages (owner only). Existing messages are untouched.
+    pub fn deactivate_inbox(ctx: Context<DeactivateInbox>) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.active = false;
+
+        emit!(InboxDeactivated {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+        });
+        Ok(())
+    }
+
+    /// Close a deactivated inbox and refund its rent (owner only).
+    /// Deactivation must come first so no send can race the close. Old messages
+    /// keep their PDAs, so they should be burned and closed before re-activating.
+    pub fn close_inbox(ctx: Context<CloseInbox>) -> Result<()> {
+        let inbox = &ctx.accounts.inbox;
+        require!(!inbox.active, VoidError::InboxStillActive);
+
+        emit!(InboxClosed {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+        });
+        Ok(())
+    }
+
+    /// Require senders to escrow `min_stake` lamports with every message (owner only).
+    /// 0 disables staking. Only affects messages sent after the change.
+    pub fn set_min_stake(ctx: Context<SetMinStake>, min_stake: u64) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.min_stake = min_stake;
+
+        emit!(MinStakeUpdated {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+            min_stake,
+        });
+        Ok(())
+    }
+
+    /// Charge senders `message_fee` lamports per message, paid straight to the owner
+    /// (owner only). Unlike `min_stake` it is never refunded. 0 disables the fee.
+    /// Only affects messages sent after the change.
+    pub fn update_message_fee(ctx: Context<UpdateMessageFee>, message_fee: u64) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.message_fee = message_fee;
+
+        emit!(MessageFeeUpdated {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+            message_fee,
+        });
+        Ok(())
+    }
+
+    /// Turn the inbox-wide burn-after-reading policy on or off (owner only).
+    /// This is the recipient's preference: while it is on, senders can't opt out.
+    /// Only affects messages sent after the change.
+    pub fn set_default_burn(ctx: Context<SetDefaultBurn>, default_burn: bool) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.default_burn = default_burn;
+
+        emit!(DefaultBurnUpdated {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+            default_burn,
+        });
+        Ok(())
+    }
+
+    /// Accept messages only from allowlisted senders, or reopen the inbox to
+    /// everyone (owner only). Only affects messages sent after the change.
+    pub fn set_inbox_allowlist_only(
+        ctx: Context<SetInboxAllowlistOnly>,
+        allowlist_only: bool,
+    ) -> Result<()> {
+        let inbox = &mut ctx.accounts.inbox;
+        inbox.allowlist_only = allowlist_only;
+
+        emit!(InboxAllowlistModeChanged {
+            inbox: inbox.key(),
+            owner: inbox.owner,
+            allowlist_only,
+        });
+        Ok(())
+    }
+
+    /// Allow a wallet to message this inbox while it is allowlist-only (owner only).
+    pub fn add_to_inbox_allowlist(ctx: Context<AddToInboxAllowlist>, sender: Pubkey) -> Result<()> {
+        let entry = &mut ctx.accounts.allowlist;
+        entry.owner = ctx.accounts.owner.key();
+        entry.sender = sender;
+        entry.bump = ctx.bumps.allowlist;
+
+        emit!(InboxAllowlistChanged {
+            owner: entry.owner,
+            sender,
+            allowed: true,
+        });
+        Ok(())
+    }
+
+    /// Revoke a sender's inbox allowlist entry and refund its rent (owner only).
+    pub fn remove_from_inbox_allowlist(ctx: Context<RemoveFromInboxAllowlist>) -> Result<()> {
+        let entry = &ctx.accounts.allowlist;
+        emit!(InboxAllowlistChanged {
+            owner: entry.owner,
+            sender: entry.sender,
+            allowed: false,
+        });
+        Ok(())
+    }
+
+    /// Block a wallet from messaging this inbox (owner only).
+    pub fn block_sender(ctx: Context<BlockSender>, blocked: Pubkey) -> Result<()> {
+        let block = &mut ctx.accounts.block;
+        block.owner = ctx.accounts.owner.key();
+        block.blocked = blocked;
+        block.timestamp = Clock::get()?.unix_timestamp;
+        block.bump = ctx.bumps.block;
+
+        emit!(BlockListChanged {
+            owner: block.owner,
+            sender: blocked,
+            blocked: true,
+        });
+        Ok(())
+    }
+
+    /// Unblock a wallet and refund the block record's rent (owner only).
+    pub fn unblock_sender(ctx: Context<UnblockSender>) -> Result<()> {
+        let block = &ctx.accounts.block;
+        emit!(BlockListChanged {
+            owner: block.owner,
+            sender: block.blocked,
+            blocked: false,
+        });
+        Ok(())
+    }
+
+    /// Send an encrypted direct message to another wallet.
+    /// Recipient must have an activated inbox.
+    /// Senders must encrypt to the inbox's current `key_version` and pass it as
+    /// `encrypted_to_version`; a message sealed to a rotated-out key is rejected.
+    /// The client must pass `sender_block` = PDA of
+    /// `[b"block", recipient, sender]`; the send fails if that account exists.
+    /// Allowlist-only inboxes also require `sender_allow` = PDA of
+    /// `[b"inbox_allow", recipient, sender]`.
+    /// With `expires_at` set, anyone can burn the message once that time passes.
+    /// `parent_id` threads the message as a reply to an earlier one in the same inbox.
+    /// If the inbox sets `min_stake`, that many lamports are escrowed in the message
+    /// PDA until the recipient reads (refunded) or burns (claimed) it.
+    /// If the inbox sets `message_fee`, that many lamports go to the owner, passed
+    /// as `recipient`, before the message is recorded.
+    /// `label` is an optional plaintext subject for the inbox list; it is visible
+    /// to everyone, so leave it `None` to keep the message fully opaque.
+    /// `content_bytes` and `attachment_count` are advisory hints so the recipient
+    /// can decide whether to fetch the payload; nothing checks them against it.
+    /// Pass `sender_stats` = PDA of `[b"sstats", recipient, sender]` to count the
+    /// message towards the sender's history with this inbox, so clients can sort by
+    /// known senders; omit it to leave no per-sender trail.
+    /// If the recipient's inbox has `default_burn` on, `burn_after_reading` is forced
+    /// to true; the sender's flag can only add to the recipient's policy.
+    /// `content_hash` is the SHA-256 of the encrypted payload, for the recipient to
+    /// check after download.
+    #[allow(clippy::too_many_arguments)]
+    pub fn send_direct_message(
+        ctx: Context<SendDirectMessage>,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+        encrypted_to_version: u16,
+        burn_after_reading: bool,
+        expires_at: Option<i64>,
+        parent_id: Option<u64>,
+        label: Option<String>,
+        content_bytes: Option<u64>,
+        attachment_count: Option<u16>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_locator(&storage)?;
+        require!(
+            label.as_ref().is_none_or(|l| l.len() <= MAX_LABEL_LEN),
+            VoidError::LabelTooLong
+        );
+        require!(
+            attachment_count.is_none_or(|count| count <= MAX_ATTACHMENTS),
+            VoidError::TooManyAttachments
+        );
+        require!(ctx.accounts.recipient_inbox.active, VoidError::InboxInactive);
+        require!(
+            encrypted_to_version == ctx.accounts.recipient_inbox.key_version,
+            VoidError::StaleKeyVersion
+        );
+        require_keys_neq!(
+            ctx.accounts.sender.key(),
+            ctx.accounts.recipient_inbox.owner,
+            VoidError::CannotMessageSelf
+        );
+        require!(ctx.accounts.sender_block.data_is_empty(), VoidError::SenderBlocked);
+        require!(
+            !ctx.accounts.recipient_inbox.allowlist_only || ctx.accounts.sender_allow.is_some(),
+            VoidError::SenderNotAllowlisted
+        );
+
+        let recipient_inbox = &mut ctx.accounts.recipient_inbox;
+        let message_id = recipient_inbox.message_count;
+        require!(
+            parent_id.is_none_or(|parent| parent < message_id),
+            VoidError::InvalidParent
+        );
+        recipient_inbox.message_count = recipient_inbox
+            .message_count
+            .checked_add(1)
+            .ok_or(VoidError::CounterOverflow)?;
+
+        let fee = recipient_inbox.message_fee;
+        if fee > 0 {
+            let recipient = ctx.accounts.recipient.as_ref().ok_or(VoidError::InsufficientFee)?;
+            require!(
+                ctx.accounts.sender.lamports() >= fee,
+                VoidError::InsufficientFee
+            );
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sender.to_account_info(),
+                        to: recipient.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        let burn_after_reading = burn_after_reading || recipient_inbox.default_burn;
+        let stake = recipient_inbox.min_stake;
+        if stake > 0 {
+            require!(
+                ctx.accounts.sender.lamports() >= stake,
+                VoidError::InsufficientStake
+            );
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sender.to_account_info(),
+                        to: ctx.accounts.message.to_account_info(),
+                    },
+                ),
+                stake,
+            )?;
+        }
+
+        let msg = &mut ctx.accounts.message;
+        msg.id = message_id;
+        msg.sender = ctx.accounts.sender.key();
+        msg.recipient = recipient_inbox.owner;
+        msg.storage = storage;
+        msg.content_hash = content_hash;
+        msg.ephemeral_key = None;
+        msg.label = label;
+        msg.content_bytes = content_bytes;
+        msg.attachment_count = attachment_count;
+        msg.burn_after_reading = burn_after_reading;
+        msg.burned = false;
+        msg.expires_at = expires_at;
+        msg.read_at = None;
+        msg.parent_id = parent_id;
+        msg.stake = stake;
+        msg.rent_recipient = Some(ctx.accounts.sender.key());
+        msg.timestamp = Clock::get()?.unix_timestamp;
+        msg.schema_version = CURRENT_SCHEMA;
+        msg.bump = ctx.bumps.message;
+
+        if let (Some(stats), Some(bump)) =
+            (ctx.accounts.sender_stats.as_mut(), ctx.bumps.sender_stats)
+        {
+            if stats.message_count == 0 {
+                stats.first_contact_at = msg.timestamp;
+            }
+            stats.message_count = stats
+                .message_count
+                .checked_add(1)
+                .ok_or(VoidError::CounterOverflow)?;
+            stats.bump = bump;
+        }
+
+        emit!(MessageSent {
+            message: msg.key(),
+            id: msg.id,
+            sender: msg.sender,
+            recipient: msg.recipient,
+            key_version: recipient_inbox.key_version,
+            burn_after_reading,
+            timestamp: msg.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Send the same message reference to several inboxes in one transaction.
+    /// `remaining_accounts` holds one `[inbox (mut), message PDA (mut), sender_block]`
+    /// triple per recipient, where the message PDA is `[b"dm", owner, message_count]`
+    /// and `sender_block` is `[b"block", owner, sender]`. At most
+    /// `MAX_BROADCAST_RECIPIENTS` inboxes per call.
+    ///
+    /// Every recipient gets the same `storage` pointer, so the payload it points to
+    /// must carry one envelope per recipient, each encrypted to that inbox's current
+    /// key, and `content_hash` covers that whole payload. Each inbox's `min_stake` is escrowed exactly as in `send_direct_message`.
+    /// Inboxes that charge a `message_fee` or are allowlist-only must be messaged directly.
+    pub fn send_broadcast<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendBroadcast<'info>>,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_locator(&storage)?;
+
+        let recipients = ctx.remaining_accounts;
+        require!(
+            !recipients.is_empty() && recipients.len() % 3 == 0,
+            VoidError::InvalidBroadcastAccounts
+        );
+        require!(
+            recipients.len() / 3 <= MAX_BROADCAST_RECIPIENTS,
+            VoidError::TooManyRecipients
+        );
+
+        let sender = &ctx.accounts.sender;
+        let now = Clock::get()?.unix_timestamp;
+        let rent = Rent::get()?.minimum_balance(DirectMessage::SPACE);
+
+        for accounts in recipients.chunks_exact(3) {
+            let (inbox_info, message_info, block_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+            let mut inbox = Account::<Inbox>::try_from(inbox_info)?;
+            require!(inbox.active, VoidError::InboxInactive);
+            require_keys_neq!(sender.key(), inbox.owner, VoidError::CannotMessageSelf);
+
+            let (block_key, _) = Pubkey::find_program_address(
+                &[seeds::BLOCK, inbox.owner.as_ref(), sender.key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(block_info.key(), block_key, VoidError::InvalidBroadcastAccounts);
+            require!(block_info.data_is_empty(), VoidError::SenderBlocked);
+
+            let message_id = inbox.message_count;
+            let id_bytes = message_id.to_le_bytes();
+            let (message_key, bump) = Pubkey::find_program_address(
+                &seeds::dm_seeds(&inbox.owner, &id_bytes),
+                ctx.program_id,
+            );
+            require_keys_eq!(message_info.key(), message_key, VoidError::InvalidBroadcastAccounts);
+
+            // Fees are paid to the owner account, which broadcasts don't carry
+            require!(inbox.message_fee == 0, VoidError::InsufficientFee);
+            require!(!inbox.allowlist_only, VoidError::SenderNotAllowlisted);
+
+            // The stake rides along with the rent when the PDA is funded
+            let stake = inbox.min_stake;
+            let lamports = rent.checked_add(stake).ok_or(VoidError::CounterOverflow)?;
+            require!(sender.lamports() >= lamports, VoidError::InsufficientStake);
+
+            create_pda(
+                sender,
+                message_info,
+                &ctx.accounts.system_program,
+                DirectMessage::SPACE,
+                lamports,
+                &[seeds::DM, inbox.owner.as_ref(), &id_bytes, &[bump]],
+            )?;
+
+            let msg = DirectMessage {
+                id: message_id,
+                sender: sender.key(),
+                recipient: inbox.owner,
+                storage: storage.clone(),
+                content_hash,
+                ephemeral_key: None,
+                label: None,
+                content_bytes: None,
+                attachment_count: None,
+                burn_after_reading: inbox.default_burn,
+                burned: false,
+                expires_at: None,
+                read_at: None,
+                parent_id: None,
+                stake,
+                rent_recipient: Some(sender.key()),
+                timestamp: now,
+                schema_version: CURRENT_SCHEMA,
+                bump,
+            };
+            msg.try_serialize(&mut &mut message_info.try_borrow_mut_data()?[..])?;
+
+            inbox.message_count = message_id
+                .checked_add(1)
+                .ok_or(VoidError::CounterOverflow)?;
+            inbox.exit(ctx.program_id)?;
+
+            emit!(MessageSent {
+                message: message_key,
+                id: message_id,
+                sender: msg.sender,
+                recipient: msg.recipient,
+                key_version: inbox.key_version,
+                burn_after_reading: msg.burn_after_reading,
+                timestamp: now,
+            });
+        }
+        Ok(())
+    }
+
+    /// Leave a message for a wallet that has no inbox yet. The sender seals the
+    /// payload to the recipient's wallet key by ECDH with a fresh key pair and
+    /// publishes its public half as `ephemeral_key`, so no inbox key is needed.
+    /// The message waits in a `PendingMessage` at
+    /// `[b"pending_dm", recipient, id]`, numbered by the recipient's
+    /// `PendingInbox`, until the recipient claims it with `claim_pending_message`.
+    /// The client must pass `recipient_inbox` = PDA of `[b"inbox", recipient]`,
+    /// which must not exist (use `send_direct_message` then), and `sender_block`
+    /// as for `send_direct_message`.
+    pub fn send_to_unactivated(
+        ctx: Context<SendToUnactivated>,
+        recipient: Pubkey,
+        storage: StoragePointer,
+        content_hash: [u8; 32],
+        ephemeral_key: [u8; 65],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, VoidError::ProgramPaused);
+        check_locator(&storage)?;
+        require_keys_neq!(ctx.accounts.sender.key(), recipient, VoidError::CannotMessageSelf);
+        require!(
+            ctx.accounts.recipient_inbox.data_is_empty(),
+            VoidError::InboxAlreadyActive
+        );
+        require!(ctx.accounts.sender_block.data_is_empty(), VoidError::SenderBlocked);
+
+        let pending_inbox = &mut ctx.accounts.pending_inbox;
+        let pending_id = pending_inbox.message_count;
+        pending_inbox.recipient = recipient;
+        pending_inbox.message_count = pending_id
+            .checked_add(1)
+            .ok_or(VoidError::CounterOverflow)?;
+        pending_inbox.bump = ctx.bumps.pending_inbox;
+
+        let pending = &mut ctx.accounts.pending_message;
+        pending.id = pending_id;
+        pending.sender = ctx.accounts.sender.key();
+        pending.recipient = recipient;
+        pending.storage = storage;
+        pending.content_hash = content_hash;
+        pending.ephemeral_key = ephemeral_key;
+        pending.timestamp = Clock::get()?.unix_timestamp;
+        pending.bump = ctx.bumps.pending_message;
+
+        emit!(PendingMessageSent {
+            pending_message: pending.key(),
+            id: pending_id,
+            sender: pending.sender,
+            recipient,
+            timestamp: pending.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Move a pending message into the recipient's activated inbox (recipient only).
+    /// Only the wallet the message was addressed to can sign for it, and the inbox
+    /// is the PDA of that signer, so a sender cannot claim on the recipient's behalf
+    /// or divert the message into an inbox of their own. The message keeps its
+    /// sender, send time, payload and `ephemeral_key`, gets the next ID in the inbox, and
+    /// follows the inbox's `default_burn`; stakes and fees don't apply. The
+    /// recipient pays the new message's rent and the pending message's rent goes
+    /// back to the sender, passed as `sender`.
+    pub fn claim_pending_message(ctx: Context<ClaimPendingMessage>) -> Result<()> {
+        let inbox = &mut ctx.accounts.recipient_inbox;
+        require!(inbox.active, VoidError::InboxInactive);
+        let message_id = inbox.message_count;
+        inbox.message_count = message_id
+            .checked_add(1)
+            .ok_or(VoidError::CounterOverflow)?;
+
+        let pending = &ctx.accounts.pending_message;
+        let msg = &mut ctx.accounts.message;
+        msg.id = message_id;
+        msg.sender = pending.sender;
+        msg.recipient = pending.recipient;
+        msg.storage = pending.storage.clone();
+        msg.content_hash = pending.content_hash;
+        msg.ephemeral_key = Some(pending.ephemeral_key);
+        msg.label = None;
+        msg.content_bytes = None;
+        msg.attachment_count = None;
+        msg.burn_after_reading = inbox.default_burn;
+        msg.burned = false;
+        msg.expires_at = None;
+        msg.read_at = None;
+        msg.parent_id = None;
+        msg.stake = 0;
+        msg.rent_recipient = Some(ctx.accounts.recipient.key());
+        msg.timestamp = pending.timestamp;
+        msg.schema_version = CURRENT_SCHEMA;
+        msg.bump = ctx.bumps.message;
+
+        emit!(PendingMessageClaimed {
+            pending_message: pending.key(),
+            message: msg.key(),
+            id: message_id,
+            sender: msg.sender,
+            recipient: msg.recipient,
+        });
+        Ok(())
+    }
+
+    /// Mark a message as burned (recipient only).
+    /// Once burned, the message reference is flagged and cannot be "unburned".
+    /// Burning an unread message treats it as spam: any escrowed stake goes to the recipient.
+    pub fn burn_message(ctx: Context<BurnMessage>) -> Result<()> {
+        let msg = &mut ctx.accounts.message;
+        require!(!msg.burned, VoidError::AlreadyBurned);
+        msg.burned = true;
+        release_stake(msg, &ctx.accounts.recipient.to_account_info())?;
+
+        emit!(MessageBurned {
+            message: msg.key(),
+            recipient: msg.recipient,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Burn several messages at once (recipient only), passed as writable
+    /// `remaining_accounts`, at most `MAX_BURN_BATCH` per call. Messages that are
+    /// already burned are skipped so a stale inbox list doesn't sink the batch;
+    /// a message addressed to anyone else fails the whole transaction.
+    pub fn burn_messages<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BurnMessages<'info>>,
+    ) -> Result<()> {
+        let messages = ctx.remaining_accounts;
+        require!(messages.len() <= MAX_BURN_BATCH, VoidError::TooManyMessages);
+
+        let recipient = ctx.accounts.recipient.to_account_info();
+        let now = Clock::get()?.unix_timestamp;
+        for info in messages {
+            let mut msg = Account::<DirectMessage>::try_from(info)?;
+            require_keys_eq!(msg.recipient, recipient.key(), VoidError::NotMessageRecipient);
+            if msg.burned {
+                continue;
+            }
+            msg.burned = true;
+            release_stake(&mut msg, &recipient)?;
+            msg.exit(ctx.program_id)?;
+
+            emit!(MessageBurned {
+                message: msg.key(),
+                recipient: msg.recipient,
+                timestamp: now,
+            });
+        }
+        Ok(())
+    }
+
+    /// Record that the recipient opened a message (recipient only).
+    /// Decryption happens off-chain, so this is an advisory receipt the recipient
+    /// chooses to publish, not proof of reading. Only the first read is recorded,
+    /// and it refunds any escrowed stake to the sender.
+    /// A `burn_after_reading` message is burned in the same instruction, so it is
+    /// never left read but unburned.
+    pub fn mark_read(ctx: Context<MarkRead>) -> Result<()> {
+        let msg = &mut ctx.accounts.message;
+        if msg.read_at.is_none() {
+            let now = Clock::get()?.unix_timestamp;
+            msg.read_at = Some(now);
+            release_stake(msg, &ctx.accounts.sender.to_account_info())?;
+
+            emit!(MessageRead {
+                message: msg.key(),
+                recipient: msg.recipient,
+                timestamp: now,
+            });
+
+            if msg.burn_after_reading && !msg.burned {
+                msg.burned = true;
+                emit!(MessageBurned {
+                    message: msg.key(),
+                    recipient: msg.recipient,
+                    timestamp: now,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Pull back an unread message (sender only). Its stake goes back to the
+    /// sender and its rent to its `rent_recipient`, or to the sender for messages
+    /// that predate one. Fails once the recipient has marked it read.
+    pub fn recall_message(ctx: Context<RecallMessage>) -> Result<()> {
+        let msg = &mut ctx.accounts.message;
+        require!(msg.read_at.is_none(), VoidError::AlreadyRead);
+        msg.burned = true;
+        release_stake(msg, &ctx.accounts.sender.to_account_info())?;
+
+        emit!(MessageRecalled {
+            message: msg.key(),
+            sender: msg.sender,
+            recipient: msg.recipient,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg.close(rent_destination(
+            msg.rent_recipient,
+            ctx.accounts.sender.to_account_info(),
+            ctx.accounts.rent_recipient.as_deref(),
+        )?)
+    }
+
+    /// Close a burned message (recipient only), refunding its rent to its
+    /// `rent_recipient`, or to the recipient for messages that predate one.
+    /// `message_count` is not decremented, so the message ID is retired and never reused.
+    /// With `tombstone` set, the recipient also pays for a `Tombstone` recording that
+    /// the message existed and when it was closed.
+    pub fn close_message(ctx: Context<CloseMessage>, tombstone: bool) -> Result<()> {
+        let msg = &ctx.accounts.message;
+        require!(msg.burned, VoidError::MessageNotBurned);
+        write_tombstone(
+            ctx.accounts.tombstone.as_mut(),
+            ctx.bumps.tombstone,
+            tombstone,
+            TombstoneKind::Message,
+            msg.id,
+        )?;
+
+        emit!(MessageClosed {
+            message: msg.key(),
+            id: msg.id,
+            recipient: msg.recipient,
+        });
+        msg.close(rent_destination(
+            msg.rent_recipient,
+            ctx.accounts.recipient.to_account_info(),
+            ctx.accounts.rent_recipient.as_deref(),
+        )?)
+    }
+
+    /// Upgrade a message sent under an older layout to the current one: grow the
+    /// account, give the fields its layout lacks their defaults (messages from
+    /// before schema 16 keep refunding the closing instruction's usual
+    /// destination) and stamp `CURRENT_SCHEMA`. Any escrowed stake stays on top of
+    /// the new rent. Permissionless, like `burn_expired_message`, so a message can
+    /// be cleared up even if neither party comes back; the payer covers the extra
+    /// rent. Current messages are left untouched, so the call is safe to repeat.
+    pub fn migrate_message(ctx: Context<MigrateMessage>) -> Result<()> {
+        let info = ctx.accounts.message.to_account_info();
+        if info.data_len() == DirectMessage::SPACE {
+            return Ok(());
+        }
+        let layout = legacy_layout(&DirectMessage::LEGACY_LAYOUTS, info.data_len())?;
+        let mut msg = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.starts_with(DirectMessage::DISCRIMINATOR),
+                VoidError::NotAMessage
+            );
+            DirectMessage::read_legacy(&mut &data[8..], layout)?
+        };
+        require!(msg.schema_version >= layout, VoidError::UnknownLayout);
+        let from_version = msg.schema_version;
+        msg.schema_version = CURRENT_SCHEMA;
+
+        grow_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            DirectMessage::SPACE,
+            msg.stake,
+        )?;
+        msg.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(MessageMigrated {
+            message: info.key(),
+            recipient: msg.recipient,
+            from_version,
+            to_version: CURRENT_SCHEMA,
+        });
+        Ok(())
+    }
+
+    /// Burn and close an expired message. Any unclaimed stake goes back to the
+    /// sender and the rent to its `rent_recipient`, or to the sender for messages
+    /// that predate one.
+    /// Permissionless: any signer can call this once `expires_at` has passed,
+    /// so messages expire even if the recipient never comes back.
+    pub fn burn_expired_message(ctx: Context<BurnExpiredMessage>) -> Result<()> {
+        let msg = &mut ctx.accounts.message;
+        let expires_at = msg.expires_at.ok_or(VoidError::NoExpiry)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= expires_at, VoidError::NotYetExpired);
+        msg.burned = true;
+        release_stake(msg, &ctx.accounts.sender.to_account_info())?;
+
+        emit!(MessageBurned {
+            message: msg.key(),
+            recipient: msg.recipient,
+            timestamp: now,
+        });
+        msg.close(rent_destination(
+            msg.rent_recipient,
+            ctx.accounts.sender.to_account_info(),
+            ctx.accounts.rent_recipient.as_deref(),
+        )?)
+    }
+
+    /// Summarize messages `start..start + count` of an inbox, passed in order as
+    /// `remaining_accounts` (the `[b"dm", owner, id]` PDAs). Every account must be
+    /// the next PDA in the range, so a client can't skip or reorder messages.
+    /// Closed messages are reported with no sender. Returned via `.view()`.
+    pub fn get_message_window<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetMessageWindow<'info>>,
+        start: u64,
+        count: u16,
+    ) -> Result<Vec<MessageSummary>> {
+        let inbox = &ctx.accounts.inbox;
+        require!(count <= MAX_MESSAGE_WINDOW, VoidError::TooManyMessages);
+        let end = start
+            .checked_add(u64::from(count))
+            .ok_or(VoidError::CounterOverflow)?;
+        require!(end <= inbox.message_count, VoidError::InvalidMessageWindow);
+        require!(
+            ctx.remaining_accounts.len() == usize::from(count),
+            VoidError::InvalidMessageWindow
+        );
+
+        let mut window = Vec::with_capacity(usize::from(count));
+        for (id, info) in (start..end).zip(ctx.remaining_accounts) {
+            let (message_key, _) = Pubkey::find_program_address(
+                &seeds::dm_seeds(&inbox.owner, &id.to_le_bytes()),
+                ctx.program_id,
+            );
+            require_keys_eq!(info.key(), message_key, VoidError::InvalidMessageWindow);
+
+            window.push(if info.data_is_empty() {
+                MessageSummary {
+                    id,
+                    sender: None,
+                    burned: true,
+                }
+            } else {
+                let msg = Account::<DirectMessage>::try_from(info)?;
+                MessageSummary {
+                    id,
+                    sender: Some(msg.sender),
+                    burned: msg.burned,
+                }
+            });
+        }
+        Ok(window)
+    }
+
+    // ─── VOID FEED (VOUCH) ─────────────────────────────────────────
+
+    /// Vouch for an article. The content_hash is a SHA-256 of the article URL.
+    /// Each wallet can only vouch once per article (enforced by PDA).
+    pub fn vouch(ctx: Context<CreateVouch>, content_hash: [u8; 32]) -> Result<()> {
+        let vouch = &mut ctx.accounts.vouch;
+        vouch.voucher = ctx.accounts.voucher.key();
+        vouch.content_hash = content_hash;
+        vouch.timestamp = Clock::get()?.unix_timestamp;
+        vouch.bump = ctx.bumps.vouch;
+        Ok(())
+    }
+
+    /// Remove a vouch (only the voucher can remove their own vouch).
+    pub fn unvouch(_ctx: Context<RemoveVouch>) -> Result<()> {
+        // Account will be closed and rent returned to voucher
+        Ok(())
+    }
+
+    /// Create a wallet profile (opt-in to social features).
+    /// By default, reputation is hidden and followers are not allowed.
+    pub fn create_profile(ctx: Context<CreateProfile>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.wallet = ctx.accounts.wallet.key();
+        profile.reputation_visible = false;
+        profile.allow_followers = false;
+        profile.total_vouches = 0;
+        profile.follower_count = 0;
+        profile.following_count = 0;
+        profile.created_at = Clock::get()?.unix_timestamp;
+        profile.bump = ctx.bumps.profile;
+        Ok(())
+    }
+
+    /// Update profile settings (toggle visibility and follower permissions).
+    pub fn update_profile(
+        ctx: Context<UpdateProfile>,
+        reputation_visible: bool,
+        allow_followers: bool,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.reputation_visible = reputation_visible;
+        profile.allow_followers = allow_followers;
+        Ok(())
+    }
+
+    /// Follow another wallet. Target must allow followers.
+    pub fn follow(ctx: Context<CreateFollow>) -> Result<()> {
+        require!(
+            ctx.accounts.target_profile.allow_followers,
+            VoidError::FollowersNotAllowed
+        );
+
+        let follow = &mut ctx.accounts.follow;
+        follow.follower = ctx.accounts.follower.key();
+        follow.following = ctx.accounts.target_profile.wallet;
+        follow.timestamp = Clock::get()?.unix_timestamp;
+        follow.bump = ctx.bumps.follow;
+
+        // Update counts
+        let follower_profile = &mut ctx.accounts.follower_profile;
+        follower_profile.following_count += 1;
+
+        let target_profile = &mut ctx.accounts.target_profile;
+        target_profile.follower_count += 1;
+
+        Ok(())
+    }
+
+    /// Unfollow a wallet.
+    pub fn unfollow(ctx: Context<RemoveFollow>) -> Result<()> {
+        // Update counts
+        let follower_profile = &mut ctx.accounts.follower_profile;
+        follower_profile.following_count = follower_profile.following_count.saturating_sub(1);
+
+        let target_profile = &mut ctx.accounts.target_profile;
+        target_profile.follower_count = target_profile.follower_count.saturating_sub(1);
+
+        // Account will be closed
+        Ok(())
+    }
+}
+
+// ─── HELPERS ────────────────────────────────────────────────────
+
+/// Walk a Merkle branch from `leaf` up to the root.
+/// At each level the low bit of `index` says whether the current node is a
+/// right child (1) or left child (0); parents are sha256(left || right).
+fn compute_merkle_root(leaf: [u8; 32], proof: &[[u8; 32]], index: u32) -> [u8; 32] {
+    let mut node = leaf;
+    let mut index = index;
+    for sibling in proof {
+        node = if index & 1 == 0 {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        };
+        index >>= 1;
+    }
+    node
+}
+
+/// Running hash of a proof chain: `sha256(prev_chain_hash || algo || digest)`.
+/// Chain roots (and unlinked proofs) use an all-zero `prev_chain_hash`.
+fn chain_hash(prev_chain_hash: &[u8; 32], algo: HashAlgo, digest: &[u8]) -> [u8; 32] {
+    hashv(&[prev_chain_hash, &[algo as u8], digest]).to_bytes()
+}
+
+/// Short digest of an org's encryption keys that the org can publish on its own site,
+/// so a source can tell the on-chain keys apart from a squatter's:
+/// `sha256(key_1 || ... || key_n)[..8]`, hashing only the bytes `key_algo` uses.
+/// For a single-key org this is just `sha256(key)[..8]`.
+fn key_fingerprint(key_algo: KeyAlgo, encryption_keys: &[[u8; 65]]) -> [u8; 8] {
+    let keys: Vec<&[u8]> = encryption_keys
+        .iter()
+        .map(|key| &key[..key_algo.key_len()])
+        .collect();
+    let mut fingerprint = [0u8; 8];
+    fingerprint.copy_from_slice(&hashv(&keys).to_bytes()[..8]);
+    fingerprint
+}
+
+/// Check and store an org's 1-`MAX_ORG_KEYS` encryption keys under its `key_algo`,
+/// zeroing unused slots and refreshing the fingerprint.
+fn set_org_keys(org: &mut Organization, keys: &[[u8; 65]]) -> Result<()> {
+    require!(
+        !keys.is_empty() && keys.len() <= MAX_ORG_KEYS,
+        VoidError::InvalidKeyCount
+    );
+    for key in keys {
+        check_encryption_key(org.key_algo, key)?;
+    }
+    org.encryption_keys = [[0u8; 65]; MAX_ORG_KEYS];
+    org.encryption_keys[..keys.len()].copy_from_slice(keys);
+    org.key_count = keys.len() as u8;
+    org.key_fingerprint = key_fingerprint(org.key_algo, keys);
+    Ok(())
+}
+
+/// Check an encryption key is well-formed for its algorithm, so a client bug fails
+/// here instead of leaving sources encrypting to garbage. Bytes past the
+/// algorithm's key length must be zero. P-256 and secp256k1 keys must be
+/// uncompressed points (0x04 prefix) with reduced coordinates that satisfy the
+/// curve equation; both curves have cofactor 1, so that is a full public key
+/// check, and it costs a few thousand CU per key. Every 32-byte string is a
+/// usable X25519 key, so those get no further check.
+fn check_encryption_key(key_algo: KeyAlgo, encryption_key: &[u8; 65]) -> Result<()> {
+    let (key, padding) = encryption_key.split_at(key_algo.key_len());
+    require!(
+        padding.iter().all(|&b| b == 0),
+        VoidError::InvalidEncryptionKey
+    );
+    let curve = match key_algo {
+        KeyAlgo::P256 => &P256,
+        KeyAlgo::Secp256k1 => &SECP256K1,
+        KeyAlgo::X25519 => return Ok(()),
+    };
+    require!(
+        key[0] == 0x04 && curve.contains(key),
+        VoidError::InvalidEncryptionKey
+    );
+    Ok(())
+}
+
+/// A short-Weierstrass curve `y² = x³ + ax + b` over a 256-bit prime field, with
+/// its constants as little-endian 64-bit limbs for Montgomery arithmetic (R = 2^256).
+struct Curve {
+    /// The field prime
+    p: [u64; 4],
+    /// -p⁻¹ mod 2^64
+    n0: u64,
+    /// R² mod p, which moves a value into Montgomery form
+    r2: [u64; 4],
+    /// The curve's b in Montgomery form
+    b: [u64; 4],
+    /// a = -3 (P-256) rather than a = 0 (secp256k1)
+    a_is_minus_3: bool,
+}
+
+const P256: Curve = Curve {
+    p: [0xffffffffffffffff, 0x00000000ffffffff, 0x0000000000000000, 0xffffffff00000001],
+    n0: 0x0000000000000001,
+    r2: [0x0000000000000003, 0xfffffffbffffffff, 0xfffffffffffffffe, 0x00000004fffffffd],
+    b: [0xd89cdf6229c4bddf, 0xacf005cd78843090, 0xe5a220abf7212ed6, 0xdc30061d04874834],
+    a_is_minus_3: true,
+};
+
+const SECP256K1: Curve = Curve {
+    p: [0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff],
+    n0: 0xd838091dd2253531,
+    r2: [0x000007a2000e90a1, 0x0000000000000001, 0x0000000000000000, 0x0000000000000000],
+    b: [0x0000000700001ab7, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000],
+    a_is_minus_3: false,
+};
+
+impl Curve {
+    /// Whether the uncompressed point `0x04 || x || y` lies on the curve. Both
+    /// coordinates must be reduced, i.e. below the field prime.
+    fn contains(&self, point: &[u8]) -> bool {
+        let (x, y) = (limbs_be(&point[1..33]), limbs_be(&point[33..65]));
+        if !less_than(&x, &self.p) || !less_than(&y, &self.p) {
+            return false;
+        }
+        let (x, y) = (self.mul(&x, &self.r2), self.mul(&y, &self.r2));
+        let lhs = self.mul(&y, &y);
+        let mut rhs = self.mul(&self.mul(&x, &x), &x);
+        if self.a_is_minus_3 {
+            for _ in 0..3 {
+                rhs = self.sub(&rhs, &x);
+            }
+        }
+        lhs == self.add(&rhs, &self.b)
+    }
+
+    /// a + b mod p, for reduced a and b
+    fn add(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let mut sum = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let v = u128::from(a[i]) + u128::from(b[i]) + carry;
+            sum[i] = v as u64;
+            carry = v >> 64;
+        }
+        if carry != 0 || !less_than(&sum, &self.p) {
+            sub_limbs(&sum, &self.p).0
+        } else {
+            sum
+        }
+    }
+
+    /// a - b mod p, for reduced a and b
+    fn sub(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let (diff, borrow) = sub_limbs(a, b);
+        if borrow {
+            let mut fixed = [0u64; 4];
+            let mut carry = 0u128;
+            for i in 0..4 {
+                let v = u128::from(diff[i]) + u128::from(self.p[i]) + carry;
+                fixed[i] = v as u64;
+                carry = v >> 64;
+            }
+            fixed
+        } else {
+            diff
+        }
+    }
+
+    /// Montgomery product a·b·R⁻¹ mod p (CIOS), for reduced a and b
+    fn mul(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let mut t = [0u64; 6];
+        for &bi in b {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let v = u128::from(t[j]) + u128::from(a[j]) * u128::from(bi) + carry;
+                t[j] = v as u64;
+                carry = v >> 64;
+            }
+            let v = u128::from(t[4]) + carry;
+            t[4] = v as u64;
+            t[5] = (v >> 64) as u64;
+
+            let m = t[0].wrapping_mul(self.n0);
+            let mut carry = (u128::from(t[0]) + u128::from(m) * u128::from(self.p[0])) >> 64;
+            for j in 1..4 {
+                let v = u128::from(t[j]) + u128::from(m) * u128::from(self.p[j]) + carry;
+                t[j - 1] = v as u64;
+                carry = v >> 64;
+            }
+            let v = u128::from(t[4]) + carry;
+            t[3] = v as u64;
+            t[4] = t[5] + (v >> 64) as u64;
+        }
+        let product = [t[0], t[1], t[2], t[3]];
+        if t[4] != 0 || !less_than(&product, &self.p) {
+            sub_limbs(&product, &self.p).0
+        } else {
+            product
+        }
+    }
+}
+
+/// Big-endian 32 bytes as little-endian 64-bit limbs
+fn limbs_be(bytes: &[u8]) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (i, &byte) in bytes.iter().enumerate() {
+        let limb = &mut limbs[3 - i / 8];
+        *limb = (*limb << 8) | u64::from(byte);
+    }
+    limbs
+}
+
+fn less_than(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// a - b over 256 bits, and whether it borrowed
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], bool) {
+    let mut diff = [0u64; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(u64::from(borrow));
+        diff[i] = d;
+        borrow = b1 || b2;
+    }
+    (diff, borrow)
+}
+
+/// Claim the tip's `SubmissionDedup` record when the org has `dedup` on. A record
+/// an earlier tip already claimed means the locator is a duplicate.
+fn record_dedup(
+    org: &Account<'_, Organization>,
+    record: Option<&mut Account<'_, SubmissionDedup>>,
+    submission_id: u64,
+    bump: Option<u8>,
+) -> Result<()> {
+    if !org.dedup {
+        return Ok(());
+    }
+    let record = record.ok_or(VoidError::DedupRecordRequired)?;
+    require!(
+        record.organization == Pubkey::default(),
+        VoidError::DuplicateSubmission
+    );
+    record.organization = org.key();
+    record.submission_id = submission_id;
+    record.bump = bump.unwrap_or_default();
+    Ok(())
+}
+
+/// Check a tip's storage locator fits the org's `max_hash_len`, which its
+/// submission account is sized for.
+fn check_org_locator(org: &Organization, storage: &StoragePointer) -> Result<()> {
+    require!(
+        storage.locator.len() <= usize::from(org.max_hash_len),
+        VoidError::LocatorExceedsOrgLimit
+    );
+    Ok(())
+}
+
+/// PDA seed identifying a locator in `SubmissionDedup`, which is too long to seed directly.
+fn locator_seed(storage: &StoragePointer) -> [u8; 32] {
+    hashv(&[storage.locator.as_bytes()]).to_bytes()
+}
+
+/// Check a storage locator fits the limit of the network it claims to live on.
+fn check_locator(storage: &StoragePointer) -> Result<()> {
+    require!(
+        storage.locator.len() <= storage.kind.max_locator_len(),
+        VoidError::LocatorTooLong
+    );
+    Ok(())
+}
+
+/// Check a proof digest fits the account and matches its algorithm's output size.
+fn check_digest(algo: HashAlgo, digest: &[u8]) -> Result<()> {
+    require!(digest.len() <= MAX_DIGEST_LEN, VoidError::DigestTooLong);
+    require!(digest.len() == algo.digest_len(), VoidError::InvalidDigestLength);
+    Ok(())
+}
+
+/// Split a digest into the two PDA seeds used by proofs. Anything past 64 bytes is
+/// dropped here so derivation can't panic; `check_digest` rejects it afterward.
+fn digest_seeds(digest: &[u8]) -> (&[u8], &[u8]) {
+    let (head, tail) = digest.split_at(digest.len().min(MAX_SEED_LEN));
+    (head, &tail[..tail.len().min(MAX_SEED_LEN)])
+}
+
+/// Pay a message's escrowed stake out to `to` and zero it.
+///
+/// Escrow invariants: while `stake > 0` the message PDA holds its rent-exempt
+/// minimum plus `stake`, and nothing more unless someone sent lamports to its
+/// address before it was created. The stake leaves the PDA exactly once:
+/// to the sender on first read, to the recipient on burn, or back to the
+/// sender when they recall it or an expired message is burned. Zeroing `stake`
+/// here means a later close only ever moves rent.
+fn release_stake<'info>(
+    msg: &mut Account<'info, DirectMessage>,
+    to: &AccountInfo<'info>,
+) -> Result<()> {
+    let stake = msg.stake;
+    if stake > 0 {
+        msg.stake = 0;
+        msg.sub_lamports(stake)?;
+        to.add_lamports(stake)?;
+    }
+    Ok(())
+}
+
+/// First schema version of the older layout an account of `len` bytes holds, from
+/// a `(size, first schema)` table of its type's past layouts. Every layout change
+/// grew its account, so the size alone tells them apart.
+fn legacy_layout(layouts: &[(usize, u8)], len: usize) -> Result<u8> {
+    layouts
+        .iter()
+        .find(|(size, _)| *size == len)
+        .map(|(_, schema)| *schema)
+        .ok_or_else(|| error!(VoidError::UnknownLayout))
+}
+
+/// Grow `info` to `new_len` bytes for a migration, first topping it up from `payer`
+/// to the rent-exempt minimum plus `reserved`, the lamports it holds on top of rent
+/// (a message's escrowed stake), so growing never eats into them.
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+    reserved: u64,
+) -> Result<()> {
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_add(reserved)
+        .saturating_sub(info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    info.resize(new_len)?;
+    Ok(())
+}
+
+/// Create a program-owned PDA whose address is only known at run time, the way
+/// Anchor's `init` does: `create_account` fails once anyone has sent the address
+/// lamports, so a pre-funded address is instead topped up to `lamports`, then
+/// allocated and assigned. A donation above `lamports` stays on the account.
+fn create_pda<'info>(
+    payer: &Signer<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    lamports: u64,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let program = system_program.to_account_info();
+    if target.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                program,
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: target.clone(),
+                },
+                &[signer_seeds],
+            ),
+            lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    let top_up = lamports.saturating_sub(target.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                program.clone(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: target.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            program.clone(),
+            system_program::Allocate {
+                account_to_allocate: target.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            program,
+            system_program::Assign {
+                account_to_assign: target.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Where a closed submission or message sends its rent. Accounts store the
+/// wallet that fronted their rent in `rent_recipient`; when that differs from
+/// `default` (the instruction's usual destination) it must be passed as the
+/// optional `rent_recipient` account. Accounts from before schema 16 stored
+/// none and refund `default`.
+fn rent_destination<'info>(
+    stored: Option<Pubkey>,
+    default: AccountInfo<'info>,
+    passed: Option<&AccountInfo<'info>>,
+) -> Result<AccountInfo<'info>> {
+    match stored {
+        Some(recipient) if recipient != default.key() => {
+            let passed = passed.ok_or(VoidError::WrongRentRecipient)?;
+            require_keys_eq!(passed.key(), recipient, VoidError::WrongRentRecipient);
+            Ok(passed.to_account_info())
+        }
+        _ => Ok(default),
+    }
+}
+
+/// Whether `info` is a submission of either layout, as opposed to a wallet passed
+/// alongside submissions to receive their rent.
+fn is_submission(info: &AccountInfo) -> bool {
+    info.owner == &crate::ID
+        && info.try_borrow_data().is_ok_and(|data| {
+            data.starts_with(Submission::DISCRIMINATOR)
+                || data.starts_with(SubmissionZC::DISCRIMINATOR)
+        })
+}
+
+/// Accounts a tip is checked and charged against, shared by every tip instruction
+/// so all of them enforce the same rules.
+struct TipAccounts<'a, 'info> {
+    config: &'a Config,
+    organization: &'a mut Account<'info, Organization>,
+    treasury: &'a Account<'info, OrgTreasury>,
+    submitter_state: &'a mut SubmitterState,
+    global_index: &'a mut GlobalIndex,
+    allowlisted: bool,
+    /// Whether the tip references a `Proof`
+    has_proof: bool,
+    /// Whether the org's validator program approved the tip
+    validated: bool,
+    /// Whether the tip carries a `SubmissionDedup` record for its locator
+    deduplicated: bool,
+    /// Pays the lamport and token fees (None = fee-free sponsored tip)
+    payer: Option<&'a Signer<'info>>,
+    fee_mint: Option<&'a Account<'info, Mint>>,
+    submitter_token_account: Option<&'a Account<'info, TokenAccount>>,
+    org_fee_account: Option<&'a Account<'info, TokenAccount>>,
+    token_program: Option<&'a Program<'info, Token>>,
+    /// Receives the protocol's cut of the lamport fee
+    protocol_treasury: Option<&'a UncheckedAccount<'info>>,
+    system_program: &'a Program<'info, System>,
+}
+
+/// Run every org-level gate for a new tip (pause, active, category, cap, allowlist,
+/// window, cooldown, rate limit), collect its fees (unless sponsored) and take the next
+/// submission ID and global ID. Returns the ID and the current timestamp.
+fn admit_tip(
+    accounts: TipAccounts<'_, '_>,
+    submitter_state_bump: u8,
+    global_index_bump: u8,
+    category: u8,
+) -> Result<(u64, i64)> {
+    require!(!accounts.config.paused, VoidError::ProgramPaused);
+    require!(accounts.organization.active, VoidError::OrgInactive);
+    require!(
+        accounts.organization.accepts_category(category),
+        VoidError::CategoryNotAccepted
+    );
+    require!(
+        accounts.organization.max_submissions == 0
+            || accounts.organization.submission_count
+                < accounts.organization.max_submissions,
+        VoidError::SubmissionCapReached
+    );
+    require!(
+        !accounts.organization.allowlist_only || accounts.allowlisted,
+        VoidError::NotAllowlisted
+    );
+    require!(
+        !accounts.organization.require_proof || accounts.has_proof,
+        VoidError::ProofRequired
+    );
+    require!(
+        accounts.organization.validator_program.is_none() || accounts.validated,
+        VoidError::ValidatorRequired
+    );
+    require!(
+        !accounts.organization.dedup || accounts.deduplicated,
+        VoidError::DedupRecordRequired
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let org = &accounts.organization;
+    require!(
+        (org.submission_open_at == 0 || org.submission_open_at <= now)
+            && (org.submission_close_at == 0 || now < org.submission_close_at),
+        VoidError::SubmissionWindowClosed
+    );
+
+    let submitter_state = &mut *accounts.submitter_state;
+    require!(
+        now.saturating_sub(submitter_state.last_submission_ts)
+            >= i64::from(accounts.organization.cooldown_secs),
+        VoidError::SubmissionTooSoon
+    );
+    submitter_state.last_submission_ts = now;
+    submitter_state.bump = submitter_state_bump;
+
+    if let Some(payer) = accounts.payer {
+        charge_tip_fees(&accounts, payer)?;
+    }
+
+    let org = &mut *accounts.organization;
+
+    if org.window_limit > 0 {
+        if now.saturating_sub(org.window_start) >= i64::from(org.window_secs) {
+            org.window_start = now;
+            org.window_count = 0;
+        }
+        require!(
+            org.window_count < org.window_limit,
+            VoidError::RateLimitExceeded
+        );
+        org.window_count += 1;
+    }
+
+    let submission_id = org.submission_count;
+    org.submission_count = org
+        .submission_count
+        .checked_add(1)
+        .ok_or(VoidError::CounterOverflow)?;
+    org.open_submissions = org
+        .open_submissions
+        .checked_add(1)
+        .ok_or(VoidError::CounterOverflow)?;
+
+    let global = &mut *accounts.global_index;
+    let global_id = global.total_submissions;
+    global.total_submissions = global
+        .total_submissions
+        .checked_add(1)
+        .ok_or(VoidError::CounterOverflow)?;
+    global.bump = global_index_bump;
+
+    emit!(GlobalSubmission {
+        global_id,
+        organization: org.key(),
+        local_id: submission_id,
+    });
+
+    Ok((submission_id, now))
+}
+
+/// Count a relayed tip against its source IP's window, restarting the window once
+/// it lapses. Orgs without a per-IP limit skip this; otherwise the relayer must
+/// pass `ip_hash` and its state PDA. The salted hash only seeds the PDA.
+fn admit_ip(
+    org: &Organization,
+    state: Option<&mut Account<'_, IpRateState>>,
+    ip_hash: Option<[u8; 32]>,
+    bump: Option<u8>,
+    now: i64,
+) -> Result<()> {
+    if org.ip_window_limit == 0 {
+        return Ok(());
+    }
+    let state = state
+        .filter(|_| ip_hash.is_some())
+        .ok_or(VoidError::IpHashRequired)?;
+    if now.saturating_sub(state.window_start) >= i64::from(org.ip_window_secs) {
+        state.window_start = now;
+        state.window_count = 0;
+    }
+    require!(
+        state.window_count < org.ip_window_limit,
+        VoidError::IpRateLimitExceeded
+    );
+    state.window_count += 1;
+    state.bump = bump.unwrap_or_default();
+    Ok(())
+}
+
+/// Run the org's validator program over a tip, if it has one. Returns whether the
+/// tip was validated, which `admit_tip` requires whenever a validator is set.
+fn validate_tip<'info>(
+    organization: &Account<'info, Organization>,
+    program: Option<&UncheckedAccount<'info>>,
+    submitter: &AccountInfo<'info>,
+    extra: &[AccountInfo<'info>],
+    content_hash: [u8; 32],
+    category: u8,
+) -> Result<bool> {
+    let expected = match organization.validator_program {
+        Some(expected) => expected,
+        None => return Ok(false),
+    };
+    let program = program
+        .filter(|program| program.key() == expected)
+        .ok_or(VoidError::ValidatorRequired)?;
+    call_validator(
+        program,
+        &organization.to_account_info(),
+        submitter,
+        extra,
+        content_hash,
+        category,
+    )?;
+    Ok(true)
+}
+
+/// Ask an org's validator program to approve a tip; any error it returns fails
+/// the tip. The interface a validator implements:
+/// - instruction data: `VALIDATE_DISCRIMINATOR` followed by the Borsh encoding of
+///   `(content_hash: [u8; 32], category: u8)`
+/// - accounts: the organization (read-only), the submitter (read-only signer; the
+///   relayer for delegated tips), then the tip's `remaining_accounts` as passed,
+///   e.g. a proof of humanity
+///
+/// Zero-copy tips store no content hash and pass 32 zero bytes in its place.
+fn call_validator<'info>(
+    program: &UncheckedAccount<'info>,
+    organization: &AccountInfo<'info>,
+    submitter: &AccountInfo<'info>,
+    extra: &[AccountInfo<'info>],
+    content_hash: [u8; 32],
+    category: u8,
+) -> Result<()> {
+    let mut data = VALIDATE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&content_hash);
+    data.push(category);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(organization.key(), false),
+        AccountMeta::new_readonly(submitter.key(), true),
+    ];
+    let mut infos = vec![organization.clone(), submitter.clone()];
+    for info in extra {
+        accounts.push(if info.is_writable {
+            AccountMeta::new(info.key(), info.is_signer)
+        } else {
+            AccountMeta::new_readonly(info.key(), info.is_signer)
+        });
+        infos.push(info.clone());
+    }
+    infos.push(program.to_account_info());
+
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts,
+        data,
+    };
+    invoke(&ix, &infos)?;
+    Ok(())
+}
+
+/// Collect an org's lamport fee and, if it has a `fee_mint`, its token fee from `payer`.
+/// The config's `protocol_fee_bps` of the lamport fee, rounded down, goes to the
+/// protocol treasury and the remainder to the org, so no lamport is lost; token
+/// fees go to the org in full.
+fn charge_tip_fees<'info>(
+    accounts: &TipAccounts<'_, 'info>,
+    payer: &Signer<'info>,
+) -> Result<()> {
+    let fee = accounts.organization.submission_fee;
+    let protocol_fee = (u128::from(fee) * u128::from(accounts.config.protocol_fee_bps) / 10_000) as u64;
+    if protocol_fee > 0 {
+        let protocol_treasury = accounts
+            .protocol_treasury
+            .ok_or(VoidError::MissingFeeAccounts)?;
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: protocol_treasury.to_account_info(),
+                },
+            ),
+            protocol_fee,
+        )?;
+    }
+    let org_fee = fee - protocol_fee;
+    if org_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: accounts.treasury.to_account_info(),
+                },
+            ),
+            org_fee,
+        )?;
+    }
+
+    if let Some(fee_mint) = accounts.organization.fee_mint {
+        let mint = accounts.fee_mint.ok_or(VoidError::MissingFeeAccounts)?;
+        require_keys_eq!(mint.key(), fee_mint, VoidError::WrongFeeMint);
+
+        let token_fee = accounts.organization.fee_amount;
+        if token_fee > 0 {
+            let (Some(from), Some(to), Some(token_program)) = (
+                accounts.submitter_token_account,
+                accounts.org_fee_account,
+                accounts.token_program,
+            ) else {
+                return err!(VoidError::MissingFeeAccounts);
+            };
+            require_keys_eq!(from.mint, fee_mint, VoidError::WrongFeeMint);
+            require_keys_eq!(to.mint, fee_mint, VoidError::WrongFeeMint);
+
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    token::Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: payer.to_account_info(),
+                    },
+                ),
+                token_fee,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Count one of the org's submissions as closed.
+fn release_submission(org: &mut Organization) -> Result<()> {
+    org.open_submissions = org
+        .open_submissions
+        .checked_sub(1)
+        .ok_or(VoidError::CounterOverflow)?;
+    Ok(())
+}
+
+/// Shared body of `freeze_submission` and `thaw_submission`.
+fn set_submission_frozen(ctx: Context<SetSubmissionFrozen>, frozen: bool) -> Result<()> {
+    record_admin_activity(&mut ctx.accounts.organization)?;
+    let sub = &mut ctx.accounts.submission;
+    sub.frozen = frozen;
+
+    emit!(SubmissionFreezeChanged {
+        submission: sub.key(),
+        organization: sub.organization,
+        frozen,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Fill in the tombstone of an account being closed. The flag and the optional
+/// account must agree, so a caller never pays for a tombstone by accident.
+fn write_tombstone(
+    tombstone: Option<&mut Account<Tombstone>>,
+    bump: Option<u8>,
+    enabled: bool,
+    kind: TombstoneKind,
+    id: u64,
+) -> Result<()> {
+    require!(enabled == tombstone.is_some(), VoidError::TombstoneMismatch);
+    if let (Some(stone), Some(bump)) = (tombstone, bump) {
+        stone.id = id;
+        stone.kind = kind;
+        stone.closed_at = Clock::get()?.unix_timestamp;
+        stone.bump = bump;
+    }
+    Ok(())
+}
+
+/// The org admin can do anything; other wallets need an `OrgMember` entry for
+/// this org with at least `min_role`. The entry's seeds are checked by the
+/// accounts struct, so here it only has to exist and rank high enough.
+fn check_org_role(
+    org: &Organization,
+    caller: &Signer,
+    member: Option<&Account<OrgMember>>,
+    min_role: Role,
+) -> Result<()> {
+    if caller.key() == org.admin {
+        return Ok(());
+    }
+    let member = member.ok_or(VoidError::InsufficientRole)?;
+    require!(member.role >= min_role, VoidError::InsufficientRole);
+    Ok(())
+}
+
+/// Reset the org's dead man's switch; called by every instruction the admin signs.
+fn record_admin_activity(org: &mut Organization) -> Result<()> {
+    org.last_admin_activity = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/// Slugs appear in URLs, so only `[a-z0-9-]` is allowed and hyphens can't
+/// start or end one. Keeps "Washington Post" from shadowing "washington-post".
+fn check_slug(slug: &str) -> Result<()> {
+    require!(
+        slug.bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'),
+        VoidError::InvalidSlug
+    );
+    require!(
+        !slug.starts_with('-') && !slug.ends_with('-'),
+        VoidError::InvalidSlug
+    );
+    Ok(())
+}
+
+/// A submission window must open before it closes when both ends are set.
+fn check_submission_window(open_at: i64, close_at: i64) -> Result<()> {
+    require!(
+        open_at == 0 || close_at == 0 || open_at < close_at,
+        VoidError::InvalidSubmissionWindow
+    );
+    Ok(())
+}
+
+/// Validate the optional metadata strings shared by both proof instructions.
+fn check_proof_metadata(metadata_uri: &Option<String>, content_type: &Option<String>) -> Result<()> {
+    require!(
+        metadata_uri.as_ref().is_none_or(|uri| uri.len() <= MAX_METADATA_URI_LEN),
+        VoidError::MetadataUriTooLong
+    );
+    require!(
+        content_type.as_ref().is_none_or(|ct| ct.len() <= MAX_CONTENT_TYPE_LEN),
+        VoidError::ContentTypeTooLong
+    );
+    Ok(())
+}
+
+/// Resolve the optional signer attestation for a proof. Signature and key must be
+/// passed together, along with the instructions sysvar.
+fn check_attestation(
+    instructions: Option<&UncheckedAccount>,
+    digest: &[u8],
+    signature: Option<[u8; 64]>,
+    signer_pubkey: Option<[u8; 32]>,
+) -> Result<Option<Pubkey>> {
+    match (signature, signer_pubkey) {
+        (None, None) => Ok(None),
+        (Some(signature), Some(signer_pubkey)) => {
+            let instructions = instructions.ok_or(VoidError::InvalidSignature)?;
+            verify_ed25519_ix(instructions, &signer_pubkey, &signature, digest)?;
+            Ok(Some(Pubkey::new_from_array(signer_pubkey)))
+        }
+        _ => err!(VoidError::InvalidSignature),
+    }
+}
+
+/// Take the next proof ID from a wallet's registry.
+fn next_proof_id(registry: &mut OwnerRegistry, bump: u8) -> Result<u64> {
+    let id = registry.proof_count;
+    registry.proof_count = registry
+        .proof_count
+        .checked_add(1)
+        .ok_or(VoidError::CounterOverflow)?;
+    registry.bump = bump;
+    Ok(id)
+}
+
+/// Check that the instruction just before the current one is an ed25519 program
+/// call verifying `signature` by `pubkey` over exactly `message`. All offsets must
+/// point into that instruction's own data.
+fn verify_ed25519_ix(
+    instructions: &AccountInfo,
+    pubkey: &[u8; 32],
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, VoidError::InvalidSignature);
+    let ix = load_instruction_at_checked(usize::from(current - 1), instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, VoidError::InvalidSignature);
+
+    let data = &ix.data;
+    require!(data.len() >= ED25519_HEADER_LEN && data[0] == 1, VoidError::InvalidSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let sig_offset = usize::from(read_u16(2));
+    let pubkey_offset = usize::from(read_u16(6));
+    let msg_offset = usize::from(read_u16(10));
+    let msg_len = usize::from(read_u16(12));
+    require!(
+        read_u16(4) == ED25519_SELF_IX
+            && read_u16(8) == ED25519_SELF_IX
+            && read_u16(14) == ED25519_SELF_IX,
+        VoidError::InvalidSignature
+    );
+
+    let slice = |at: usize, len: usize| data.get(at..at + len);
+    require!(
+        slice(pubkey_offset, 32) == Some(&pubkey[..])
+            && slice(sig_offset, 64) == Some(&signature[..])
+            && msg_len == message.len()
+            && slice(msg_offset, msg_len) == Some(message),
+        VoidError::InvalidSignature
+    );
+    Ok(())
+}
+
+// ─── ERRORS ─────────────────────────────────────────────────────
+
+#[error_code]
+pub enum VoidError {
+    #[msg("Organization slug too long (max 32 chars)")]
+    SlugTooLong,
+    #[msg("Organization name too long (max 64 chars)")]
+    NameTooLong,
+    #[msg("Organization description too long (max 256 chars)")]
+    DescriptionTooLong,
+    #[msg("Slug cannot be empty")]
+    SlugEmpty,
+    #[msg("Storage locator too long for its storage kind (Arweave 64, IPFS 80, Filecoin 96)")]
+    LocatorTooLong,
+    #[msg("Organization is inactive")]
+    OrgInactive,
+    #[msg("Message has already been burned")]
+    AlreadyBurned,
+    #[msg("This wallet does not allow followers")]
+    FollowersNotAllowed,
+    #[msg("Batch must contain at least one leaf")]
+    EmptyBatch,
+    #[msg("Leaf index is outside the batch")]
+    LeafIndexOutOfRange,
+    #[msg("Merkle proof too long (max 32 levels)")]
+    MerkleProofTooLong,
+    #[msg("Merkle proof does not match the stored root")]
+    InvalidMerkleProof,
+    #[msg("Metadata URI too long (max 128 chars)")]
+    MetadataUriTooLong,
+    #[msg("Content type too long (max 32 chars)")]
+    ContentTypeTooLong,
+    #[msg("Counter overflow")]
+    CounterOverflow,
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("No fees available to withdraw")]
+    NothingToWithdraw,
+    #[msg("Fee mint does not match the organization's fee mint")]
+    WrongFeeMint,
+    #[msg("Token fee accounts are required for this organization")]
+    MissingFeeAccounts,
+    #[msg("Submitter must wait for the org's cooldown before submitting again")]
+    SubmissionTooSoon,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Recipient has blocked this sender")]
+    SenderBlocked,
+    #[msg("Message has not expired yet")]
+    NotYetExpired,
+    #[msg("Message has no expiry set")]
+    NoExpiry,
+    #[msg("Message must be burned before it can be closed")]
+    MessageNotBurned,
+    #[msg("Inbox is inactive")]
+    InboxInactive,
+    #[msg("Inbox must be deactivated before it can be closed")]
+    InboxStillActive,
+    #[msg("Parent message does not exist in this inbox")]
+    InvalidParent,
+    #[msg("Message has already been read")]
+    AlreadyRead,
+    #[msg("Signature does not match a preceding ed25519 instruction over the digest")]
+    InvalidSignature,
+    #[msg("Digest too long (max 64 bytes)")]
+    DigestTooLong,
+    #[msg("Digest length does not match the hash algorithm")]
+    InvalidDigestLength,
+    #[msg("Submitter is not on this organization's allowlist")]
+    NotAllowlisted,
+    #[msg("Organization has reached its submission cap")]
+    SubmissionCapReached,
+    #[msg("Organization is outside its submission window")]
+    SubmissionWindowClosed,
+    #[msg("Submission window must open before it closes")]
+    InvalidSubmissionWindow,
+    #[msg("Sender cannot cover the inbox's minimum stake")]
+    InsufficientStake,
+    #[msg("Too many recipients for one broadcast")]
+    TooManyRecipients,
+    #[msg("Broadcast recipient accounts are missing or out of order")]
+    InvalidBroadcastAccounts,
+    #[msg("Message label too long")]
+    LabelTooLong,
+    #[msg("Slug may only contain a-z, 0-9 and inner hyphens")]
+    InvalidSlug,
+    #[msg("Locator capacity exceeds the maximum")]
+    LocatorCapacityTooLarge,
+    #[msg("Locator capacity is smaller than the stored locator")]
+    LocatorCapacityTooSmall,
+    #[msg("Storage locator too long for a zero-copy submission (max 64 chars)")]
+    ZcLocatorTooLong,
+    #[msg("Bounty vault cannot cover this payout")]
+    InsufficientBounty,
+    #[msg("Cannot send a message to your own inbox")]
+    CannotMessageSelf,
+    #[msg("Previous proof is missing, mismatched or owned by someone else")]
+    InvalidPrevProof,
+    #[msg("Caller's org role is too low for this action")]
+    InsufficientRole,
+    #[msg("Too many attachments")]
+    TooManyAttachments,
+    #[msg("Submission has not expired yet")]
+    SubmissionNotExpired,
+    #[msg("Too many messages for one batch")]
+    TooManyMessages,
+    #[msg("Message is addressed to a different recipient")]
+    NotMessageRecipient,
+    #[msg("Organization does not accept this tip category")]
+    CategoryNotAccepted,
+    #[msg("Accounts do not match the requested message range")]
+    InvalidMessageWindow,
+    #[msg("Source signature does not match a preceding ed25519 instruction")]
+    InvalidDelegateSignature,
+    #[msg("Organization must be deactivated first")]
+    OrgStillActive,
+    #[msg("Organization still has open submissions")]
+    SubmissionsRemaining,
+    #[msg("Too many submissions for one purge")]
+    TooManySubmissions,
+    #[msg("Submission belongs to a different organization")]
+    SubmissionNotInOrg,
+    #[msg("Organization description too short (min 8 chars)")]
+    DescriptionTooShort,
+    #[msg("Organization slug too short (min 3 chars)")]
+    SlugTooShort,
+    #[msg("Challengeable proofs need a non-zero stake")]
+    StakeRequired,
+    #[msg("Challenge period must be positive")]
+    InvalidChallengePeriod,
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Proof has already been challenged")]
+    AlreadyChallenged,
+    #[msg("Proof is under challenge and must be resolved")]
+    ProofUnderChallenge,
+    #[msg("Proof has not been challenged")]
+    NotChallenged,
+    #[msg("Encryption key does not match its declared algorithm")]
+    InvalidEncryptionKey,
+    #[msg("Only verified organizations can sponsor tips")]
+    OrgNotVerified,
+    #[msg("Organization has reached its sponsorship cap")]
+    SponsorCapReached,
+    #[msg("Sponsorship vault cannot cover this tip")]
+    InsufficientSponsorFunds,
+    #[msg("Account is not a submission")]
+    NotASubmission,
+    #[msg("Organization admin is still within the liveness timeout")]
+    AdminStillActive,
+    #[msg("Sender did not pay the inbox's message fee")]
+    InsufficientFee,
+    #[msg("Proof title too long (max 48 chars)")]
+    TitleTooLong,
+    #[msg("Account size matches no known layout of its type")]
+    UnknownLayout,
+    #[msg("Account is not a proof")]
+    NotAProof,
+    #[msg("Organization has hit its tip rate limit for this window")]
+    RateLimitExceeded,
+    #[msg("A rate limit needs a non-zero window")]
+    InvalidRateLimit,
+    #[msg("Message is encrypted to an outdated inbox key")]
+    StaleKeyVersion,
+    #[msg("Tombstone flag and tombstone account must be passed together")]
+    TombstoneMismatch,
+    #[msg("An organization needs between 1 and 3 encryption keys")]
+    InvalidKeyCount,
+    #[msg("The organization has already acted on this submission")]
+    SubmissionAlreadyProcessed,
+    #[msg("Organization guidelines too long (max 200 chars)")]
+    GuidelinesTooLong,
+    #[msg("Parent submission is not an earlier submission of yours in this organization")]
+    InvalidParentSubmission,
+    #[msg("Claim secret does not match the proof's commitment")]
+    InvalidClaimSecret,
+    #[msg("Proof is not claimable or was already claimed")]
+    NotClaimable,
+    #[msg("Relayed tips to this organization need an IP hash and its rate state")]
+    IpHashRequired,
+    #[msg("Too many tips from this source IP; try again later")]
+    IpRateLimitExceeded,
+    #[msg("Sender is not on this inbox's allowlist")]
+    SenderNotAllowlisted,
+    #[msg("Account is not an inbox")]
+    NotAnInbox,
+    #[msg("Submission is frozen under a legal hold")]
+    SubmissionFrozen,
+    #[msg("Handle must be 1-32 characters")]
+    InvalidHandleLength,
+    #[msg("Too many proofs in one call (max 6)")]
+    TooManyProofs,
+    #[msg("Proof accounts must match the PDAs derived from the hashes, in order")]
+    ProofAccountMismatch,
+    #[msg("Rent must be refunded to the account's stored rent recipient")]
+    WrongRentRecipient,
+    #[msg("Account is not a direct message")]
+    NotAMessage,
+    #[msg("Proof has already been superseded")]
+    AlreadySuperseded,
+    #[msg("This organization requires each tip to reference a proof")]
+    ProofRequired,
+    #[msg("This organization's validator program must be passed to approve the tip")]
+    ValidatorRequired,
+    #[msg("Protocol fee cannot exceed 10% (1000 bps)")]
+    FeeTooHigh,
+    #[msg("Protocol fee must be paid to the config's treasury")]
+    WrongProtocolTreasury,
+    #[msg("Recipient already has an inbox; send a direct message instead")]
+    InboxAlreadyActive,
+    #[msg("Only the wallet a pending message is addressed to can claim it")]
+    NotPendingRecipient,
+    #[msg("This organization has already received a tip with this storage locator")]
+    DuplicateSubmission,
+    #[msg("This organization rejects duplicate tips; pass the submission_dedup account")]
+    DedupRecordRequired,
+    #[msg("Storage locator is longer than this organization accepts")]
+    LocatorExceedsOrgLimit,
+    #[msg("Account is not an organization")]
+    NotAnOrganization,
+    #[msg("Account size matches no known layout of its type")]
+    UnknownLayout,
+}
+
+// ─── EVENTS ─────────────────────────────────────────────────────
+
+/// The program pause switch was flipped.
+#[event]
+pub struct PausedChanged {
+    pub authority: Pubkey,
+    pub paused: bool,
+}
+
+/// The protocol relayer wallet was changed.
+#[event]
+pub struct RelayerChanged {
+    pub authority: Pubkey,
+    pub relayer: Pubkey,
+}
+
+/// The protocol's cut of tip fees, or the wallet receiving it, was changed.
+#[event]
+pub struct ProtocolFeeChanged {
+    pub authority: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub treasury: Pubkey,
+}
+
+/// A new proof of existence was stamped.
+#[event]
+pub struct ProofCreated {
+    pub proof: Pubkey,
+    pub algo: HashAlgo,
+    pub digest: Vec<u8>,
+    pub owner: Pubkey,
+    pub title: Option<String>,
+    pub timestamp: i64,
+}
+
+/// An organization stamped a proof for something it published.
+#[event]
+pub struct OrgProofCreated {
+    pub proof: Pubkey,
+    pub organization: Pubkey,
+    pub algo: HashAlgo,
+    pub digest: Vec<u8>,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A proof was appended to its owner's chain via `create_linked_proof`.
+#[event]
+pub struct ProofLinked {
+    pub proof: Pubkey,
+    pub prev: Option<Pubkey>,
+    pub chain_hash: [u8; 32],
+}
+
+/// A challengeable proof's stake was escrowed until `finalized_at`.
+#[event]
+pub struct ProofEscrowed {
+    pub proof: Pubkey,
+    pub stake: u64,
+    pub finalized_at: i64,
+}
+
+/// Someone matched a proof's stake to dispute its timestamp.
+#[event]
+pub struct ProofChallenged {
+    pub proof: Pubkey,
+    pub challenger: Pubkey,
+    pub timestamp: i64,
+}
+
+/// An unchallenged proof's stake was returned to its owner.
+#[event]
+pub struct ProofFinalized {
+    pub proof: Pubkey,
+    pub owner: Pubkey,
+    pub stake: u64,
+}
+
+/// The protocol authority settled a proof challenge.
+#[event]
+pub struct ChallengeResolved {
+    pub proof: Pubkey,
+    /// Wallet that received both stakes
+    pub winner: Pubkey,
+    pub upheld: bool,
+}
+
+/// A proof was closed and its rent refunded.
+#[event]
+pub struct ProofClosed {
+    pub proof: Pubkey,
+    pub algo: HashAlgo,
+    pub digest: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+/// A proof changed custodian.
+#[event]
+pub struct ProofTransferred {
+    pub proof: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A proof was upgraded to a newer account layout.
+#[event]
+pub struct ProofMigrated {
+    pub proof: Pubkey,
+    pub owner: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// A claimable proof was bound to the wallet that revealed its secret.
+#[event]
+pub struct ProofClaimed {
+    pub proof: Pubkey,
+    pub previous_owner: Pubkey,
+    pub claimer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A verifier recorded a read of a proof in its access log.
+#[event]
+pub struct ProofVerifyLogged {
+    pub proof: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A revised document's proof replaced an earlier version.
+#[event]
+pub struct ProofSuperseded {
+    pub old_proof: Pubkey,
+    pub new_proof: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A Merkle batch of proofs was stamped.
+#[event]
+pub struct BatchProofCreated {
+    pub batch: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub leaf_count: u32,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A new organization drop box was created.
+#[event]
+pub struct OrganizationCreated {
+    pub organization: Pubkey,
+    pub index: u64,
+    pub slug: String,
+    pub admin: Pubkey,
+    pub key_fingerprint: [u8; 8],
+    pub timestamp: i64,
+}
+
+/// An organization's display details were edited.
+#[event]
+pub struct OrganizationUpdated {
+    pub organization: Pubkey,
+    pub name: String,
+    pub description: String,
+}
+
+/// An organization rotated its encryption key.
+#[event]
+pub struct OrgKeyRotated {
+    pub organization: Pubkey,
+    pub key_version: u16,
+    pub key_fingerprint: [u8; 8],
+    pub timestamp: i64,
+}
+
+/// An org admin proposed handing the org to a new wallet.
+#[event]
+pub struct AdminTransferProposed {
+    pub organization: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/// The proposed admin accepted control of the org.
+#[event]
+pub struct AdminTransferAccepted {
+    pub organization: Pubkey,
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+/// An org admin withdrew a pending handoff before it was accepted.
+#[event]
+pub struct AdminTransferCancelled {
+    pub organization: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+/// An org was deactivated or reactivated.
+#[event]
+pub struct OrganizationActiveChanged {
+    pub organization: Pubkey,
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+/// An org was deactivated because its admin went silent past the liveness timeout.
+#[event]
+pub struct OrganizationLapsed {
+    pub organization: Pubkey,
+    pub last_admin_activity: i64,
+    pub timestamp: i64,
+}
+
+/// A submission account was resized to a new locator capacity.
+#[event]
+pub struct SubmissionResized {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub locator_capacity: u16,
+}
+
+/// A deactivated org was closed and its rent refunded to the admin.
+#[event]
+pub struct OrganizationPurged {
+    pub organization: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// The protocol authority granted or revoked an org's verification badge.
+#[event]
+pub struct OrganizationVerified {
+    pub organization: Pubkey,
+    pub verified: bool,
+}
+
+/// A wallet reported an org or submission as abusive.
+#[event]
+pub struct ReportFiled {
+    pub report: Pubkey,
+    /// The reported organization or submission
+    pub target: Pubkey,
+    pub reporter: Pubkey,
+    pub reason_code: u8,
+    pub timestamp: i64,
+}
+
+/// A wallet was given a role in an org, or removed (`role` = None).
+#[event]
+pub struct MemberChanged {
+    pub organization: Pubkey,
+    pub member: Pubkey,
+    pub role: Option<Role>,
+}
+
+/// A processed submission was closed by the org admin.
+#[event]
+pub struct SubmissionClosed {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+}
+
+/// A submitter withdrew an untriaged submission and took back its rent.
+#[event]
+pub struct SubmissionCancelled {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+}
+
+/// An expired submission was deleted and its rent returned to the org treasury.
+#[event]
+pub struct SubmissionExpired {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+}
+
+/// An org admin changed a submission's triage status.
+#[event]
+pub struct SubmissionStatusChanged {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub status: SubmissionStatus,
+    pub timestamp: i64,
+    pub read_at: Option<i64>,
+    pub resolved_at: Option<i64>,
+}
+
+/// An org admin exported a submission's full record, typically before closing it.
+#[event]
+pub struct SubmissionExported {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub status_updated_at: i64,
+    pub parent_id: Option<u64>,
+    pub frozen: bool,
+    pub rent_recipient: Option<Pubkey>,
+    pub proof: Option<Pubkey>,
+    pub read_at: Option<i64>,
+    pub replied_at: Option<i64>,
+    pub resolved_at: Option<i64>,
+    pub schema_version: u8,
+    pub exported_at: i64,
+}
+
+/// An org admin exported a zero-copy submission's full record. `storage_kind`
+/// and `status` are the raw discriminants the account stores.
+#[event]
+pub struct SubmissionZcExported {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+    pub storage_kind: u8,
+    pub locator: String,
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub category: u8,
+    pub status: u8,
+    pub exported_at: i64,
+}
+
+/// An org admin froze or thawed a submission for a legal hold.
+#[event]
+pub struct SubmissionFreezeChanged {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub frozen: bool,
+    pub timestamp: i64,
+}
+
+/// An organization was upgraded to a newer account layout.
+#[event]
+pub struct OrganizationMigrated {
+    pub organization: Pubkey,
+    pub index: u64,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// A submission was upgraded to a newer account layout.
+#[event]
+pub struct SubmissionMigrated {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// An org admin replied to a submission.
+#[event]
+pub struct ReplyPosted {
+    pub reply: Pubkey,
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub timestamp: i64,
+    pub read_at: Option<i64>,
+}
+
+/// An org changed its per-tip submission fee.
+#[event]
+pub struct SubmissionFeeUpdated {
+    pub organization: Pubkey,
+    pub submission_fee: u64,
+}
+
+/// An org changed which tip categories it accepts.
+#[event]
+pub struct CategoriesUpdated {
+    pub organization: Pubkey,
+    pub categories: [u8; 8],
+}
+
+/// An org changed its submission guidelines.
+#[event]
+pub struct GuidelinesUpdated {
+    pub organization: Pubkey,
+    pub guidelines: Option<String>,
+}
+
+/// An org changed its display handle; its slug is unchanged.
+#[event]
+pub struct HandleUpdated {
+    pub organization: Pubkey,
+    pub slug: String,
+    pub handle: String,
+}
+
+/// An org rescheduled its submission window.
+#[event]
+pub struct SubmissionWindowUpdated {
+    pub organization: Pubkey,
+    pub submission_open_at: i64,
+    pub submission_close_at: i64,
+}
+
+/// An org changed its submission cap.
+#[event]
+pub struct MaxSubmissionsUpdated {
+    pub organization: Pubkey,
+    pub max_submissions: u64,
+}
+
+/// An org switched allowlist-only submissions on or off.
+#[event]
+pub struct AllowlistModeChanged {
+    pub organization: Pubkey,
+    pub allowlist_only: bool,
+}
+
+/// An org started or stopped requiring a proof reference on every tip.
+#[event]
+pub struct RequireProofChanged {
+    pub organization: Pubkey,
+    pub require_proof: bool,
+}
+
+/// An org set or cleared the program that approves its tips.
+#[event]
+pub struct ValidatorProgramUpdated {
+    pub organization: Pubkey,
+    pub validator_program: Option<Pubkey>,
+}
+
+/// An org started or stopped rejecting duplicate storage locators.
+#[event]
+pub struct DedupModeChanged {
+    pub organization: Pubkey,
+    pub dedup: bool,
+}
+
+/// A wallet was added to or removed from an org's allowlist.
+#[event]
+pub struct AllowlistChanged {
+    pub organization: Pubkey,
+    pub submitter: Pubkey,
+    pub allowed: bool,
+}
+
+/// An org changed its per-wallet submission cooldown.
+#[event]
+pub struct CooldownUpdated {
+    pub organization: Pubkey,
+    pub cooldown_secs: u32,
+}
+
+/// An org changed (or removed) its org-wide tip rate limit.
+#[event]
+pub struct RateLimitUpdated {
+    pub organization: Pubkey,
+    pub window_secs: u32,
+    pub window_limit: u32,
+}
+
+/// An org changed its per-IP limit on relayed tips.
+#[event]
+pub struct IpRateLimitUpdated {
+    pub organization: Pubkey,
+    pub ip_window_secs: u32,
+    pub ip_window_limit: u32,
+}
+
+/// An org changed (or removed) its SPL-token submission fee.
+#[event]
+pub struct TokenFeeUpdated {
+    pub organization: Pubkey,
+    pub fee_mint: Option<Pubkey>,
+    pub fee_amount: u64,
+}
+
+/// An org admin swept collected fees out of the treasury.
+#[event]
+pub struct FeesWithdrawn {
+    pub organization: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+}
+
+/// Lamports were added to an org's bounty pool.
+#[event]
+pub struct BountyFunded {
+    pub organization: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
+/// An org paid a bounty to a submitter.
+#[event]
+pub struct BountyPaid {
+    pub organization: Pubkey,
+    pub submission: Pubkey,
+    pub submitter: Pubkey,
+    pub amount: u64,
+}
+
+/// Lamports were added to an org's sponsorship pool.
+#[event]
+pub struct SponsorshipFunded {
+    pub organization: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
+/// An org admin changed the sponsorship pool's spending cap.
+#[event]
+pub struct SponsorCapUpdated {
+    pub organization: Pubkey,
+    pub cap: u64,
+    pub spent: u64,
+}
+
+/// A sponsored tip's rent was refunded to the relayer from the org's pool.
+#[event]
+pub struct TipSponsored {
+    pub organization: Pubkey,
+    pub submission: Pubkey,
+    pub amount: u64,
+}
+
+/// A tip took the next slot in the protocol-wide order. Indexers can follow
+/// `global_id` to detect missed tips across all orgs.
+#[event]
+pub struct GlobalSubmission {
+    pub global_id: u64,
+    pub organization: Pubkey,
+    pub local_id: u64,
+}
+
+/// A tip was submitted to an organization.
+#[event]
+pub struct TipSubmitted {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+    pub submitter: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A submitter continued a submission thread with a follow-up.
+#[event]
+pub struct FollowUpSubmitted {
+    pub submission: Pubkey,
+    pub organization: Pubkey,
+    pub id: u64,
+    pub parent_id: u64,
+    pub timestamp: i64,
+}
+
+/// A wallet activated its inbox.
+#[event]
+pub struct InboxActivated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// An inbox was upgraded to a newer account layout.
+#[event]
+pub struct InboxMigrated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// An inbox owner rotated their encryption key.
+#[event]
+pub struct InboxKeyRotated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub key_version: u16,
+    pub timestamp: i64,
+}
+
+/// An inbox stopped accepting new messages.
+#[event]
+pub struct InboxDeactivated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// An inbox was closed and its rent refunded.
+#[event]
+pub struct InboxClosed {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// An inbox changed the stake it requires per message.
+#[event]
+pub struct MinStakeUpdated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub min_stake: u64,
+}
+
+/// An inbox owner changed the fee it charges per message.
+#[event]
+pub struct MessageFeeUpdated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub message_fee: u64,
+}
+
+/// An inbox owner turned its burn-after-reading policy on or off.
+#[event]
+pub struct DefaultBurnUpdated {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub default_burn: bool,
+}
+
+/// An inbox owner switched allowlist-only messaging on or off.
+#[event]
+pub struct InboxAllowlistModeChanged {
+    pub inbox: Pubkey,
+    pub owner: Pubkey,
+    pub allowlist_only: bool,
+}
+
+/// A sender was added to or removed from an inbox's allowlist.
+#[event]
+pub struct InboxAllowlistChanged {
+    pub owner: Pubkey,
+    pub sender: Pubkey,
+    pub allowed: bool,
+}
+
+/// An inbox owner blocked or unblocked a sender.
+#[event]
+pub struct BlockListChanged {
+    pub owner: Pubkey,
+    pub sender: Pubkey,
+    pub blocked: bool,
+}
+
+/// A direct message was sent to an inbox.
+#[event]
+pub struct MessageSent {
+    pub message: Pubkey,
+    pub id: u64,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    /// Inbox key version at send time
+    pub key_version: u16,
+    pub burn_after_reading: bool,
+    pub timestamp: i64,
+}
+
+/// A message was left for a wallet without an inbox.
+#[event]
+pub struct PendingMessageSent {
+    pub pending_message: Pubkey,
+    pub id: u64,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A recipient moved a pending message into their inbox.
+#[event]
+pub struct PendingMessageClaimed {
+    pub pending_message: Pubkey,
+    pub message: Pubkey,
+    /// ID of the message in the inbox
+    pub id: u64,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+}
+
+/// A direct message was burned by its recipient.
+#[event]
+pub struct MessageBurned {
+    pub message: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A recipient marked a direct message as read.
+#[event]
+pub struct MessageRead {
+    pub message: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A sender recalled an unread direct message.
+#[event]
+pub struct MessageRecalled {
+    pub message: Pubkey,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A burned direct message was closed and its rent refunded.
+#[event]
+pub struct MessageClosed {
+    pub message: Pubkey,
+    pub id: u64,
+    pub recipient: Pubkey,
+}
+
+/// A direct message was upgraded to a newer account layout.
+#[event]
+pub struct MessageMigrated {
+    pub message: Pubkey,
+    pub recipient: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+// ─── PROTOCOL CONFIG ACCOUNTS ───────────────────────────────────
+
+/// Singleton program settings: the incident-response kill switch, the tip relayer
+/// and the protocol's cut of tip fees.
+/// Size: 8 + 32 + 1 + 32 + 2 + 32 + 1 = 108 bytes
+#[account]
+pub struct Config {
+    /// Wallet allowed to pause and unpause the program
+    pub authority: Pubkey,
+    /// When true, new proofs, tips and messages are rejected
+    pub paused: bool,
+    /// Backend wallet that submits anonymous tips (default = none)
+    pub relayer: Pubkey,
+    /// Share of each tip's lamport fee paid to `treasury`, in basis points (0-1000)
+    pub protocol_fee_bps: u16,
+    /// Wallet that receives the protocol fee
+    pub treasury: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Singleton counter over every tip to every org, giving indexers a gap-free global
+/// order. Created by the first tip after deployment.
+/// Size: 8 + 8 + 1 = 17 bytes
+#[account]
+pub struct GlobalIndex {
+    /// Tips admitted so far across all orgs; only ever increases
+    pub total_submissions: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl GlobalIndex {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 8 + 1;
+}
+
+/// Singleton counter over every organization, giving indexers a gap-free creation
+/// order. Created by the first org after deployment.
+/// Size: 8 + 8 + 1 = 17 bytes
+#[account]
+pub struct OrgRegistry {
+    /// Orgs created so far; only ever increases, even when orgs are purged
+    pub org_count: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl OrgRegistry {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 8 + 1;
+}
+
+/// What kind of account a tombstone stands in for. Serialized as 1 byte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TombstoneKind {
+    Message,
+    Submission,
+}
+
+/// Opt-in record that a closed message or submission once existed. Seeded like the
+/// original account behind a `b"tombstone"` prefix, so auditors can derive it from
+/// the same inputs. Holds no content.
+/// Size: 8 + 8 + 1 + 8 + 1 = 26 bytes
+#[account]
+pub struct Tombstone {
+    /// ID of the closed message or submission
+    pub id: u64,
+    /// Which kind of account was closed
+    pub kind: TombstoneKind,
+    /// When the original account was closed
+    pub closed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 1 + 32 + 2 + 32 + 1,
+        seeds = [seeds::CONFIG],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [seeds::CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelayer<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [seeds::CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [seeds::CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+// ─── STORAGE POINTERS ───────────────────────────────────────────
+
+/// Which off-chain network holds an encrypted payload.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    Arweave,
+    Ipfs,
+    Filecoin,
+}
+
+impl StorageKind {
+    /// Longest locator this network uses (Arweave ids are 43 chars, CIDv1s 59+)
+    pub fn max_locator_len(self) -> usize {
+        match self {
+            StorageKind::Arweave => MAX_ARWEAVE_LOCATOR_LEN,
+            StorageKind::Ipfs => MAX_IPFS_LOCATOR_LEN,
+            StorageKind::Filecoin => MAX_FILECOIN_LOCATOR_LEN,
+        }
+    }
+}
+
+/// Pointer to an encrypted payload stored off-chain.
+/// Serialized size: 1 (kind) + (4+128) (locator) = 133 bytes at most
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StoragePointer {
+    /// Network the payload lives on
+    pub kind: StorageKind,
+    /// Arweave tx id or IPFS/Filecoin CID
+    pub locator: String,
+}
+
+// ─── VOID STAMP ACCOUNTS ────────────────────────────────────────
+
+/// Hash function that produced a proof's digest. Serialized as 1 byte, which is
+/// also the algorithm seed in the proof PDA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Output size in bytes
+    pub fn digest_len(self) -> usize {
+        match self {
+            HashAlgo::Sha256 | HashAlgo::Blake3 => 32,
+            HashAlgo::Sha512 => 64,
+        }
+    }
+}
+
+/// Account that stores a single proof of existence.
+/// Size: 8 (discriminator) + 8 (id) + 1 (algo) + (4+64) (digest) + 32 (owner pubkey)
+///     + 8 (timestamp) + 8 (transferred_at) + (1+4+128) (metadata_uri)
+///     + (1+4+32) (content_type) + (1+32) (signer_pubkey) + (1+32) (namespace)
+///     + (1+32) (organization) + (1+32) (prev) + 32 (chain_hash) + 1 (schema_version)
+///     + (1+4+48) (title) + (1+32) (claim_secret_hash) + (1+32) (supersedes)
+///     + (1+32) (superseded_by) + 1 (bump) = 621 bytes
+///
+/// The digest and optional metadata fields always reserve their max length, so
+/// callers pay the same rent regardless and metadata can be set later without a
+/// realloc. New fields still change the size and need `migrate_proof`.
+///
+/// Migration: proofs stamped before titles used a 469-byte layout without `title`,
+/// proofs stamped before claims a 522-byte layout without `claim_secret_hash`, and
+/// proofs stamped before supersession a 555-byte layout without `supersedes` and
+/// `superseded_by`; `migrate_proof` upgrades all three in place.
+#[account]
+pub struct Proof {
+    /// Index in the stamping wallet's registry (kept across transfers)
+    pub id: u64,
+    /// Hash function the digest was produced with
+    pub algo: HashAlgo,
+    /// File digest (32 or 64 bytes depending on `algo`)
+    pub digest: Vec<u8>,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+    /// When the proof last changed owner (0 if never transferred)
+    pub transferred_at: i64,
+    /// Optional pointer to off-chain metadata about the file
+    pub metadata_uri: Option<String>,
+    /// Optional MIME type of the file (e.g. "application/pdf")
+    pub content_type: Option<String>,
+    /// Key whose ed25519 signature over the digest was verified at stamp time
+    pub signer_pubkey: Option<Pubkey>,
+    /// Stamping wallet for namespaced proofs (None = global)
+    pub namespace: Option<Pubkey>,
+    /// Organization that published this proof via `create_org_proof`
+    pub organization: Option<Pubkey>,
+    /// Previous entry in the owner's proof chain (None = chain root or unlinked)
+    pub prev: Option<Pubkey>,
+    /// sha256(prev.chain_hash || algo || digest), zeroes standing in for a missing prev
+    pub chain_hash: [u8; 32],
+    /// `CURRENT_SCHEMA` when the proof was created
+    pub schema_version: u8,
+    /// Human-readable label for wallet UIs; public, so omit it to keep filenames private
+    pub title: Option<String>,
+    /// sha256 of the secret that lets `claim_proof` take ownership (None = not claimable)
+    pub claim_secret_hash: Option<[u8; 32]>,
+    /// Earlier version of the document this proof replaces (None = original)
+    pub supersedes: Option<Pubkey>,
+    /// Later version that replaced this proof (None = current version)
+    pub superseded_by: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl Proof {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 8 + 1 + (4 + MAX_DIGEST_LEN) + 32 + 8 + 8
+        + (1 + 4 + MAX_METADATA_URI_LEN)
+        + (1 + 4 + MAX_CONTENT_TYPE_LEN)
+        + (1 + 32)
+        + (1 + 32)
+        + (1 + 32)
+        + (1 + 32)
+        + 32
+        + 1
+        + (1 + 4 + MAX_TITLE_LEN)
+        + (1 + 32)
+        + (1 + 32)
+        + (1 + 32)
+        + 1;
+
+    /// Size and first schema of each older layout `migrate_proof` reads
+    pub const LEGACY_LAYOUTS: [(usize, u8); 3] = [(469, 1), (522, 5), (555, 10)];
+
+    /// Fill in a newly created proof of `digest`, stamped now as an unlinked chain
+    /// root at `CURRENT_SCHEMA` with every optional field unset. Each creator sets
+    /// whatever its kind of proof adds on top.
+    fn init(
+        &mut self,
+        id: u64,
+        algo: HashAlgo,
+        digest: Vec<u8>,
+        owner: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        self.id = id;
+        self.algo = algo;
+        self.chain_hash = chain_hash(&[0; 32], algo, &digest);
+        self.digest = digest;
+        self.owner = owner;
+        self.timestamp = Clock::get()?.unix_timestamp;
+        self.transferred_at = 0;
+        self.metadata_uri = None;
+        self.content_type = None;
+        self.signer_pubkey = None;
+        self.namespace = None;
+        self.organization = None;
+        self.prev = None;
+        self.schema_version = CURRENT_SCHEMA;
+        self.title = None;
+        self.claim_secret_hash = None;
+        self.supersedes = None;
+        self.superseded_by = None;
+        self.bump = bump;
+        Ok(())
+    }
+
+    /// Decode a proof written under the older layout first used by schema
+    /// `layout`, leaving the fields it lacks unset.
+    fn read_legacy(buf: &mut &[u8], layout: u8) -> Result<Self> {
+        Ok(Self {
+            id: u64::deserialize(buf)?,
+            algo: HashAlgo::deserialize(buf)?,
+            digest: Vec::deserialize(buf)?,
+            owner: Pubkey::deserialize(buf)?,
+            timestamp: i64::deserialize(buf)?,
+            transferred_at: i64::deserialize(buf)?,
+            metadata_uri: Option::deserialize(buf)?,
+            content_type: Option::deserialize(buf)?,
+            signer_pubkey: Option::deserialize(buf)?,
+            namespace: Option::deserialize(buf)?,
+            organization: Option::deserialize(buf)?,
+            prev: Option::deserialize(buf)?,
+            chain_hash: <[u8; 32]>::deserialize(buf)?,
+            schema_version: u8::deserialize(buf)?,
+            title: if layout >= 5 {
+                Option::deserialize(buf)?
+            } else {
+                None
+            },
+            claim_secret_hash: if layout >= 10 {
+                Option::deserialize(buf)?
+            } else {
+                None
+            },
+            supersedes: if layout >= 17 {
+                Option::deserialize(buf)?
+            } else {
+                None
+            },
+            superseded_by: if layout >= 17 {
+                Option::deserialize(buf)?
+            } else {
+                None
+            },
+            bump: u8::deserialize(buf)?,
+        })
+    }
+}
+
+/// Stake escrowed behind a challengeable proof. The stake (and a challenger's
+/// matching stake) is held as lamports on this PDA on top of its rent.
+/// Size: 8 + 32 + 32 + 8 + 8 + (1+32) + 1 = 122 bytes
+#[account]
+pub struct ProofEscrow {
+    /// The proof this stake backs
+    pub proof: Pubkey,
+    /// Wallet that staked, and gets the stake back if unchallenged
+    pub owner: Pubkey,
+    /// Lamports each side puts up
+    pub stake: u64,
+    /// End of the challenge window
+    pub finalized_at: i64,
+    /// Wallet that matched the stake to dispute the proof
+    pub challenger: Option<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Per-wallet proof counter, so a wallet's proofs can be enumerated by `id`.
+/// The count only ever grows; closing a proof does not free its ID.
+/// Size: 8 + 8 + 1 = 17 bytes
+#[account]
+pub struct OwnerRegistry {
+    /// Number of proofs this wallet has stamped
+    pub proof_count: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct CreateProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Proof::SPACE,
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&digest).0, digest_seeds(&digest).1],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, required only when attesting with a signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProofs<'info> {
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct CreateNamespacedProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Proof::SPACE,
+        seeds = [
+            seeds::PROOF,
+            owner.key().as_ref(),
+            &[algo as u8],
+            digest_seeds(&digest).0,
+            digest_seeds(&digest).1,
+        ],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, required only when attesting with a signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct CreateOrgProof<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Proof::SPACE,
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&digest).0, digest_seeds(&digest).1],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, admin.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct CreateLinkedProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Proof::SPACE,
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&digest).0, digest_seeds(&digest).1],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    /// Required when `prev_proof` is set; must be that proof
+    pub prev: Option<Account<'info, Proof>>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, new_digest: Vec<u8>)]
+pub struct SupersedeProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Proof::SPACE,
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&new_digest).0, digest_seeds(&new_digest).1],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    /// The version being replaced. Global or namespaced, so it relies on
+    /// `has_one = owner` rather than re-deriving the PDA.
+    #[account(mut, has_one = owner)]
+    pub old_proof: Account<'info, Proof>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct CreateChallengeableProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Proof::SPACE,
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&digest).0, digest_seeds(&digest).1],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 8 + (1 + 32) + 1,
+        seeds = [seeds::ESCROW, proof.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, ProofEscrow>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeProof<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::ESCROW, escrow.proof.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, ProofEscrow>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProof<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [seeds::ESCROW, escrow.proof.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, ProofEscrow>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveChallenge<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::ESCROW, escrow.proof.as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.challenger == Some(challenger.key()) @ VoidError::NotChallenged
+    )]
+    pub escrow: Account<'info, ProofEscrow>,
+
+    #[account(
+        has_one = authority,
+        seeds = [seeds::CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+
+    /// Staking wallet, paid if the challenge is rejected
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    /// Challenging wallet, paid if the challenge is upheld
+    #[account(mut)]
+    pub challenger: SystemAccount<'info>,
+}
+
+// Close and transfer accept both global and namespaced proofs, so they rely on
+// `has_one = owner` rather than re-deriving the PDA.
+#[derive(Accounts)]
+pub struct CloseProof<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferProof<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub proof: Account<'info, Proof>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProof<'info> {
+    /// CHECK: May still hold an older `Proof` layout that `Account` cannot load;
+    /// the handler checks the discriminator itself
+    #[account(mut, owner = crate::ID)]
+    pub proof: UncheckedAccount<'info>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct CreateClaimableProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Proof::SPACE,
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&digest).0, digest_seeds(&digest).1],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1,
+        seeds = [seeds::REGISTRY, owner.key().as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, OwnerRegistry>,
+
+    /// Stamping wallet, which owns the proof until it is claimed
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimProof<'info> {
+    #[account(mut)]
+    pub proof: Account<'info, Proof>,
+
+    pub claimer: Signer<'info>,
+}
+
+/// A batch stamp: one Merkle root standing in for many file hashes.
+/// Size: 8 + 32 (merkle_root) + 4 (leaf_count) + 32 (owner) + 8 (timestamp) + 1 (bump) = 85 bytes
+#[account]
+pub struct BatchProof {
+    /// Root of the Merkle tree built over the file hashes
+    pub merkle_root: [u8; 32],
+    /// Number of leaves (files) in the tree
+    pub leaf_count: u32,
+    /// Wallet that stamped the batch
+    pub owner: Pubkey,
+    /// When the batch was stamped
+    pub timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct CreateBatchProof<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 4 + 32 + 8 + 1,
+        seeds = [seeds::BATCH, merkle_root.as_ref()],
+        bump
+    )]
+    pub batch: Account<'info, BatchProof>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Result of `assert_proof`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProofStatus {
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(algo: HashAlgo, digest: Vec<u8>)]
+pub struct AssertProof<'info> {
+    #[account(
+        seeds = [seeds::PROOF, &[algo as u8], digest_seeds(&digest).0, digest_seeds(&digest).1],
+        bump = proof.bump
+    )]
+    pub proof: Account<'info, Proof>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyInclusion<'info> {
+    #[account(
+        seeds = [seeds::BATCH, batch.merkle_root.as_ref()],
+        bump = batch.bump
+    )]
+    pub batch: Account<'info, BatchProof>,
+}
+
+/// One recorded verification of a proof.
+/// Serialized size: 32 + 8 = 40 bytes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct VerifyEntry {
+    /// Wallet that verified the proof
+    pub verifier: Pubkey,
+    /// When it recorded the verification
+    pub timestamp: i64,
+}
+
+/// Opt-in access trail of a proof: a ring of its last `MAX_VERIFY_LOG` verifiers.
+/// Entry `total % MAX_VERIFY_LOG` is the next to be overwritten; while `total` is
+/// below `MAX_VERIFY_LOG` the remaining entries are zeroed.
+/// Size: 8 + 32 + 40 * 16 (entries) + 8 + 1 = 689 bytes
+#[account]
+pub struct VerifyLog {
+    /// The proof being verified
+    pub proof: Pubkey,
+    /// Ring buffer of recorded verifications
+    pub entries: [VerifyEntry; MAX_VERIFY_LOG],
+    /// Verifications recorded over the log's lifetime; only ever increases
+    pub total: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+// Like close and transfer, logging accepts both global and namespaced proofs.
+#[derive(Accounts)]
+pub struct LogVerify<'info> {
+    pub proof: Account<'info, Proof>,
+
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + 32 + 40 * MAX_VERIFY_LOG + 8 + 1,
+        seeds = [seeds::VERIFY_LOG, proof.key().as_ref()],
+        bump
+    )]
+    pub verify_log: Account<'info, VerifyLog>,
+
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ─── VOID DROP ACCOUNTS ─────────────────────────────────────────
+
+/// Key agreement scheme of an org or inbox encryption key. Serialized as 1 byte.
+/// Keys live in a fixed 65-byte field; shorter keys are zero-padded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAlgo {
+    /// ECDH P-256, uncompressed point (0x04 + 32 x + 32 y)
+    P256,
+    /// ECDH secp256k1, uncompressed point (0x04 + 32 x + 32 y)
+    Secp256k1,
+    /// X25519 Montgomery u-coordinate
+    X25519,
+}
+
+impl KeyAlgo {
+    /// Bytes of the 65-byte key field the key occupies
+    pub fn key_len(self) -> usize {
+        match self {
+            KeyAlgo::P256 | KeyAlgo::Secp256k1 => 65,
+            KeyAlgo::X25519 => 32,
+        }
+    }
+}
+
+/// Organization drop box. Stores the org's public encryption keys so anyone
+/// can encrypt messages to them.
+/// Size: 8 + (4+32) + (4+32) + (4+64) + (4+256) + (1+4+200) + 65*3 + 1 + 1 + 8 + 2 + 8 + 32 + (1+32) + 8 + (1+32) + 8
+///     + 4 + 4 + 4 + 8 + 4 + 4 + 4 + 1 + 1 + (1 + 32) + 1 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 1 + 1 + 8 + 1 + 1
+///     = 1100 bytes
+/// Every org reserves room for `MAX_ORG_KEYS` keys, so creating one costs about
+/// 0.0009 SOL more rent than the single-key layout, even with one key.
+///
+/// Migration: from schema 1 on, `schema_version` tells which layout an org was
+/// written with. Orgs written under an older layout (see `LEGACY_LAYOUTS`) don't
+/// deserialize until `migrate_organization` upgrades them in place. Orgs from
+/// before `schema_version` (before key rotation, key algorithms, key
+/// fingerprints, token fees, cooldowns, allowlists, submission caps, submission
+/// windows, open-submission tracking, verification, bounties or categories)
+/// can't be migrated.
+#[account]
+pub struct Organization {
+    /// URL slug (e.g. "washington-post"); the PDA seed, so it never changes
+    pub slug: String,
+    /// Display handle for branding and URLs; starts as the slug, changed by `set_handle`
+    pub handle: String,
+    /// Display name
+    pub name: String,
+    /// What kind of tips they accept
+    pub description: String,
+    /// Longer submission guidelines for the submission form (None = not set)
+    pub guidelines: Option<String>,
+    /// Public keys for ECDH, each laid out as `key_algo` describes; slots past
+    /// `key_count` are zeroed
+    pub encryption_keys: [[u8; 65]; MAX_ORG_KEYS],
+    /// How many of `encryption_keys` are in use (1-3)
+    pub key_count: u8,
+    /// Curve of every key in `encryption_keys`
+    pub key_algo: KeyAlgo,
+    /// First 8 bytes of sha256 over the listed keys, for checking them out-of-band
+    pub key_fingerprint: [u8; 8],
+    /// Bumped on every key rotation (0 = original keys)
+    pub key_version: u16,
+    /// When the key was last rotated (0 if never)
+    pub key_rotated_at: i64,
+    /// Wallet that controls this org
+    pub admin: Pubkey,
+    /// Proposed new admin awaiting acceptance
+    pub pending_admin: Option<Pubkey>,
+    /// Lamports charged per submission (0 = free)
+    pub submission_fee: u64,
+    /// SPL mint that token fees are charged in (None = no token fee)
+    pub fee_mint: Option<Pubkey>,
+    /// Token base units charged per submission when `fee_mint` is set
+    pub fee_amount: u64,
+    /// Seconds a wallet must wait between submissions (0 = no limit)
+    pub cooldown_secs: u32,
+    /// Length of the org-wide rate-limit window in seconds
+    pub window_secs: u32,
+    /// Most tips accepted per window across all submitters (0 = no limit)
+    pub window_limit: u32,
+    /// When the current rate-limit window opened
+    pub window_start: i64,
+    /// Tips accepted in the current window
+    pub window_count: u32,
+    /// Length of the per-IP window for relayed tips in seconds
+    pub ip_window_secs: u32,
+    /// Most relayed tips accepted per IP per window (0 = no limit)
+    pub ip_window_limit: u32,
+    /// Only wallets with an `Allowlisted` entry may submit
+    pub allowlist_only: bool,
+    /// Every tip must reference a `Proof` of its evidence
+    pub require_proof: bool,
+    /// Program `submit_tip` asks to approve each tip (None = no custom validation)
+    pub validator_program: Option<Pubkey>,
+    /// Each storage locator may only be submitted once, so only `submit_tip` is open
+    pub dedup: bool,
+    /// Longest storage locator a tip may carry; new submissions are sized for it
+    pub max_hash_len: u16,
+    /// Most submissions the org will accept (0 = unlimited)
+    pub max_submissions: u64,
+    /// Submissions open at this time (0 = already open)
+    pub submission_open_at: i64,
+    /// Submissions close at this time (0 = never close)
+    pub submission_close_at: i64,
+    /// How many submissions received
+    pub submission_count: u64,
+    /// Submissions not yet closed; must reach 0 before the org can be purged
+    pub open_submissions: u64,
+    /// Lamports paid out of the bounty vault so far
+    pub total_paid: u64,
+    /// Position in creation order across all orgs, from `OrgRegistry.org_count`
+    pub index: u64,
+    /// When the org was created
+    pub created_at: i64,
+    /// Last time the admin signed an org instruction
+    pub last_admin_activity: i64,
+    /// Seconds of admin silence after which anyone may deactivate the org (0 = never)
+    pub liveness_timeout: u32,
+    /// Whether org is accepting submissions
+    pub active: bool,
+    /// Vouched for by the protocol authority
+    pub verified: bool,
+    /// Bitmask of accepted tip categories (bit n = category n; all set by default)
+    pub categories: [u8; 8],
+    /// `CURRENT_SCHEMA` when the account was created
+    pub schema_version: u8,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Organization {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + (4 + MAX_SLUG_LEN) + (4 + MAX_HANDLE_LEN) + (4 + MAX_NAME_LEN)
+        + (4 + MAX_DESC_LEN) + (1 + 4 + MAX_GUIDELINES_LEN) + 65 * MAX_ORG_KEYS + 1 + 1 + 8 + 2
+        + 8 + 32 + (1 + 32) + 8 + (1 + 32) + 8 + 4 + 4 + 4 + 8 + 4 + 4 + 4 + 1 + 1 + (1 + 32) + 1
+        + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 1 + 1 + 8 + 1 + 1;
+
+    /// Size and first schema of each older layout `migrate_organization` reads
+    pub const LEGACY_LAYOUTS: [(usize, u8); 11] = [
+        (643, 1),
+        (655, 3),
+        (675, 6),
+        (806, 7),
+        (1011, 8),
+        (1019, 11),
+        (1027, 13),
+        (1063, 15),
+        (1064, 18),
+        (1097, 19),
+        (1098, 22),
+    ];
+
+    /// Decode an org written under the older layout first used by schema `layout`.
+    /// Layouts only ever inserted fields, so each is read in the current order and
+    /// fields the layout lacks get the defaults `create_organization` gives them.
+    /// `index` and `last_admin_activity` are left for the caller to fill in.
+    fn read_legacy(buf: &mut &[u8], layout: u8) -> Result<Self> {
+        fn since<T: AnchorDeserialize>(buf: &mut &[u8], on: bool, default: T) -> Result<T> {
+            Ok(if on { T::deserialize(buf)? } else { default })
+        }
+        let slug = String::deserialize(buf)?;
+        let handle = since(buf, layout >= 15, slug.clone())?;
+        let name = String::deserialize(buf)?;
+        let description = String::deserialize(buf)?;
+        let guidelines = since(buf, layout >= 8, None)?;
+        let (encryption_keys, key_count) = if layout >= 7 {
+            (
+                <[[u8; 65]; MAX_ORG_KEYS]>::deserialize(buf)?,
+                u8::deserialize(buf)?,
+            )
+        } else {
+            let mut keys = [[0u8; 65]; MAX_ORG_KEYS];
+            keys[0] = <[u8; 65]>::deserialize(buf)?;
+            (keys, 1)
+        };
+        let key_algo = KeyAlgo::deserialize(buf)?;
+        let key_fingerprint = <[u8; 8]>::deserialize(buf)?;
+        let key_version = u16::deserialize(buf)?;
+        let key_rotated_at = i64::deserialize(buf)?;
+        let admin = Pubkey::deserialize(buf)?;
+        let pending_admin = Option::<Pubkey>::deserialize(buf)?;
+        let submission_fee = u64::deserialize(buf)?;
+        let fee_mint = Option::<Pubkey>::deserialize(buf)?;
+        let fee_amount = u64::deserialize(buf)?;
+        let cooldown_secs = u32::deserialize(buf)?;
+        let window_secs = since(buf, layout >= 6, 0)?;
+        let window_limit = since(buf, layout >= 6, 0)?;
+        let window_start = since(buf, layout >= 6, 0)?;
+        let window_count = since(buf, layout >= 6, 0)?;
+        let ip_window_secs = since(buf, layout >= 11, 0)?;
+        let ip_window_limit = since(buf, layout >= 11, 0)?;
+        let allowlist_only = bool::deserialize(buf)?;
+        let require_proof = since(buf, layout >= 18, false)?;
+        let validator_program = since(buf, layout >= 19, None)?;
+        let dedup = since(buf, layout >= 22, false)?;
+        let max_hash_len = since(buf, layout >= 23, MAX_LOCATOR_LEN as u16)?;
+        let max_submissions = u64::deserialize(buf)?;
+        let submission_open_at = i64::deserialize(buf)?;
+        let submission_close_at = i64::deserialize(buf)?;
+        let submission_count = u64::deserialize(buf)?;
+        let open_submissions = u64::deserialize(buf)?;
+        let total_paid = u64::deserialize(buf)?;
+        let index = since(buf, layout >= 13, 0)?;
+        let created_at = i64::deserialize(buf)?;
+        let last_admin_activity = since(buf, layout >= 3, 0)?;
+        let liveness_timeout = since(buf, layout >= 3, 0)?;
+        Ok(Self {
+            slug,
+            handle,
+            name,
+            description,
+            guidelines,
+            encryption_keys,
+            key_count,
+            key_algo,
+            key_fingerprint,
+            key_version,
+            key_rotated_at,
+            admin,
+            pending_admin,
+            submission_fee,
+            fee_mint,
+            fee_amount,
+            cooldown_secs,
+            window_secs,
+            window_limit,
+            window_start,
+            window_count,
+            ip_window_secs,
+            ip_window_limit,
+            allowlist_only,
+            require_proof,
+            validator_program,
+            dedup,
+            max_hash_len,
+            max_submissions,
+            submission_open_at,
+            submission_close_at,
+            submission_count,
+            open_submissions,
+            total_paid,
+            index,
+            created_at,
+            last_admin_activity,
+            liveness_timeout,
+            active: bool::deserialize(buf)?,
+            verified: bool::deserialize(buf)?,
+            categories: <[u8; 8]>::deserialize(buf)?,
+            schema_version: u8::deserialize(buf)?,
+            bump: u8::deserialize(buf)?,
+        })
+    }
+
+    /// Whether category `n` (0-63) is set in the org's `categories` bitmask
+    pub fn accepts_category(&self, category: u8) -> bool {
+        let (byte, bit) = (usize::from(category / 8), category % 8);
+        self.categories
+            .get(byte)
+            .is_some_and(|mask| mask & (1 << bit) != 0)
+    }
+}
+
+/// Aggregate view of an organization returned by `get_org_stats`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OrgStats {
+    pub submission_count: u64,
+    pub created_at: i64,
+    pub active: bool,
+    pub verified: bool,
+}
+
+/// What an org member may do. Ordered so a higher role implies the lower ones:
+/// Viewer is read-only, Editor can reply and edit the profile, Admin can also
+/// deactivate the org and manage members. Serialized as 1 byte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Viewer,
+    Editor,
+    Admin,
+}
+
+/// A wallet's role in an organization, alongside the single `admin`.
+/// Size: 8 + 32 + 32 + 1 + 8 + 1 = 82 bytes
+#[account]
+pub struct OrgMember {
+    /// The organization this role is in
+    pub organization: Pubkey,
+    /// The wallet holding the role
+    pub member: Pubkey,
+    /// What the wallet may do
+    pub role: Role,
+    /// When the role was granted
+    pub added_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// One wallet's abuse report against an org or submission.
+/// Size: 8 + 32 + 32 + 1 + 8 + 1 = 82 bytes
+#[account]
+pub struct Report {
+    /// Wallet that filed the report
+    pub reporter: Pubkey,
+    /// Reported organization or submission
+    pub target: Pubkey,
+    /// Client-defined reason (spam, impersonation, ...)
+    pub reason_code: u8,
+    /// When the report was filed
+    pub timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Where a submission sits in the org's triage workflow. Serialized as 1 byte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    New,
+    UnderReview,
+    Resolved,
+    Spam,
+}
+
+/// A submission reference. The actual encrypted content lives off-chain;
+/// this just records the pointer and metadata on-chain.
+/// Size: 8 + 8 + 32 + (1+4+128) + 32 + 2 + 32 + 1 + 8 + (1+8) + 1 + 1 + 1 + 8 + (1+8) + 1 + (1+32)
+///     + (1+32) + (1+8) + (1+8) + (1+8) + 1 = 380 bytes
+///
+/// Migration: schema 1 submissions (269 bytes at the default locator capacity) lack
+/// `status_updated_at`, `parent_id`, `frozen`, `rent_recipient`, `proof` and the
+/// three lifecycle timestamps, schema 2-8 submissions (277 bytes) lack all but
+/// `status_updated_at`, schema 9-13 submissions (286 bytes) also have `parent_id`,
+/// schema 14-15 submissions (287 bytes) also `frozen`, schema 16-17 submissions
+/// (320 bytes) also `rent_recipient`, and schema 18-19 submissions (353 bytes)
+/// lack only the timestamps; all must go through `migrate_submission` before any
+/// other instruction can load them.
+#[account]
+pub struct Submission {
+    /// Sequential ID within the org
+    pub id: u64,
+    /// The organization this was submitted to
+    pub organization: Pubkey,
+    /// Where the encrypted content is stored
+    pub storage: StoragePointer,
+    /// SHA-256 of the encrypted payload, so a swapped upload is detectable
+    pub content_hash: [u8; 32],
+    /// Org key version the content was encrypted to
+    pub key_version: u16,
+    /// Who submitted (can be throwaway wallet)
+    pub submitter: Pubkey,
+    /// Submitted by the protocol relayer (an anonymous tip) rather than directly
+    pub via_relay: bool,
+    /// When submitted
+    pub timestamp: i64,
+    /// After this time anyone may delete the submission (None = kept until closed)
+    pub expires_at: Option<i64>,
+    /// Submitter-chosen tip category (0-63), checked against the org's mask
+    pub category: u8,
+    /// Triage status set by the org admin
+    pub status: SubmissionStatus,
+    /// Layout version: `CURRENT_SCHEMA` at creation, raised by `migrate_submission`
+    pub schema_version: u8,
+    /// When the triage status last changed (0 = never)
+    pub status_updated_at: i64,
+    /// Earlier submission this one follows up on (None = starts a thread)
+    pub parent_id: Option<u64>,
+    /// Under a legal hold: cannot be closed, cancelled, expired or purged
+    pub frozen: bool,
+    /// Who gets the rent back when the submission is closed: whoever fronted it
+    /// (None = the closing instruction's usual destination)
+    pub rent_recipient: Option<Pubkey>,
+    /// `Proof` of the evidence the submitter attached (None = no proof referenced)
+    pub proof: Option<Pubkey>,
+    /// When the org first triaged or replied to it (None = still unread)
+    pub read_at: Option<i64>,
+    /// When the org replied (None = no reply yet)
+    pub replied_at: Option<i64>,
+    /// When the org last marked it `Resolved` (None = never resolved)
+    pub resolved_at: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Submission {
+    /// Account size for a given locator capacity; see the breakdown above
+    pub fn space(locator_cap: usize) -> usize {
+        8 + 8 + 32 + (1 + 4 + locator_cap) + 32 + 2 + 32 + 1 + 8 + (1 + 8) + 1 + 1 + 1 + 8 + (1 + 8) + 1 + (1 + 32) + (1 + 32) + (1 + 8) + (1 + 8) + (1 + 8) + 1
+    }
+}
+
+/// Schema 1 layout of `Submission`, read by `migrate_submission`.
+#[derive(AnchorDeserialize)]
+pub struct SubmissionV1 {
+    pub id: u64,
+    pub organization: Pubkey,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub schema_version: u8,
+    pub bump: u8,
+}
+
+/// Schema 2-8 layout of `Submission`, read by `migrate_submission`.
+#[derive(AnchorDeserialize)]
+pub struct SubmissionV2 {
+    pub id: u64,
+    pub organization: Pubkey,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub schema_version: u8,
+    pub status_updated_at: i64,
+    pub bump: u8,
+}
+
+impl From<SubmissionV1> for SubmissionV2 {
+    fn from(old: SubmissionV1) -> Self {
+        SubmissionV2 {
+            id: old.id,
+            organization: old.organization,
+            storage: old.storage,
+            content_hash: old.content_hash,
+            key_version: old.key_version,
+            submitter: old.submitter,
+            via_relay: old.via_relay,
+            timestamp: old.timestamp,
+            expires_at: old.expires_at,
+            category: old.category,
+            status: old.status,
+            schema_version: old.schema_version,
+            status_updated_at: 0,
+            bump: old.bump,
+        }
+    }
+}
+
+/// Schema 9-13 layout of `Submission`, read by `migrate_submission`.
+#[derive(AnchorDeserialize)]
+pub struct SubmissionV3 {
+    pub id: u64,
+    pub organization: Pubkey,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub schema_version: u8,
+    pub status_updated_at: i64,
+    pub parent_id: Option<u64>,
+    pub bump: u8,
+}
+
+impl From<SubmissionV2> for SubmissionV3 {
+    fn from(old: SubmissionV2) -> Self {
+        SubmissionV3 {
+            id: old.id,
+            organization: old.organization,
+            storage: old.storage,
+            content_hash: old.content_hash,
+            key_version: old.key_version,
+            submitter: old.submitter,
+            via_relay: old.via_relay,
+            timestamp: old.timestamp,
+            expires_at: old.expires_at,
+            category: old.category,
+            status: old.status,
+            schema_version: old.schema_version,
+            status_updated_at: old.status_updated_at,
+            parent_id: None,
+            bump: old.bump,
+        }
+    }
+}
+
+/// Schema 14-15 layout of `Submission`, read by `migrate_submission`.
+#[derive(AnchorDeserialize)]
+pub struct SubmissionV4 {
+    pub id: u64,
+    pub organization: Pubkey,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub schema_version: u8,
+    pub status_updated_at: i64,
+    pub parent_id: Option<u64>,
+    pub frozen: bool,
+    pub bump: u8,
+}
+
+impl From<SubmissionV3> for SubmissionV4 {
+    fn from(old: SubmissionV3) -> Self {
+        SubmissionV4 {
+            id: old.id,
+            organization: old.organization,
+            storage: old.storage,
+            content_hash: old.content_hash,
+            key_version: old.key_version,
+            submitter: old.submitter,
+            via_relay: old.via_relay,
+            timestamp: old.timestamp,
+            expires_at: old.expires_at,
+            category: old.category,
+            status: old.status,
+            schema_version: old.schema_version,
+            status_updated_at: old.status_updated_at,
+            parent_id: old.parent_id,
+            frozen: false,
+            bump: old.bump,
+        }
+    }
+}
+
+/// Schema 16-17 layout of `Submission`, read by `migrate_submission`.
+#[derive(AnchorDeserialize)]
+pub struct SubmissionV5 {
+    pub id: u64,
+    pub organization: Pubkey,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub schema_version: u8,
+    pub status_updated_at: i64,
+    pub parent_id: Option<u64>,
+    pub frozen: bool,
+    pub rent_recipient: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl From<SubmissionV4> for SubmissionV5 {
+    fn from(old: SubmissionV4) -> Self {
+        SubmissionV5 {
+            id: old.id,
+            organization: old.organization,
+            storage: old.storage,
+            content_hash: old.content_hash,
+            key_version: old.key_version,
+            submitter: old.submitter,
+            via_relay: old.via_relay,
+            timestamp: old.timestamp,
+            expires_at: old.expires_at,
+            category: old.category,
+            status: old.status,
+            schema_version: old.schema_version,
+            status_updated_at: old.status_updated_at,
+            parent_id: old.parent_id,
+            frozen: old.frozen,
+            rent_recipient: None,
+            bump: old.bump,
+        }
+    }
+}
+
+/// Schema 18-19 layout of `Submission`, read by `migrate_submission`.
+#[derive(AnchorDeserialize)]
+pub struct SubmissionV6 {
+    pub id: u64,
+    pub organization: Pubkey,
+    pub storage: StoragePointer,
+    pub content_hash: [u8; 32],
+    pub key_version: u16,
+    pub submitter: Pubkey,
+    pub via_relay: bool,
+    pub timestamp: i64,
+    pub expires_at: Option<i64>,
+    pub category: u8,
+    pub status: SubmissionStatus,
+    pub schema_version: u8,
+    pub status_updated_at: i64,
+    pub parent_id: Option<u64>,
+    pub frozen: bool,
+    pub rent_recipient: Option<Pubkey>,
+    pub proof: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl From<SubmissionV5> for SubmissionV6 {
+    fn from(old: SubmissionV5) -> Self {
+        SubmissionV6 {
+            id: old.id,
+            organization: old.organization,
+            storage: old.storage,
+            content_hash: old.content_hash,
+            key_version: old.key_version,
+            submitter: old.submitter,
+            via_relay: old.via_relay,
+            timestamp: old.timestamp,
+            expires_at: old.expires_at,
+            category: old.category,
+            status: old.status,
+            schema_version: old.schema_version,
+            status_updated_at: old.status_updated_at,
+            parent_id: old.parent_id,
+            frozen: old.frozen,
+            rent_recipient: old.rent_recipient,
+            proof: None,
+            bump: old.bump,
+        }
+    }
+}
+
+/// Zero-copy submission for high-volume orgs, written by `submit_tip_zc`.
+/// Shares the `[b"submission", org, id]` seeds and ID sequence with `Submission`;
+/// the discriminator tells the two apart. Fields are ordered so the `repr(C)`
+/// layout has no implicit padding. The layout is fixed, so unlike `Submission`
+/// it carries no `schema_version`. It also leaves out `content_hash` to stay
+/// small, trading away detection of a swapped upload.
+/// Size: 8 + 8 + 8 + 32 + 32 + 64 + 2 + 1 + 1 + 1 + 1 + 1 + 1 = 160 bytes
+#[account(zero_copy)]
+pub struct SubmissionZC {
+    /// Sequential ID within the org
+    pub id: u64,
+    /// When submitted
+    pub timestamp: i64,
+    /// The organization this was submitted to
+    pub organization: Pubkey,
+    /// Who submitted (can be throwaway wallet)
+    pub submitter: Pubkey,
+    /// Storage locator bytes; only the first `locator_len` are meaningful
+    pub locator: [u8; MAX_ZC_LOCATOR_LEN],
+    /// Org key version the content was encrypted to
+    pub key_version: u16,
+    /// Length of the locator in `locator`
+    pub locator_len: u8,
+    /// `StorageKind` discriminant
+    pub storage_kind: u8,
+    /// `SubmissionStatus` discriminant
+    pub status: u8,
+    /// 1 if submitted by the protocol relayer
+    pub via_relay: u8,
+    /// Submitter-chosen tip category (0-63)
+    pub category: u8,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// An org's encrypted reply to a submission. The content lives off-chain.
+/// Size: 8 + 32 + (1+4+128) + 8 + 1 = 182 bytes
+#[account]
+pub struct SubmissionReply {
+    /// The submission being replied to
+    pub submission: Pubkey,
+    /// Where the encrypted reply is stored
+    pub storage: StoragePointer,
+    /// When the reply was posted
+    pub timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Tracks when a wallet last submitted to an org, for cooldown enforcement.
+/// Size: 8 + 8 + 1 = 17 bytes
+#[account]
+pub struct SubmitterState {
+    /// Timestamp of this wallet's most recent submission (0 if none yet)
+    pub last_submission_ts: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SubmitterState {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 8 + 1;
+}
+
+/// Per-source-IP tip window for relayed tips to one org. Seeded by
+/// `[b"iprate", org, ip_hash]`; the IP itself is never stored.
+/// Size: 8 + 8 + 4 + 1 = 21 bytes
+#[account]
+pub struct IpRateState {
+    /// When the current window opened
+    pub window_start: i64,
+    /// Relayed tips from this IP in the current window
+    pub window_count: u32,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl IpRateState {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 8 + 4 + 1;
+}
+
+/// Records that an org with `dedup` on received a storage locator, so the same
+/// locator cannot be submitted again. Kept after the submission is closed.
+/// Size: 8 + 32 + 8 + 1 = 49 bytes
+#[account]
+pub struct SubmissionDedup {
+    /// The organization that received the locator (default until first use)
+    pub organization: Pubkey,
+    /// ID of the submission that carried it
+    pub submission_id: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SubmissionDedup {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+}
+
+/// Lets one wallet submit to an allowlist-only org. Existence of the PDA is the grant.
+/// Size: 8 + 32 + 32 + 1 = 73 bytes
+#[account]
+pub struct Allowlisted {
+    /// The organization granting access
+    pub organization: Pubkey,
+    /// The wallet allowed to submit
+    pub submitter: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Holds an organization's collected submission fees.
+/// Size: 8 + 32 + 1 = 41 bytes
+#[account]
+pub struct OrgTreasury {
+    /// The organization these fees belong to
+    pub organization: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Holds an organization's bounty pool, paid out to accepted submitters.
+/// Size: 8 + 32 + 1 = 41 bytes
+#[account]
+pub struct BountyVault {
+    /// The organization this pool belongs to
+    pub organization: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Holds an organization's gas-sponsorship pool for `submit_tip_sponsored`.
+/// Size: 8 + 32 + 8 + 8 + 1 = 57 bytes
+#[account]
+pub struct SponsorVault {
+    /// The organization this pool belongs to
+    pub organization: Pubkey,
+    /// Most lamports the pool may pay out in total (0 = no cap)
+    pub cap: u64,
+    /// Lamports paid out so far
+    pub spent: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(slug: String)]
+pub struct CreateOrganization<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Organization::SPACE,
+        seeds = [seeds::ORG, slug.as_bytes()],
+        bump
+    )]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 1,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// Protocol-wide org counter, assigning this org its `index`. Created by the
+    /// first org after deployment.
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = OrgRegistry::SPACE,
+        seeds = [seeds::ORG_REGISTRY],
+        bump
+    )]
+    pub org_registry: Account<'info, OrgRegistry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(storage: StoragePointer, content_hash: [u8; 32], expires_at: Option<i64>, category: u8, ip_hash: Option<[u8; 32]>)]
+pub struct SubmitTip<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = Submission::space(usize::from(organization.max_hash_len)),
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &organization.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// Protocol-wide tip counter, assigning this tip its global ID
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = GlobalIndex::SPACE,
+        seeds = [seeds::INDEX],
+        bump
+    )]
+    pub global_index: Account<'info, GlobalIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SubmitterState::SPACE,
+        seeds = [seeds::SUBMITTER, organization.key().as_ref(), submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_state: Account<'info, SubmitterState>,
+
+    /// Required only when the org is allowlist-only
+    #[account(
+        seeds = [seeds::ALLOW, organization.key().as_ref(), submitter.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, Allowlisted>>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// Proof of the tip's evidence; required only when the org has `require_proof`
+    pub proof: Option<Account<'info, Proof>>,
+
+    /// CHECK: Must be the org's `validator_program`, checked in the handler;
+    /// required only when the org has one
+    pub validator_program: Option<UncheckedAccount<'info>>,
+
+    /// Required only when the org has `dedup` on; already filled in for a duplicate
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SubmissionDedup::SPACE,
+        seeds = [seeds::SUBDEDUP, organization.key().as_ref(), &locator_seed(&storage)],
+        bump
+    )]
+    pub submission_dedup: Option<Account<'info, SubmissionDedup>>,
+
+    /// Required only when the relayer submits to an org with a per-IP limit
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = IpRateState::SPACE,
+        seeds = [seeds::IPRATE, organization.key().as_ref(), &ip_hash.unwrap_or_default()],
+        bump
+    )]
+    pub ip_rate_state: Option<Account<'info, IpRateState>>,
+
+    /// Required only when the org has a `fee_mint`
+    pub fee_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::authority = submitter,
+    )]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = organization.admin,
+    )]
+    pub org_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Must be the config's `treasury`; required only when the config
+    /// takes a protocol fee and the org charges a lamport fee
+    #[account(mut, address = config.treasury @ VoidError::WrongProtocolTreasury)]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(storage: StoragePointer, content_hash: [u8; 32], parent_submission: u64, ip_hash: Option<[u8; 32]>)]
+pub struct FollowUpSubmission<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = Submission::space(usize::from(organization.max_hash_len)),
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &organization.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    /// The submission being followed up on; must be the submitter's own, in this org
+    #[account(
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &parent_submission.to_le_bytes()],
+        bump = parent.bump,
+        constraint = parent.organization == organization.key() @ VoidError::InvalidParentSubmission,
+        constraint = parent.submitter == submitter.key() @ VoidError::InvalidParentSubmission
+    )]
+    pub parent: Account<'info, Submission>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// Protocol-wide tip counter, assigning this follow-up its global ID
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = GlobalIndex::SPACE,
+        seeds = [seeds::INDEX],
+        bump
+    )]
+    pub global_index: Account<'info, GlobalIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SubmitterState::SPACE,
+        seeds = [seeds::SUBMITTER, organization.key().as_ref(), submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_state: Account<'info, SubmitterState>,
+
+    /// Required only when the org is allowlist-only
+    #[account(
+        seeds = [seeds::ALLOW, organization.key().as_ref(), submitter.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, Allowlisted>>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// Proof of the tip's evidence; required only when the org has `require_proof`
+    pub proof: Option<Account<'info, Proof>>,
+
+    /// CHECK: Must be the org's `validator_program`, checked in the handler;
+    /// required only when the org has one
+    pub validator_program: Option<UncheckedAccount<'info>>,
+
+    /// Required only when the org has `dedup` on; already filled in for a duplicate
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SubmissionDedup::SPACE,
+        seeds = [seeds::SUBDEDUP, organization.key().as_ref(), &locator_seed(&storage)],
+        bump
+    )]
+    pub submission_dedup: Option<Account<'info, SubmissionDedup>>,
+
+    /// Required only when the relayer follows up to an org with a per-IP limit
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = IpRateState::SPACE,
+        seeds = [seeds::IPRATE, organization.key().as_ref(), &ip_hash.unwrap_or_default()],
+        bump
+    )]
+    pub ip_rate_state: Option<Account<'info, IpRateState>>,
+
+    /// Required only when the org has a `fee_mint`
+    pub fee_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::authority = submitter,
+    )]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = organization.admin,
+    )]
+    pub org_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Must be the config's `treasury`; required only when the config
+    /// takes a protocol fee and the org charges a lamport fee
+    #[account(mut, address = config.treasury @ VoidError::WrongProtocolTreasury)]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(storage: StoragePointer, content_hash: [u8; 32], category: u8, source_sig: [u8; 64], source_pubkey: [u8; 32], ip_hash: Option<[u8; 32]>)]
+pub struct SubmitTipDelegated<'info> {
+    #[account(
+        init,
+        payer = relayer,
+        space = Submission::space(usize::from(organization.max_hash_len)),
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &organization.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// Protocol-wide tip counter, assigning this tip its global ID
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = GlobalIndex::SPACE,
+        seeds = [seeds::INDEX],
+        bump
+    )]
+    pub global_index: Account<'info, GlobalIndex>,
+
+    /// Cooldown state of the source, not the relayer
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SubmitterState::SPACE,
+        seeds = [seeds::SUBMITTER, organization.key().as_ref(), source_pubkey.as_ref()],
+        bump
+    )]
+    pub submitter_state: Account<'info, SubmitterState>,
+
+    /// Required only when the org is allowlist-only; must allow the source
+    #[account(
+        seeds = [seeds::ALLOW, organization.key().as_ref(), source_pubkey.as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, Allowlisted>>,
+
+    /// Pays rent and fees on the source's behalf
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Proof of the tip's evidence; required only when the org has `require_proof`
+    pub proof: Option<Account<'info, Proof>>,
+
+    /// CHECK: Must be the org's `validator_program`, checked in the handler;
+    /// required only when the org has one
+    pub validator_program: Option<UncheckedAccount<'info>>,
+
+    /// Required only when the org has `dedup` on; already filled in for a duplicate
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SubmissionDedup::SPACE,
+        seeds = [seeds::SUBDEDUP, organization.key().as_ref(), &locator_seed(&storage)],
+        bump
+    )]
+    pub submission_dedup: Option<Account<'info, SubmissionDedup>>,
+
+    /// Required only when the org has a per-IP limit
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = IpRateState::SPACE,
+        seeds = [seeds::IPRATE, organization.key().as_ref(), &ip_hash.unwrap_or_default()],
+        bump
+    )]
+    pub ip_rate_state: Option<Account<'info, IpRateState>>,
+
+    /// Required only when the org has a `fee_mint`
+    pub fee_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::authority = relayer,
+    )]
+    pub relayer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = organization.admin,
+    )]
+    pub org_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Must be the config's `treasury`; required only when the config
+    /// takes a protocol fee and the org charges a lamport fee
+    #[account(mut, address = config.treasury @ VoidError::WrongProtocolTreasury)]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, holding the ed25519 check of the source signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(storage: StoragePointer, content_hash: [u8; 32], category: u8, ip_hash: Option<[u8; 32]>)]
+pub struct SubmitTipSponsored<'info> {
+    #[account(
+        init,
+        payer = relayer,
+        space = Submission::space(usize::from(organization.max_hash_len)),
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &organization.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(has_one = relayer, seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    #[account(
+        mut,
+        seeds = [seeds::SPONSOR, organization.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Account<'info, SponsorVault>,
+
+    /// Protocol-wide tip counter, assigning this tip its global ID
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = GlobalIndex::SPACE,
+        seeds = [seeds::INDEX],
+        bump
+    )]
+    pub global_index: Account<'info, GlobalIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SubmitterState::SPACE,
+        seeds = [seeds::SUBMITTER, organization.key().as_ref(), submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_state: Account<'info, SubmitterState>,
+
+    /// Required only when the org is allowlist-only
+    #[account(
+        seeds = [seeds::ALLOW, organization.key().as_ref(), submitter.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, Allowlisted>>,
+
+    /// Author of the tip; signs but pays nothing
+    pub submitter: Signer<'info>,
+
+    /// Protocol relayer: fee payer, reimbursed for rent from `sponsor`
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Proof of the tip's evidence; required only when the org has `require_proof`
+    pub proof: Option<Account<'info, Proof>>,
+
+    /// CHECK: Must be the org's `validator_program`, checked in the handler;
+    /// required only when the org has one
+    pub validator_program: Option<UncheckedAccount<'info>>,
+
+    /// Required only when the org has `dedup` on; already filled in for a duplicate
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = SubmissionDedup::SPACE,
+        seeds = [seeds::SUBDEDUP, organization.key().as_ref(), &locator_seed(&storage)],
+        bump
+    )]
+    pub submission_dedup: Option<Account<'info, SubmissionDedup>>,
+
+    /// Required only when the org has a per-IP limit; its rent is reimbursed too
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = IpRateState::SPACE,
+        seeds = [seeds::IPRATE, organization.key().as_ref(), &ip_hash.unwrap_or_default()],
+        bump
+    )]
+    pub ip_rate_state: Option<Account<'info, IpRateState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(storage: StoragePointer, category: u8, ip_hash: Option<[u8; 32]>)]
+pub struct SubmitTipZc<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + std::mem::size_of::<SubmissionZC>(),
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &organization.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub submission: AccountLoader<'info, SubmissionZC>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// Protocol-wide tip counter, assigning this tip its global ID
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = GlobalIndex::SPACE,
+        seeds = [seeds::INDEX],
+        bump
+    )]
+    pub global_index: Account<'info, GlobalIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SubmitterState::SPACE,
+        seeds = [seeds::SUBMITTER, organization.key().as_ref(), submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_state: Account<'info, SubmitterState>,
+
+    /// Required only when the org is allowlist-only
+    #[account(
+        seeds = [seeds::ALLOW, organization.key().as_ref(), submitter.key().as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Option<Account<'info, Allowlisted>>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// Proof of the tip's evidence; required only when the org has `require_proof`
+    pub proof: Option<Account<'info, Proof>>,
+
+    /// CHECK: Must be the org's `validator_program`, checked in the handler;
+    /// required only when the org has one
+    pub validator_program: Option<UncheckedAccount<'info>>,
+
+    /// Required only when the org has `dedup` on; already filled in for a duplicate
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SubmissionDedup::SPACE,
+        seeds = [seeds::SUBDEDUP, organization.key().as_ref(), &locator_seed(&storage)],
+        bump
+    )]
+    pub submission_dedup: Option<Account<'info, SubmissionDedup>>,
+
+    /// Required only when the relayer submits to an org with a per-IP limit
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = IpRateState::SPACE,
+        seeds = [seeds::IPRATE, organization.key().as_ref(), &ip_hash.unwrap_or_default()],
+        bump
+    )]
+    pub ip_rate_state: Option<Account<'info, IpRateState>>,
+
+    /// Required only when the org has a `fee_mint`
+    pub fee_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::authority = submitter,
+    )]
+    pub submitter_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::authority = organization.admin,
+    )]
+    pub org_fee_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Must be the config's `treasury`; required only when the config
+    /// takes a protocol fee and the org charges a lamport fee
+    #[account(mut, address = config.treasury @ VoidError::WrongProtocolTreasury)]
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSubmission<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &submission.id.to_le_bytes()],
+        bump = submission.bump,
+        constraint = submission.organization == organization.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    /// CHECK: Checked against the submission's stored `rent_recipient`; only needed
+    /// when that differs from the admin
+    #[account(mut)]
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSubmission<'info> {
+    #[account(
+        mut,
+        has_one = organization,
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &submission.id.to_le_bytes()],
+        bump = submission.bump,
+        constraint = submission.submitter == submitter.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// CHECK: Checked against the submission's stored `rent_recipient`; only needed
+    /// when that differs from the submitter
+    #[account(mut)]
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireSubmission<'info> {
+    #[account(
+        mut,
+        has_one = organization,
+        seeds = [seeds::SUBMISSION, organization.key().as_ref(), &submission.id.to_le_bytes()],
+        bump = submission.bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// Org treasury, which receives the rent of submissions without a `rent_recipient`
+    #[account(
+        mut,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// CHECK: Checked against the submission's stored `rent_recipient`; only needed
+    /// when that differs from the treasury
+    #[account(mut)]
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Optional record that the submission existed, paid for by the caller
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + 8 + 1 + 8 + 1,
+        seeds = [seeds::TOMBSTONE, seeds::SUBMISSION, organization.key().as_ref(), &submission.id.to_le_bytes()],
+        bump
+    )]
+    pub tombstone: Option<Account<'info, Tombstone>>,
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSubmissionStatus<'info> {
+    #[account(
+        mut,
+        constraint = submission.organization == organization.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExportSubmission<'info> {
+    #[account(constraint = submission.organization == organization.key())]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExportSubmissionZc<'info> {
+    pub submission: AccountLoader<'info, SubmissionZC>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSubmissionFrozen<'info> {
+    #[account(
+        mut,
+        constraint = submission.organization == organization.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeSubmissionHash<'info> {
+    #[account(
+        mut,
+        constraint = submission.organization == organization.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    /// Pays the rent when growing
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Checked against the submission's stored `rent_recipient`; only needed
+    /// when shrinking and that differs from the admin
+    #[account(mut)]
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateOrganization<'info> {
+    /// CHECK: May still hold an older `Organization` layout that `Account` cannot load;
+    /// the handler checks the discriminator and admin itself
+    #[account(mut, owner = crate::ID)]
+    pub organization: UncheckedAccount<'info>,
+
+    /// Assigns an `index` to orgs that predate creation indexes
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = OrgRegistry::SPACE,
+        seeds = [seeds::ORG_REGISTRY],
+        bump
+    )]
+    pub org_registry: Account<'info, OrgRegistry>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateSubmission<'info> {
+    /// CHECK: May still hold an older `Submission` layout that `Account` cannot load;
+    /// the handler checks the discriminator and organization itself
+    #[account(mut, owner = crate::ID)]
+    pub submission: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReplyToSubmission<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + (1 + 4 + MAX_LOCATOR_LEN) + 8 + 1,
+        seeds = [seeds::REPLY, submission.key().as_ref()],
+        bump
+    )]
+    pub reply: Account<'info, SubmissionReply>,
+
+    #[account(
+        mut,
+        constraint = submission.organization == organization.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// Caller's role entry; omit when the caller is the org admin
+    #[account(
+        seeds = [seeds::MEMBER, organization.key().as_ref(), authority.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Option<Account<'info, OrgMember>>,
+
+    /// Org admin or a member with at least Editor role
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOrganization<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// Caller's role entry; omit when the caller is the org admin
+    #[account(
+        seeds = [seeds::MEMBER, organization.key().as_ref(), authority.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Option<Account<'info, OrgMember>>,
+
+    /// Org admin or a member with at least Editor role
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSubmissionFee<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCategories<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuidelines<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetHandle<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSubmissionWindow<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMaxSubmissions<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowlistOnly<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequireProof<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetValidatorProgram<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDedup<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(submitter: Pubkey)]
+pub struct AddToAllowlist<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 1,
+        seeds = [seeds::ALLOW, organization.key().as_ref(), submitter.as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, Allowlisted>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(
+        mut,
+        close = admin,
+        has_one = organization,
+        seeds = [seeds::ALLOW, organization.key().as_ref(), allowlist.submitter.as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, Allowlisted>,
+
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCooldown<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRateLimit<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateIpRateLimit<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenFee<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundBounty<'info> {
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + 32 + 1,
+        seeds = [seeds::BOUNTY, organization.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, BountyVault>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayBounty<'info> {
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::BOUNTY, organization.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, BountyVault>,
+
+    #[account(
+        has_one = submitter,
+        constraint = submission.organization == organization.key()
+    )]
+    pub submission: Account<'info, Submission>,
+
+    /// Wallet that sent the submission, who receives the bounty
+    #[account(mut)]
+    pub submitter: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundSponsorship<'info> {
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + 32 + 8 + 8 + 1,
+        seeds = [seeds::SPONSOR, organization.key().as_ref()],
+        bump
+    )]
+    pub sponsor: Account<'info, SponsorVault>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSponsorCap<'info> {
+    #[account(mut, has_one = admin)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        seeds = [seeds::SPONSOR, organization.key().as_ref()],
+        bump = sponsor.bump
+    )]
+    pub sponsor: Account<'info, SponsorVault>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RotateOrgKey<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminTransfer<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminTransfer<'info> {
+    #[account(
+        mut,
+        constraint = organization.pending_admin == Some(new_admin.key()) @ VoidError::NotPendingAdmin
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateOrganization<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// Caller's role entry; omit when the caller is the org admin
+    #[account(
+        seeds = [seeds::MEMBER, organization.key().as_ref(), authority.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Option<Account<'info, OrgMember>>,
+
+    /// Org admin or a member with Admin role
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct AddMember<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 8 + 1,
+        seeds = [seeds::MEMBER, organization.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub member: Account<'info, OrgMember>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// Caller's role entry; omit when the caller is the org admin
+    #[account(
+        seeds = [seeds::MEMBER, organization.key().as_ref(), authority.key().as_ref()],
+        bump = authority_member.bump
+    )]
+    pub authority_member: Option<Account<'info, OrgMember>>,
+
+    /// Org admin or a member with Admin role
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMember<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [seeds::MEMBER, organization.key().as_ref(), member.member.as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, OrgMember>,
+
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    /// Caller's role entry; omit when the caller is the org admin
+    #[account(
+        seeds = [seeds::MEMBER, organization.key().as_ref(), authority.key().as_ref()],
+        bump = authority_member.bump
+    )]
+    pub authority_member: Option<Account<'info, OrgMember>>,
+
+    /// Org admin or a member with Admin role
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Open submissions to close, and the wallets their rent goes to, are passed as
+/// `remaining_accounts`.
+#[derive(Accounts)]
+pub struct PurgeOrganization<'info> {
+    #[account(
+        mut,
+        close = admin,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [seeds::TREASURY, organization.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, OrgTreasury>,
+
+    /// Omit if the org never funded a bounty
+    #[account(
+        mut,
+        close = admin,
+        seeds = [seeds::BOUNTY, organization.key().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Option<Account<'info, BountyVault>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateOrganization<'info> {
+    #[account(
+        mut,
+        has_one = admin,
+    )]
+    pub organization: Account<'info, Organization>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckOrgLiveness<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+}
+
+#[derive(Accounts)]
+pub struct GetOrgStats<'info> {
+    pub organization: Account<'info, Organization>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyOrganization<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+
+    #[account(
+        has_one = authority,
+        seeds = [seeds::CONFIG],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReportOrganization<'info> {
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + 32 + 32 + 1 + 8 + 1,
+        seeds = [seeds::REPORT, organization.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, Report>,
+
+    pub organization: Account<'info, Organization>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportSubmission<'info> {
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + 32 + 32 + 1 + 8 + 1,
+        seeds = [seeds::REPORT, submission.key().as_ref(), reporter.key().as_ref()],
+        bump
+    )]
+    pub report: Account<'info, Report>,
+
+    pub submission: Account<'info, Submission>,
+
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ─── VOID BURN ACCOUNTS ────────────────────────────────────────
+
+/// A user's inbox for receiving encrypted direct messages.
+/// The encryption key is derived client-side from a wallet signature.
+/// Size: 8 + 32 + 65 + 1 + 2 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 1 + 1 = 153 bytes
+///
+/// Migration: inboxes activated before message fees used a 144-byte layout
+/// without `message_fee`, and inboxes activated before allowlists a 152-byte
+/// layout without `allowlist_only`; `migrate_inbox` upgrades both in place.
+/// Inboxes activated before schema versioning (before key rotation,
+/// deactivation support, message staking, burn policies or key algorithms)
+/// can't be migrated.
+#[account]
+pub struct Inbox {
+    /// The wallet that owns this inbox
+    pub owner: Pubkey,
+    /// Public key for ECDH (derived from wallet signature), laid out as `key_algo` describes
+    pub encryption_key: [u8; 65],
+    /// Curve of `encryption_key`
+    pub key_algo: KeyAlgo,
+    /// Bumped on every key rotation (0 = original key)
+    pub key_version: u16,
+    /// When the key was last rotated (0 if never)
+    pub key_rotated_at: i64,
+    /// How many messages received
+    pub message_count: u64,
+    /// Lamports a sender must escrow per message (0 = free)
+    pub min_stake: u64,
+    /// Lamports a sender pays the owner per message, non-refundable (0 = free)
+    pub message_fee: u64,
+    /// Recipient's policy: force `burn_after_reading` on every incoming message
+    pub default_burn: bool,
+    /// Only senders with an `InboxAllow` entry may message this inbox
+    pub allowlist_only: bool,
+    /// When the inbox was activated
+    pub created_at: i64,
+    /// Whether the inbox accepts new messages
+    pub active: bool,
+    /// `CURRENT_SCHEMA` when the account was created
+    pub schema_version: u8,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Inbox {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize = 8 + 32 + 65 + 1 + 2 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 1 + 1;
+
+    /// Size and first schema of each older layout `migrate_inbox` reads
+    pub const LEGACY_LAYOUTS: [(usize, u8); 2] = [(144, 1), (152, 4)];
+
+    /// Decode an inbox written under the older layout first used by schema
+    /// `layout`, giving the fields it lacks the defaults `activate_inbox` gives them.
+    fn read_legacy(buf: &mut &[u8], layout: u8) -> Result<Self> {
+        Ok(Self {
+            owner: Pubkey::deserialize(buf)?,
+            encryption_key: <[u8; 65]>::deserialize(buf)?,
+            key_algo: KeyAlgo::deserialize(buf)?,
+            key_version: u16::deserialize(buf)?,
+            key_rotated_at: i64::deserialize(buf)?,
+            message_count: u64::deserialize(buf)?,
+            min_stake: u64::deserialize(buf)?,
+            message_fee: if layout >= 4 {
+                u64::deserialize(buf)?
+            } else {
+                0
+            },
+            default_burn: bool::deserialize(buf)?,
+            allowlist_only: if layout >= 12 {
+                bool::deserialize(buf)?
+            } else {
+                false
+            },
+            created_at: i64::deserialize(buf)?,
+            active: bool::deserialize(buf)?,
+            schema_version: u8::deserialize(buf)?,
+            bump: u8::deserialize(buf)?,
+        })
+    }
+}
+
+/// A direct message reference. The encrypted content lives off-chain.
+/// Size: 8 + 8 + 32 + 32 + (1+4+128) + 32 + (1+65) + (1+4+32) + (1+8) + (1+2) + 1 + 1
+///     + (1+8) + (1+8) + (1+8) + 8 + (1+32) + 8 + 1 + 1 = 440 bytes
+///
+/// Migration: messages sent before rent refunds used a 341-byte layout without
+/// `rent_recipient`, and messages sent before pending messages a 374-byte layout
+/// without `ephemeral_key`; `migrate_message` upgrades both in place.
+#[account]
+pub struct DirectMessage {
+    /// Sequential ID within the recipient's inbox
+    pub id: u64,
+    /// Who sent the message
+    pub sender: Pubkey,
+    /// Who receives the message
+    pub recipient: Pubkey,
+    /// Where the encrypted content is stored
+    pub storage: StoragePointer,
+    /// SHA-256 of the encrypted payload, so a swapped upload is detectable
+    pub content_hash: [u8; 32],
+    /// Sender's ephemeral ECDH key when the payload is sealed to the recipient's
+    /// wallet key, as for a claimed pending message (None = sealed to the inbox key)
+    pub ephemeral_key: Option<[u8; 65]>,
+    /// Optional plaintext subject shown in the inbox list
+    pub label: Option<String>,
+    /// Advisory size of the encrypted payload in bytes
+    pub content_bytes: Option<u64>,
+    /// Advisory number of attachments in the payload
+    pub attachment_count: Option<u16>,
+    /// If true, `mark_read` burns the message as it records the read
+    pub burn_after_reading: bool,
+    /// Whether the message has been burned
+    pub burned: bool,
+    /// After this time anyone may burn the message (None = never expires)
+    pub expires_at: Option<i64>,
+    /// When the recipient marked it read (advisory; None = unread)
+    pub read_at: Option<i64>,
+    /// ID of the message in the same inbox this replies to (None = new thread)
+    pub parent_id: Option<u64>,
+    /// Lamports escrowed on top of rent until read or burned (0 = none or released)
+    pub stake: u64,
+    /// Who gets the rent back when the message is closed: the sender, who paid it
+    /// (None = the closing instruction's usual destination)
+    pub rent_recipient: Option<Pubkey>,
+    /// When the message was sent
+    pub timestamp: i64,
+    /// `CURRENT_SCHEMA` when the account was created
+    pub schema_version: u8,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl DirectMessage {
+    /// Account size including the discriminator; see the breakdown above
+    pub const SPACE: usize =
+        8 + 8 + 32 + 32 + (1 + 4 + MAX_LOCATOR_LEN) + 32 + (1 + 65) + (1 + 4 + MAX_LABEL_LEN)
+            + (1 + 8) + (1 + 2) + 1 + 1 + (1 + 8) + (1 + 8) + (1 + 8) + 8 + (1 + 32) + 8 + 1 + 1;
+
+
+    /// Size and first schema of each older layout `migrate_message` reads
+    pub const LEGACY_LAYOUTS: [(usize, u8); 2] = [(341, 1), (374, 16)];
+
+    /// Decode a message written under the older layout first used by schema
+    /// `layout`, leaving the fields it lacks unset.
+    fn read_legacy(buf: &mut &[u8], layout: u8) -> Result<Self> {
+        Ok(Self {
+            id: u64::deserialize(buf)?,
+            sender: Pubkey::deserialize(buf)?,
+            recipient: Pubkey::deserialize(buf)?,
+            storage: StoragePointer::deserialize(buf)?,
+            content_hash: <[u8; 32]>::deserialize(buf)?,
+            ephemeral_key: if layout >= 21 {
+                Option::deserialize(buf)?
+            } else {
+                None
+            },
+            label: Option::deserialize(buf)?,
+            content_bytes: Option::deserialize(buf)?,
+            attachment_count: Option::deserialize(buf)?,
+            burn_after_reading: bool::deserialize(buf)?,
+            burned: bool::deserialize(buf)?,
+            expires_at: Option::deserialize(buf)?,
+            read_at: Option::deserialize(buf)?,
+            parent_id: Option::deserialize(buf)?,
+            stake: u64::deserialize(buf)?,
+            rent_recipient: if layout >= 16 {
+                Option::deserialize(buf)?
+            } else {
+                None
+            },
+            timestamp: i64::deserialize(buf)?,
+            schema_version: u8::deserialize(buf)?,
+            bump: u8::deserialize(buf)?,
+        })
+    }
+}
+
+/// One entry returned by `get_message_window`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MessageSummary {
+    pub id: u64,
+    /// None once the message has been closed and its rent reclaimed
+    pub sender: Option<Pubkey>,
+    pub burned: bool,
+}
+
+/// How often a sender has messaged an inbox, for sorting by known senders.
+/// Size: 8 + 8 + 8 + 1 = 25 bytes
+#[account]
+pub struct SenderStats {
+    /// Messages sent to this inbox while passing the stats account
+    pub message_count: u64,
+    /// When the first of those messages was sent
+    pub first_contact_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Lets one wallet message an allowlist-only inbox. Existence of the PDA is the grant.
+/// Size: 8 + 32 + 32 + 1 = 73 bytes
+#[account]
+pub struct InboxAllow {
+    /// The inbox owner granting access
+    pub owner: Pubkey,
+    /// The wallet allowed to send
+    pub sender: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Marks a sender as blocked by an inbox owner. Existence of the PDA is the block.
+/// Size: 8 + 32 + 32 + 8 + 1 = 81 bytes
+#[account]
+pub struct BlockedSender {
+    /// The inbox owner who created the block
+    pub owner: Pubkey,
+    /// The wallet that can no longer message the owner
+    pub blocked: Pubkey,
+    /// When the block was created
+    pub timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Numbers the messages left for a wallet that has no inbox yet.
+/// Size: 8 + 32 + 8 + 1 = 49 bytes
+#[account]
+pub struct PendingInbox {
+    /// The wallet the messages are for
+    pub recipient: Pubkey,
+    /// Pending messages sent so far; only ever increases, even as they are claimed
+    pub message_count: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A message waiting for its recipient to activate an inbox and claim it.
+/// Size: 8 + 8 + 32 + 32 + (1+4+128) + 32 + 65 + 8 + 1 = 319 bytes
+#[account]
+pub struct PendingMessage {
+    /// Sequential ID within the recipient's `PendingInbox`
+    pub id: u64,
+    /// Who sent the message
+    pub sender: Pubkey,
+    /// The wallet it is addressed to, the only one that can claim it
+    pub recipient: Pubkey,
+    /// Where the encrypted content is stored
+    pub storage: StoragePointer,
+    /// SHA-256 of the encrypted payload, so a swapped upload is detectable
+    pub content_hash: [u8; 32],
+    /// Sender's ephemeral ECDH key; with the recipient's wallet key it opens the payload
+    pub ephemeral_key: [u8; 65],
+    /// When the message was sent
+    pub timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct ActivateInbox<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Inbox::SPACE,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateInbox<'info> {
+    /// CHECK: May still hold an older `Inbox` layout that `Account` cannot load;
+    /// the handler checks the discriminator itself
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump
+    )]
+    pub inbox: UncheckedAccount<'info>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RotateInboxKey<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateInbox<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinStake<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMessageFee<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDefaultBurn<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetInboxAllowlistOnly<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(sender: Pubkey)]
+pub struct AddToInboxAllowlist<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 1,
+        seeds = [seeds::INBOX_ALLOW, owner.key().as_ref(), sender.as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, InboxAllow>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromInboxAllowlist<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [seeds::INBOX_ALLOW, owner.key().as_ref(), allowlist.sender.as_ref()],
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, InboxAllow>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
 }
 
-// ─── VOID STAMP ACCOUNTS ────────────────────────────────────────
+#[derive(Accounts)]
+pub struct CloseInbox<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [seeds::INBOX, owner.key().as_ref()],
+        bump = inbox.bump
+    )]
+    pub inbox: Account<'info, Inbox>,
 
-/// Account that stores a single proof of existence.
-/// Size: 8 (discriminator) + 32 (hash) + 32 (owner pubkey) + 8 (timestamp) + 1 (bump) = 81 bytes
-#[account]
-pub struct Proof {
-    pub hash: [u8; 32],
-    pub owner: Pubkey,
-    pub timestamp: i64,
-    pub bump: u8,
+    #[account(mut)]
+    pub owner: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(hash: [u8; 32])]
-pub struct CreateProof<'info> {
+pub struct SendDirectMessage<'info> {
     #[account(
         init,
-        payer = owner,
-        space = 8 + 32 + 32 + 8 + 1,
-        seeds = [b"proof", hash.as_ref()],
+        payer = sender,
+        space = DirectMessage::SPACE,
+        seeds = [seeds::DM, recipient_inbox.owner.as_ref(), &recipient_inbox.message_count.to_le_bytes()],
         bump
     )]
-    pub proof: Account<'info, Proof>,
+    pub message: Account<'info, DirectMessage>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub recipient_inbox: Account<'info, Inbox>,
+
+    /// CHECK: Block record for this sender; must be empty (not created) for the send to pass
+    #[account(
+        seeds = [seeds::BLOCK, recipient_inbox.owner.as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub sender_block: UncheckedAccount<'info>,
+
+    /// Optional; created on the sender's first message to this inbox
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + 8 + 8 + 1,
+        seeds = [seeds::SSTATS, recipient_inbox.owner.as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub sender_stats: Option<Account<'info, SenderStats>>,
+
+    /// Inbox owner; required only when the inbox charges a `message_fee`
+    #[account(mut, address = recipient_inbox.owner)]
+    pub recipient: Option<SystemAccount<'info>>,
+
+    /// Required only when the inbox is allowlist-only
+    #[account(
+        seeds = [seeds::INBOX_ALLOW, recipient_inbox.owner.as_ref(), sender.key().as_ref()],
+        bump = sender_allow.bump
+    )]
+    pub sender_allow: Option<Account<'info, InboxAllow>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-// ─── VOID DROP ACCOUNTS ─────────────────────────────────────────
+/// Recipient inboxes and message PDAs are passed as `remaining_accounts`.
+#[derive(Accounts)]
+pub struct SendBroadcast<'info> {
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
-/// Organization drop box. Stores the org's public encryption key so anyone
-/// can encrypt messages to it.
-/// Size: 8 + (4+32) + (4+64) + (4+256) + 65 + 32 + 8 + 8 + 1 + 1 = 487 bytes
-#[account]
-pub struct Organization {
-    /// URL slug (e.g. "washington-post")
-    pub slug: String,
-    /// Display name
-    pub name: String,
-    /// What kind of tips they accept
-    pub description: String,
-    /// ECDH P-256 uncompressed public key (65 bytes: 0x04 + 32 x + 32 y)
-    pub encryption_key: [u8; 65],
-    /// Wallet that controls this org
-    pub admin: Pubkey,
-    /// How many submissions received
-    pub submission_count: u64,
-    /// When the org was created
-    pub created_at: i64,
-    /// Whether org is accepting submissions
-    pub active: bool,
-    /// PDA bump
-    pub bump: u8,
-}
+    #[account(mut)]
+    pub sender: Signer<'info>,
 
-/// A submission reference. The actual encrypted content lives on Arweave;
-/// this just records the pointer and metadata on-chain.
-/// Size: 8 + 8 + 32 + (4+64) + 32 + 8 + 1 = 157 bytes
-#[account]
-pub struct Submission {
-    /// Sequential ID within the org
-    pub id: u64,
-    /// The organization this was submitted to
-    pub organization: Pubkey,
-    /// Arweave transaction hash where encrypted content is stored
-    pub arweave_hash: String,
-    /// Who submitted (can be throwaway wallet)
-    pub submitter: Pubkey,
-    /// When submitted
-    pub timestamp: i64,
-    /// PDA bump
-    pub bump: u8,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(slug: String)]
-pub struct CreateOrganization<'info> {
+#[instruction(recipient: Pubkey)]
+pub struct SendToUnactivated<'info> {
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + 32 + 8 + 1,
+        seeds = [seeds::PENDING_INBOX, recipient.as_ref()],
+        bump
+    )]
+    pub pending_inbox: Account<'info, PendingInbox>,
+
     #[account(
         init,
-        payer = admin,
-        space = 8 + (4 + MAX_SLUG_LEN) + (4 + MAX_NAME_LEN) + (4 + MAX_DESC_LEN) + 65 + 32 + 8 + 8 + 1 + 1,
-        seeds = [b"org", slug.as_bytes()],
+        payer = sender,
+        space = 8 + 8 + 32 + 32 + (1 + 4 + MAX_LOCATOR_LEN) + 32 + 65 + 8 + 1,
+        seeds = [seeds::PENDING_DM, recipient.as_ref(), &pending_inbox.message_count.to_le_bytes()],
         bump
     )]
-    pub organization: Account<'info, Organization>,
+    pub pending_message: Account<'info, PendingMessage>,
+
+    #[account(seeds = [seeds::CONFIG], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The recipient's inbox; must be empty (not activated) for the send to pass
+    #[account(
+        seeds = [seeds::INBOX, recipient.as_ref()],
+        bump
+    )]
+    pub recipient_inbox: UncheckedAccount<'info>,
+
+    /// CHECK: Block record for this sender; must be empty (not created) for the send to pass
+    #[account(
+        seeds = [seeds::BLOCK, recipient.as_ref(), sender.key().as_ref()],
+        bump
+    )]
+    pub sender_block: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub sender: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SubmitTip<'info> {
+pub struct ClaimPendingMessage<'info> {
+    #[account(
+        mut,
+        has_one = recipient @ VoidError::NotPendingRecipient,
+        has_one = sender,
+        close = sender,
+        seeds = [seeds::PENDING_DM, pending_message.recipient.as_ref(), &pending_message.id.to_le_bytes()],
+        bump = pending_message.bump
+    )]
+    pub pending_message: Account<'info, PendingMessage>,
+
+    #[account(
+        mut,
+        seeds = [seeds::INBOX, recipient.key().as_ref()],
+        bump = recipient_inbox.bump
+    )]
+    pub recipient_inbox: Account<'info, Inbox>,
+
     #[account(
         init,
-        payer = submitter,
-        space = 8 + 8 + 32 + (4 + MAX_ARWEAVE_HASH_LEN) + 32 + 8 + 1,
-        seeds = [b"submission", organization.key().as_ref(), &organization.submission_count.to_le_bytes()],
+        payer = recipient,
+        space = DirectMessage::SPACE,
+        seeds = [seeds::DM, recipient.key().as_ref(), &recipient_inbox.message_count.to_le_bytes()],
         bump
     )]
-    pub submission: Account<'info, Submission>,
+    pub message: Account<'info, DirectMessage>,
 
+    /// CHECK: The pending message's sender, refunded its rent; checked by `has_one`
     #[account(mut)]
-    pub organization: Account<'info, Organization>,
+    pub sender: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub submitter: Signer<'info>,
+    pub recipient: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DeactivateOrganization<'info> {
+#[instruction(blocked: Pubkey)]
+pub struct BlockSender<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [seeds::BLOCK, owner.key().as_ref(), blocked.as_ref()],
+        bump
+    )]
+    pub block: Account<'info, BlockedSender>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnblockSender<'info> {
     #[account(
         mut,
-        has_one = admin,
+        close = owner,
+        has_one = owner,
+        seeds = [seeds::BLOCK, owner.key().as_ref(), block.blocked.as_ref()],
+        bump = block.bump
     )]
-    pub organization: Account<'info, Organization>,
+    pub block: Account<'info, BlockedSender>,
 
-    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
 }
 
-// ─── VOID BURN ACCOUNTS ────────────────────────────────────────
+#[derive(Accounts)]
+pub struct BurnMessage<'info> {
+    #[account(
+        mut,
+        constraint = message.recipient == recipient.key()
+    )]
+    pub message: Account<'info, DirectMessage>,
 
-/// A user's inbox for receiving encrypted direct messages.
-/// The encryption key is derived client-side from a wallet signature.
-/// Size: 8 + 32 + 65 + 8 + 8 + 1 = 122 bytes
-#[account]
-pub struct Inbox {
-    /// The wallet that owns this inbox
-    pub owner: Pubkey,
-    /// ECDH P-256 uncompressed public key (derived from wallet signature)
-    pub encryption_key: [u8; 65],
-    /// How many messages received
-    pub message_count: u64,
-    /// When the inbox was activated
-    pub created_at: i64,
-    /// PDA bump
-    pub bump: u8,
+    /// Receives any escrowed stake
+    #[account(mut)]
+    pub recipient: Signer<'info>,
 }
 
-/// A direct message reference. The encrypted content lives on Arweave.
-/// Size: 8 + 8 + 32 + 32 + (4+64) + 1 + 1 + 8 + 1 = 159 bytes
-#[account]
-pub struct DirectMessage {
-    /// Sequential ID within the recipient's inbox
-    pub id: u64,
-    /// Who sent the message
-    pub sender: Pubkey,
-    /// Who receives the message
-    pub recipient: Pubkey,
-    /// Arweave transaction hash where encrypted content is stored
-    pub arweave_hash: String,
-    /// If true, recipient intends to burn after reading
-    pub burn_after_reading: bool,
-    /// Whether the message has been burned
-    pub burned: bool,
-    /// When the message was sent
-    pub timestamp: i64,
-    /// PDA bump
-    pub bump: u8,
+/// Messages to burn are passed as `remaining_accounts`.
+#[derive(Accounts)]
+pub struct BurnMessages<'info> {
+    /// Receives any escrowed stakes
+    #[account(mut)]
+    pub recipient: Signer<'info>,
 }
 
+/// Messages in the window are passed as `remaining_accounts`.
 #[derive(Accounts)]
-pub struct ActivateInbox<'info> {
+pub struct GetMessageWindow<'info> {
     #[account(
-        init,
-        payer = owner,
-        space = 8 + 32 + 65 + 8 + 8 + 1,
-        seeds = [b"inbox", owner.key().as_ref()],
-        bump
+        seeds = [seeds::INBOX, inbox.owner.as_ref()],
+        bump = inbox.bump
     )]
     pub inbox: Account<'info, Inbox>,
+}
+
+#[derive(Accounts)]
+pub struct MarkRead<'info> {
+    #[account(
+        mut,
+        has_one = sender,
+        constraint = message.recipient == recipient.key()
+    )]
+    pub message: Account<'info, DirectMessage>,
 
+    /// Original sender, who gets any escrowed stake back
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub sender: SystemAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    pub recipient: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SendDirectMessage<'info> {
+pub struct RecallMessage<'info> {
     #[account(
-        init,
-        payer = sender,
-        space = 8 + 8 + 32 + 32 + (4 + MAX_ARWEAVE_HASH_LEN) + 1 + 1 + 8 + 1,
-        seeds = [b"dm", recipient_inbox.owner.as_ref(), &recipient_inbox.message_count.to_le_bytes()],
-        bump
+        mut,
+        constraint = message.sender == sender.key()
     )]
     pub message: Account<'info, DirectMessage>,
 
+    /// CHECK: Checked against the message's stored `rent_recipient`; only needed
+    /// when that differs from the sender
     #[account(mut)]
-    pub recipient_inbox: Account<'info, Inbox>,
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
 
     #[account(mut)]
     pub sender: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct BurnMessage<'info> {
+pub struct CloseMessage<'info> {
     #[account(
         mut,
         constraint = message.recipient == recipient.key()
     )]
     pub message: Account<'info, DirectMessage>,
 
+    /// CHECK: Checked against the message's stored `rent_recipient`; only needed
+    /// when that differs from the recipient
+    #[account(mut)]
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Optional record that the message existed, paid for by the recipient
+    #[account(
+        init,
+        payer = recipient,
+        space = 8 + 8 + 1 + 8 + 1,
+        seeds = [seeds::TOMBSTONE, seeds::DM, message.recipient.as_ref(), &message.id.to_le_bytes()],
+        bump
+    )]
+    pub tombstone: Option<Account<'info, Tombstone>>,
+
+    #[account(mut)]
     pub recipient: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMessage<'info> {
+    /// CHECK: May still hold an older `DirectMessage` layout that `Account` cannot
+    /// load; the handler checks the discriminator itself
+    #[account(mut, owner = crate::ID)]
+    pub message: UncheckedAccount<'info>,
+
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnExpiredMessage<'info> {
+    #[account(
+        mut,
+        has_one = sender,
+        seeds = [seeds::DM, message.recipient.as_ref(), &message.id.to_le_bytes()],
+        bump = message.bump
+    )]
+    pub message: Account<'info, DirectMessage>,
+
+    /// Original sender, who receives any unclaimed stake
+    #[account(mut)]
+    pub sender: SystemAccount<'info>,
+
+    /// CHECK: Checked against the message's stored `rent_recipient`; only needed
+    /// when that differs from the sender
+    #[account(mut)]
+    pub rent_recipient: Option<UncheckedAccount<'info>>,
+
+    pub caller: Signer<'info>,
 }
 
 // ─── VOID FEED (VOUCH) ACCOUNTS ────────────────────────────────
@@ -481,7 +9232,7 @@ pub struct CreateVouch<'info> {
         init,
         payer = voucher,
         space = 8 + 32 + 32 + 8 + 1,
-        seeds = [b"vouch", voucher.key().as_ref(), content_hash.as_ref()],
+        seeds = [seeds::VOUCH, voucher.key().as_ref(), content_hash.as_ref()],
         bump
     )]
     pub vouch: Account<'info, Vouch>,
@@ -549,7 +9300,7 @@ pub struct CreateProfile<'info> {
         init,
         payer = wallet,
         space = 8 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 1,
-        seeds = [b"profile", wallet.key().as_ref()],
+        seeds = [seeds::PROFILE, wallet.key().as_ref()],
         bump
     )]
     pub profile: Account<'info, WalletProfile>,
@@ -564,7 +9315,7 @@ pub struct CreateProfile<'info> {
 pub struct UpdateProfile<'info> {
     #[account(
         mut,
-        seeds = [b"profile", wallet.key().as_ref()],
+        seeds = [seeds::PROFILE, wallet.key().as_ref()],
         bump = profile.bump,
         constraint = profile.wallet == wallet.key()
     )]
@@ -579,14 +9330,14 @@ pub struct CreateFollow<'info> {
         init,
         payer = follower,
         space = 8 + 32 + 32 + 8 + 1,
-        seeds = [b"follow", follower.key().as_ref(), target_profile.wallet.as_ref()],
+        seeds = [seeds::FOLLOW, follower.key().as_ref(), target_profile.wallet.as_ref()],
         bump
     )]
     pub follow: Account<'info, Follow>,
 
     #[account(
         mut,
-        seeds = [b"profile", follower.key().as_ref()],
+        seeds = [seeds::PROFILE, follower.key().as_ref()],
         bump = follower_profile.bump
     )]
     pub follower_profile: Account<'info, WalletProfile>,
@@ -608,7 +9359,7 @@ pub struct RemoveFollow<'info> {
     #[account(
         mut,
         close = follower,
-        seeds = [b"follow", follower.key().as_ref(), target_profile.wallet.as_ref()],
+        seeds = [seeds::FOLLOW, follower.key().as_ref(), target_profile.wallet.as_ref()],
         bump = follow.bump,
         constraint = follow.follower == follower.key()
     )]
@@ -616,7 +9367,7 @@ pub struct RemoveFollow<'info> {
 
     #[account(
         mut,
-        seeds = [b"profile", follower.key().as_ref()],
+        seeds = [seeds::PROFILE, follower.key().as_ref()],
         bump = follower_profile.bump
     )]
     pub follower_profile: Account<'info, WalletProfile>,
@@ -627,3 +9378,82 @@ pub struct RemoveFollow<'info> {
     #[account(mut)]
     pub follower: Signer<'info>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `0x04 || x || y` from big-endian hex coordinates
+    fn point(x: &str, y: &str) -> [u8; 65] {
+        let mut point = [0u8; 65];
+        point[0] = 0x04;
+        for (i, pair) in [x, y].concat().as_bytes().chunks(2).enumerate() {
+            point[1 + i] = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+        }
+        point
+    }
+
+    fn p256_generator() -> [u8; 65] {
+        point(
+            "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+            "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+        )
+    }
+
+    fn secp256k1_generator() -> [u8; 65] {
+        point(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+        )
+    }
+
+    /// Add one to the last byte of y, which leaves the point off the curve
+    fn bump_y(mut point: [u8; 65]) -> [u8; 65] {
+        point[64] = point[64].wrapping_add(1);
+        point
+    }
+
+    #[test]
+    fn generators_are_on_their_curves() {
+        assert!(P256.contains(&p256_generator()));
+        assert!(SECP256K1.contains(&secp256k1_generator()));
+        assert!(!P256.contains(&secp256k1_generator()));
+        assert!(!SECP256K1.contains(&p256_generator()));
+    }
+
+    #[test]
+    fn off_curve_points_are_rejected() {
+        assert!(!P256.contains(&bump_y(p256_generator())));
+        assert!(!SECP256K1.contains(&bump_y(secp256k1_generator())));
+    }
+
+    #[test]
+    fn unreduced_coordinates_are_rejected() {
+        // (5, y) is on P-256 and (1, y) on secp256k1; adding p to x gives the same
+        // point mod p, but the key must carry the reduced coordinate
+        let y = "459243B9AA581806FE913BCE99817ADE11CA503C64D9A3C533415C083248FBCC";
+        let reduced = "0000000000000000000000000000000000000000000000000000000000000005";
+        let unreduced = "FFFFFFFF00000001000000000000000000000001000000000000000000000004";
+        assert!(P256.contains(&point(reduced, y)));
+        assert!(!P256.contains(&point(unreduced, y)));
+
+        let y = "4218F20AE6C646B363DB68605822FB14264CA8D2587FDD6FBC750D587E76A7EE";
+        let reduced = "0000000000000000000000000000000000000000000000000000000000000001";
+        let unreduced = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30";
+        assert!(SECP256K1.contains(&point(reduced, y)));
+        assert!(!SECP256K1.contains(&point(unreduced, y)));
+    }
+
+    #[test]
+    fn keys_need_the_uncompressed_prefix() {
+        assert!(check_encryption_key(KeyAlgo::P256, &p256_generator()).is_ok());
+        assert!(check_encryption_key(KeyAlgo::Secp256k1, &secp256k1_generator()).is_ok());
+
+        let mut compressed = p256_generator();
+        compressed[0] = 0x02;
+        assert!(check_encryption_key(KeyAlgo::P256, &compressed).is_err());
+        let mut compressed = secp256k1_generator();
+        compressed[0] = 0x03;
+        assert!(check_encryption_key(KeyAlgo::Secp256k1, &compressed).is_err());
+    }
+}
@@ -2,13 +2,117 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { VoidProtocol } from "../target/types/void_protocol";
 import { assert } from "chai";
-import { createHash } from "crypto";
+import { createECDH, createHash } from "crypto";
+import { readFileSync } from "fs";
+import {
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
 
 describe("void-protocol", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.voidProtocol as Program<VoidProtocol>;
+  // Mirrors CURRENT_SCHEMA in lib.rs; bump both together
+  const CURRENT_SCHEMA = 23;
+
+  // Events a transaction emitted, decoded from its logs
+  const eventsOf = async (sig: string) => {
+    await provider.connection.confirmTransaction(sig, "confirmed");
+    const tx = await provider.connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    return [...parser.parseLogs(tx.meta.logMessages)];
+  };
+
+  const [configPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  // Proof PDAs are seeded by [b"proof", algo, digest[..32], digest[32..]]
+  const sha256 = { sha256: {} };
+  const proofSeeds = (digest: Buffer, algo = 0) => [
+    Buffer.from([algo]),
+    digest.subarray(0, 32),
+    digest.subarray(32),
+  ];
+  const [globalIndexPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("index")],
+    program.programId
+  );
+  const [registryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("registry"), provider.wallet.publicKey.toBuffer()],
+    program.programId
+  );
+  // Owns the legacy-layout accounts loaded from tests/fixtures
+  const legacyAdmin = anchor.web3.Keypair.fromSecretKey(
+    Uint8Array.from(JSON.parse(readFileSync("tests/fixtures/legacy-admin.json", "utf8")))
+  );
+  const [orgRegistryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("org_registry")],
+    program.programId
+  );
+
+  // ─── PROTOCOL CONFIG TESTS ─────────────────────────────
+
+  it("Initializes the program config", async () => {
+    await program.methods
+      .initConfig()
+      .accounts({
+        config: configPDA,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const config = await program.account.config.fetch(configPDA);
+    assert.equal(config.authority.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.equal(config.paused, false);
+  });
+
+  it("Rejects new proofs while the program is paused", async () => {
+    const pausedHash = createHash("sha256").update("paused document").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(pausedHash)],
+      program.programId
+    );
+
+    await program.methods
+      .setPaused(true)
+      .accounts({ config: configPDA, authority: provider.wallet.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .createProof(sha256, pausedHash, null, null, null, null, null)
+        .accounts({
+          proof: proofPDA,
+          config: configPDA,
+          registry: registryPDA,
+          owner: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a proof while paused");
+    } catch (err) {
+      assert.include(String(err), "ProgramPaused");
+    }
+
+    await program.methods
+      .setPaused(false)
+      .accounts({ config: configPDA, authority: provider.wallet.publicKey })
+      .rpc();
+
+    const config = await program.account.config.fetch(configPDA);
+    assert.equal(config.paused, false);
+  });
 
   // ─── VOID STAMP TESTS ──────────────────────────────────
 
@@ -17,14 +121,16 @@ describe("void-protocol", () => {
 
   it("Creates a proof of existence", async () => {
     const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("proof"), hash],
+      [Buffer.from("proof"), ...proofSeeds(hash)],
       program.programId
     );
 
     const tx = await program.methods
-      .createProof([...hash])
+      .createProof(sha256, hash, null, null, null, null, null)
       .accounts({
         proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
         owner: provider.wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
@@ -32,10 +138,16 @@ describe("void-protocol", () => {
 
     console.log("  Transaction:", tx);
 
+    const created = (await eventsOf(tx)).find((event) => event.name === "proofCreated");
+    assert.ok(created, "Should emit ProofCreated");
+    assert.ok(created.data.proof.equals(proofPDA));
+    assert.ok(created.data.owner.equals(provider.wallet.publicKey));
+
     const proof = await program.account.proof.fetch(proofPDA);
-    assert.deepEqual(Buffer.from(proof.hash), hash, "Hash should match");
+    assert.deepEqual(Buffer.from(proof.digest), hash, "Hash should match");
     assert.equal(proof.owner.toBase58(), provider.wallet.publicKey.toBase58());
     assert.isAbove(proof.timestamp.toNumber(), 0);
+    assert.equal(proof.schemaVersion, CURRENT_SCHEMA);
 
     console.log("  Hash:", hash.toString("hex"));
     console.log("  Owner:", proof.owner.toBase58());
@@ -43,18 +155,18 @@ describe("void-protocol", () => {
 
   it("Can verify a proof exists by looking up the PDA", async () => {
     const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("proof"), hash],
+      [Buffer.from("proof"), ...proofSeeds(hash)],
       program.programId
     );
     const proof = await program.account.proof.fetch(proofPDA);
     assert.ok(proof, "Proof should exist on-chain");
-    assert.deepEqual(Buffer.from(proof.hash), hash);
+    assert.deepEqual(Buffer.from(proof.digest), hash);
   });
 
   it("Returns null for a hash that was never registered", async () => {
     const unknownHash = createHash("sha256").update("unknown file").digest();
     const [unknownPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("proof"), unknownHash],
+      [Buffer.from("proof"), ...proofSeeds(unknownHash)],
       program.programId
     );
     try {
@@ -65,16 +177,95 @@ describe("void-protocol", () => {
     }
   });
 
+  it("Asserts a proof exists and returns its owner and timestamp", async () => {
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(hash)],
+      program.programId
+    );
+    const status = await program.methods
+      .assertProof(sha256, hash)
+      .accounts({ proof: proofPDA })
+      .view();
+    assert.ok(status.owner.equals(provider.wallet.publicKey));
+    assert.isAbove(status.timestamp.toNumber(), 0);
+
+    const unknownHash = createHash("sha256").update("never stamped").digest();
+    const [unknownPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(unknownHash)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .assertProof(sha256, unknownHash)
+        .accounts({ proof: unknownPDA })
+        .view();
+      assert.fail("Should have failed for an unstamped hash");
+    } catch (err) {
+      assert.include(String(err), "AccountNotInitialized");
+    }
+  });
+
+  it("Records verifiers in a proof's access log", async () => {
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(hash)],
+      program.programId
+    );
+    const [verifyLogPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("verify_log"), proofPDA.toBuffer()],
+      program.programId
+    );
+    const auditor = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: auditor.publicKey,
+          lamports: 10_000_000,
+        })
+      )
+    );
+
+    await program.methods
+      .logVerify()
+      .accounts({
+        proof: proofPDA,
+        verifyLog: verifyLogPDA,
+        verifier: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .logVerify()
+      .accounts({
+        proof: proofPDA,
+        verifyLog: verifyLogPDA,
+        verifier: auditor.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([auditor])
+      .rpc();
+
+    const log = await program.account.verifyLog.fetch(verifyLogPDA);
+    assert.ok(log.proof.equals(proofPDA));
+    assert.equal(log.total.toNumber(), 2);
+    assert.ok(log.entries[0].verifier.equals(provider.wallet.publicKey));
+    assert.ok(log.entries[1].verifier.equals(auditor.publicKey));
+    assert.isAbove(log.entries[1].timestamp.toNumber(), 0);
+    assert.equal(log.entries[2].timestamp.toNumber(), 0);
+  });
+
   it("Rejects duplicate proof for the same hash", async () => {
     const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("proof"), hash],
+      [Buffer.from("proof"), ...proofSeeds(hash)],
       program.programId
     );
     try {
       await program.methods
-        .createProof([...hash])
+        .createProof(sha256, hash, null, null, null, null, null)
         .accounts({
           proof: proofPDA,
+          config: configPDA,
+          registry: registryPDA,
           owner: provider.wallet.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
@@ -85,184 +276,4473 @@ describe("void-protocol", () => {
     }
   });
 
-  // ─── VOID DROP TESTS ───────────────────────────────────
+  it("Closes a proof and allows re-stamping the same hash", async () => {
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(hash)],
+      program.programId
+    );
 
-  const orgSlug = "test-org";
-  const orgName = "Test Organization";
-  const orgDescription = "Submit anonymous tips here";
-  // Fake ECDH public key (65 bytes: 0x04 prefix + 32 x + 32 y)
-  const fakeEncryptionKey = new Uint8Array(65);
-  fakeEncryptionKey[0] = 0x04;
-  for (let i = 1; i < 65; i++) fakeEncryptionKey[i] = i;
+    await program.methods
+      .closeProof()
+      .accounts({
+        proof: proofPDA,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
 
-  let orgPDA: anchor.web3.PublicKey;
+    const closed = await provider.connection.getAccountInfo(proofPDA);
+    assert.isNull(closed, "Proof account should be closed");
 
-  it("Creates an organization", async () => {
-    [orgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("org"), Buffer.from(orgSlug)],
+    await program.methods
+      .createProof(sha256, hash, null, null, null, null, null)
+      .accounts({
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proof = await program.account.proof.fetch(proofPDA);
+    assert.deepEqual(Buffer.from(proof.digest), hash);
+  });
+
+  it("Transfers a proof to a new owner", async () => {
+    const custodyHash = createHash("sha256").update("custody document").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(custodyHash)],
       program.programId
     );
 
-    const tx = await program.methods
-      .createOrganization(orgSlug, orgName, orgDescription, [...fakeEncryptionKey])
+    await program.methods
+      .createProof(sha256, custodyHash, null, null, null, null, null)
       .accounts({
-        organization: orgPDA,
-        admin: provider.wallet.publicKey,
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
-    console.log("  Org created:", tx);
+    const before = await program.account.proof.fetch(proofPDA);
+    assert.equal(before.transferredAt.toNumber(), 0);
 
-    const org = await program.account.organization.fetch(orgPDA);
-    assert.equal(org.slug, orgSlug);
-    assert.equal(org.name, orgName);
-    assert.equal(org.description, orgDescription);
-    assert.equal(org.admin.toBase58(), provider.wallet.publicKey.toBase58());
-    assert.equal(org.submissionCount.toNumber(), 0);
-    assert.equal(org.active, true);
-    assert.deepEqual(
-      Buffer.from(org.encryptionKey),
-      Buffer.from(fakeEncryptionKey)
+    const newOwner = anchor.web3.Keypair.generate().publicKey;
+    await program.methods
+      .transferProof(newOwner)
+      .accounts({
+        proof: proofPDA,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const after = await program.account.proof.fetch(proofPDA);
+    assert.equal(after.owner.toBase58(), newOwner.toBase58());
+    assert.equal(after.timestamp.toNumber(), before.timestamp.toNumber());
+    assert.isAbove(after.transferredAt.toNumber(), 0);
+  });
+
+  it("Migrates a schema 1 proof so it can be verified again", async () => {
+    // The fixture in tests/fixtures/proof-v1.json predates titles and claims
+    const legacyDigest = createHash("sha256")
+      .update("Stamped under the schema 1 proof layout")
+      .digest();
+    const [legacyProofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(legacyDigest)],
+      program.programId
     );
+    const migrate = () =>
+      program.methods
+        .migrateProof()
+        .accounts({
+          proof: legacyProofPDA,
+          payer: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
 
-    console.log("  Slug:", org.slug);
-    console.log("  Name:", org.name);
-    console.log("  Admin:", org.admin.toBase58());
+    let info = await provider.connection.getAccountInfo(legacyProofPDA);
+    assert.equal(info.data.length, 469);
+
+    // Anyone may upgrade a proof, not just its owner
+    await migrate();
+    info = await provider.connection.getAccountInfo(legacyProofPDA);
+    assert.equal(info.data.length, 621);
+    const proof = await program.account.proof.fetch(legacyProofPDA);
+    assert.equal(proof.schemaVersion, CURRENT_SCHEMA);
+    assert.deepEqual(Buffer.from(proof.digest), legacyDigest);
+    assert.ok(proof.owner.equals(legacyAdmin.publicKey));
+    assert.isNull(proof.title);
+    assert.isNull(proof.claimSecretHash);
+    assert.isNull(proof.supersedes);
+    assert.isNull(proof.supersededBy);
+
+    // Already current: a second call changes nothing
+    await migrate();
+    assert.equal((await provider.connection.getAccountInfo(legacyProofPDA)).data.length, 621);
+
+    // The upgraded proof loads like any other
+    await program.methods
+      .transferProof(provider.wallet.publicKey)
+      .accounts({ proof: legacyProofPDA, owner: legacyAdmin.publicKey })
+      .signers([legacyAdmin])
+      .rpc();
+    assert.ok((await program.account.proof.fetch(legacyProofPDA)).owner.equals(provider.wallet.publicKey));
   });
 
-  it("Submits a tip to the organization", async () => {
-    const org = await program.account.organization.fetch(orgPDA);
-    const submissionId = org.submissionCount.toNumber();
+  it("Stores optional metadata on a proof", async () => {
+    const docHash = createHash("sha256").update("contract.pdf").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(docHash)],
+      program.programId
+    );
 
-    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("submission"),
-        orgPDA.toBuffer(),
-        new anchor.BN(submissionId).toArrayLike(Buffer, "le", 8),
-      ],
+    await program.methods
+      .createProof(sha256, docHash, "ar://contract-metadata", "application/pdf", "Signed lease", null, null)
+      .accounts({
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proof = await program.account.proof.fetch(proofPDA);
+    assert.equal(proof.metadataUri, "ar://contract-metadata");
+    assert.equal(proof.contentType, "application/pdf");
+    assert.equal(proof.title, "Signed lease");
+  });
+
+  it("Escrows a challengeable proof's stake until the dispute is resolved", async () => {
+    const disputedHash = createHash("sha256").update("disputed affidavit").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(disputedHash)],
       program.programId
     );
+    const [escrowPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), proofPDA.toBuffer()],
+      program.programId
+    );
+    const stake = new anchor.BN(5_000_000);
 
-    const fakeArweaveHash = "aBcDeFgHiJkLmNoPqRsTuVwXyZ0123456789abcdef";
+    const challenger = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: challenger.publicKey,
+          lamports: 10_000_000,
+        })
+      )
+    );
 
-    const tx = await program.methods
-      .submitTip(fakeArweaveHash)
+    await program.methods
+      .createChallengeableProof(sha256, disputedHash, stake, new anchor.BN(3600))
       .accounts({
-        submission: submissionPDA,
-        organization: orgPDA,
-        submitter: provider.wallet.publicKey,
+        proof: proofPDA,
+        escrow: escrowPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
-    console.log("  Submission tx:", tx);
-
-    const sub = await program.account.submission.fetch(submissionPDA);
-    assert.equal(sub.id.toNumber(), 0);
-    assert.equal(sub.organization.toBase58(), orgPDA.toBase58());
-    assert.equal(sub.arweaveHash, fakeArweaveHash);
-    assert.equal(sub.submitter.toBase58(), provider.wallet.publicKey.toBase58());
-    assert.isAbove(sub.timestamp.toNumber(), 0);
+    try {
+      await program.methods
+        .finalizeProof()
+        .accounts({ escrow: escrowPDA, owner: provider.wallet.publicKey })
+        .rpc();
+      assert.fail("Should have rejected finalizing inside the challenge window");
+    } catch (err) {
+      assert.include(String(err), "ChallengeWindowOpen");
+    }
 
-    // Check org submission count incremented
-    const orgAfter = await program.account.organization.fetch(orgPDA);
-    assert.equal(orgAfter.submissionCount.toNumber(), 1);
+    const challenge = () =>
+      program.methods
+        .challengeProof()
+        .accounts({
+          escrow: escrowPDA,
+          challenger: challenger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([challenger])
+        .rpc();
+    await challenge();
+    const escrow = await program.account.proofEscrow.fetch(escrowPDA);
+    assert.ok(escrow.challenger.equals(challenger.publicKey));
+    try {
+      await challenge();
+      assert.fail("Should have rejected a second challenge");
+    } catch (err) {
+      assert.include(String(err), "AlreadyChallenged");
+    }
 
-    console.log("  Submission ID:", sub.id.toNumber());
-    console.log("  Arweave hash:", sub.arweaveHash);
+    // Both stakes (and the rent) go to the owner when the challenge is rejected
+    const escrowLamports = await provider.connection.getBalance(escrowPDA);
+    const ownerBefore = await provider.connection.getBalance(provider.wallet.publicKey);
+    await program.methods
+      .resolveChallenge(false)
+      .accounts({
+        escrow: escrowPDA,
+        config: configPDA,
+        authority: provider.wallet.publicKey,
+        owner: provider.wallet.publicKey,
+        challenger: challenger.publicKey,
+      })
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(escrowPDA));
+    const ownerAfter = await provider.connection.getBalance(provider.wallet.publicKey);
+    assert.isAbove(ownerAfter - ownerBefore, escrowLamports - 10_000);
   });
 
-  it("Submits a second tip (increments count)", async () => {
-    const org = await program.account.organization.fetch(orgPDA);
-    const submissionId = org.submissionCount.toNumber();
-
-    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("submission"),
-        orgPDA.toBuffer(),
-        new anchor.BN(submissionId).toArrayLike(Buffer, "le", 8),
-      ],
+  it("Stamps a proof attested by an ed25519 signature", async () => {
+    const attestedHash = createHash("sha256").update("signed report").digest();
+    const signer = anchor.web3.Keypair.generate();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(attestedHash)],
       program.programId
     );
 
+    const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message: attestedHash,
+    });
+    // Single-signature layout: 16-byte header, 32-byte pubkey, 64-byte signature
+    const signature = ed25519Ix.data.subarray(48, 112);
+
     await program.methods
-      .submitTip("secondSubmissionArweaveHash12345678901234567")
+      .createProof(sha256, attestedHash, null, null, null, [...signature], [...signer.publicKey.toBytes()])
       .accounts({
-        submission: submissionPDA,
-        organization: orgPDA,
-        submitter: provider.wallet.publicKey,
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
+      .preInstructions([ed25519Ix])
       .rpc();
 
-    const sub = await program.account.submission.fetch(submissionPDA);
-    assert.equal(sub.id.toNumber(), 1);
-
-    const orgAfter = await program.account.organization.fetch(orgPDA);
-    assert.equal(orgAfter.submissionCount.toNumber(), 2);
+    const proof = await program.account.proof.fetch(proofPDA);
+    assert.equal(proof.signerPubkey.toBase58(), signer.publicKey.toBase58());
   });
 
-  it("Deactivates an organization", async () => {
+  it("Assigns sequential IDs from the owner's registry", async () => {
+    const registryBefore = await program.account.ownerRegistry.fetch(registryPDA);
+    const nextId = registryBefore.proofCount.toNumber();
+
+    const registeredHash = createHash("sha256").update("registry entry").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(registeredHash)],
+      program.programId
+    );
+
     await program.methods
-      .deactivateOrganization()
+      .createProof(sha256, registeredHash, null, null, null, null, null)
       .accounts({
-        organization: orgPDA,
-        admin: provider.wallet.publicKey,
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
-    const org = await program.account.organization.fetch(orgPDA);
-    assert.equal(org.active, false);
+    const proof = await program.account.proof.fetch(proofPDA);
+    assert.equal(proof.id.toNumber(), nextId);
+
+    const registryAfter = await program.account.ownerRegistry.fetch(registryPDA);
+    assert.equal(registryAfter.proofCount.toNumber(), nextId + 1);
   });
 
-  it("Rejects submissions to inactive orgs", async () => {
-    const org = await program.account.organization.fetch(orgPDA);
-    const submissionId = org.submissionCount.toNumber();
+  it("Rejects a proof once the owner's registry is exhausted", async () => {
+    // Its registry is loaded from tests/fixtures with proof_count = u64::MAX
+    const exhausted = anchor.web3.Keypair.fromSeed(new Uint8Array(32).fill(28));
+    const [exhaustedRegistryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("registry"), exhausted.publicKey.toBuffer()],
+      program.programId
+    );
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: exhausted.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
 
-    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("submission"),
-        orgPDA.toBuffer(),
-        new anchor.BN(submissionId).toArrayLike(Buffer, "le", 8),
-      ],
+    const overflowHash = createHash("sha256").update("registry overflow").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), overflowHash],
       program.programId
     );
 
     try {
       await program.methods
-        .submitTip("shouldFailArweaveHash1234567890123456789012")
+        .createProof([...overflowHash], null, null, null, null)
         .accounts({
-          submission: submissionPDA,
-          organization: orgPDA,
-          submitter: provider.wallet.publicKey,
+          proof: proofPDA,
+          config: configPDA,
+          registry: exhaustedRegistryPDA,
+          owner: exhausted.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
+        .signers([exhausted])
         .rpc();
-      assert.fail("Should have rejected submission to inactive org");
-    } catch {
-      assert.ok(true, "Correctly rejected submission to inactive org");
+      assert.fail("Should have rejected the overflowing proof ID");
+    } catch (err) {
+      assert.include(err.toString(), "CounterOverflow");
     }
+
+    const registry = await program.account.ownerRegistry.fetch(exhaustedRegistryPDA);
+    assert.equal(registry.proofCount.toString(), "18446744073709551615");
   });
 
-  it("Rejects duplicate org slug", async () => {
-    const [dupOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("org"), Buffer.from(orgSlug)],
+  it("Stamps a namespaced proof for a hash that is already stamped globally", async () => {
+    const [namespacedPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), provider.wallet.publicKey.toBuffer(), ...proofSeeds(hash)],
+      program.programId
+    );
+
+    await program.methods
+      .createNamespacedProof(sha256, hash, null, null, null, null)
+      .accounts({
+        proof: namespacedPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proof = await program.account.proof.fetch(namespacedPDA);
+    assert.deepEqual(Buffer.from(proof.digest), hash);
+    assert.equal(proof.namespace.toBase58(), provider.wallet.publicKey.toBase58());
+  });
+
+  it("Stamps a SHA-512 digest without truncation", async () => {
+    const sha512 = createHash("sha512").update("large archive").digest();
+    const truncated = sha512.subarray(0, 32);
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(sha512, 1)],
+      program.programId
+    );
+    const [truncatedPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(truncated, 1)],
       program.programId
     );
 
     try {
       await program.methods
-        .createOrganization(orgSlug, "Duplicate Org", "desc", [...fakeEncryptionKey])
+        .createProof({ sha512: {} }, truncated, null, null, null, null, null)
         .accounts({
-          organization: dupOrgPDA,
-          admin: provider.wallet.publicKey,
+          proof: truncatedPDA,
+          config: configPDA,
+          registry: registryPDA,
+          owner: provider.wallet.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
-      assert.fail("Should have rejected duplicate slug");
+      assert.fail("Should have rejected a truncated SHA-512 digest");
+    } catch (err) {
+      assert.include(String(err), "InvalidDigestLength");
+    }
+
+    await program.methods
+      .createProof({ sha512: {} }, sha512, null, null, null, null, null)
+      .accounts({
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proof = await program.account.proof.fetch(proofPDA);
+    assert.deepEqual(proof.algo, { sha512: {} });
+    assert.deepEqual(Buffer.from(proof.digest), sha512);
+  });
+
+  it("Stamps a Merkle batch and verifies inclusion", async () => {
+    const sha = (...parts: Buffer[]) =>
+      createHash("sha256").update(Buffer.concat(parts)).digest();
+    const leaves = ["a.pdf", "b.pdf", "c.pdf", "d.pdf"].map((f) => sha(Buffer.from(f)));
+    const left = sha(leaves[0], leaves[1]);
+    const right = sha(leaves[2], leaves[3]);
+    const root = sha(left, right);
+
+    const [batchPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("batch"), root],
+      program.programId
+    );
+
+    await program.methods
+      .createBatchProof([...root], leaves.length)
+      .accounts({
+        batch: batchPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const batch = await program.account.batchProof.fetch(batchPDA);
+    assert.deepEqual(Buffer.from(batch.merkleRoot), root);
+    assert.equal(batch.leafCount, 4);
+
+    // Leaf 2 ("c.pdf"): sibling is leaf 3, then the left subtree
+    await program.methods
+      .verifyInclusion([...leaves[2]], [[...leaves[3]], [...left]], 2)
+      .accounts({ batch: batchPDA })
+      .rpc();
+
+    try {
+      await program.methods
+        .verifyInclusion([...leaves[2]], [[...leaves[3]], [...left]], 1)
+        .accounts({ batch: batchPDA })
+        .rpc();
+      assert.fail("Should have rejected proof at the wrong index");
     } catch {
-      assert.ok(true, "Correctly rejected duplicate org slug");
+      assert.ok(true);
+    }
+  });
+
+  it("Stamps several files as individual proofs in one transaction", async () => {
+    const hashes = ["bulk one", "bulk two", "bulk three"].map((name) =>
+      createHash("sha256").update(name).digest()
+    );
+    const proofPDA = (digest: Buffer) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("proof"), ...proofSeeds(digest)],
+        program.programId
+      )[0];
+    const stamp = (digests: Buffer[], pdas: anchor.web3.PublicKey[]) =>
+      program.methods
+        .createProofs(sha256, digests.map((digest) => [...digest]))
+        .accounts({
+          config: configPDA,
+          registry: registryPDA,
+          owner: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(pdas.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .rpc();
+
+    try {
+      await stamp(hashes, [proofPDA(hashes[1]), proofPDA(hashes[0]), proofPDA(hashes[2])]);
+      assert.fail("Should have rejected proof accounts out of order");
+    } catch (err) {
+      assert.include(String(err), "ProofAccountMismatch");
+    }
+
+    const tooMany = Array.from({ length: 7 }, (_, i) => createHash("sha256").update(`bulk ${i}`).digest());
+    try {
+      await stamp(tooMany, tooMany.map(proofPDA));
+      assert.fail("Should have rejected more than 6 proofs");
+    } catch (err) {
+      assert.include(String(err), "TooManyProofs");
+    }
+
+    // Sending lamports to a proof address ahead of time must not block the batch
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: proofPDA(hashes[1]),
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(0),
+        })
+      )
+    );
+
+    await stamp(hashes, hashes.map(proofPDA));
+    for (const digest of hashes) {
+      const proof = await program.account.proof.fetch(proofPDA(digest));
+      assert.deepEqual(Buffer.from(proof.digest), digest);
+      assert.ok(proof.owner.equals(provider.wallet.publicKey));
+    }
+  });
+
+  it("Chains proofs into a tamper-evident log", async () => {
+    const entryHashes = ["log entry 1", "log entry 2"].map((entry) =>
+      createHash("sha256").update(entry).digest()
+    );
+    const [firstPDA, secondPDA] = entryHashes.map(
+      (digest) =>
+        anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("proof"), ...proofSeeds(digest)], program.programId)[0]
+    );
+    const link = (digest: Buffer, proof: anchor.web3.PublicKey, prevProof: anchor.web3.PublicKey | null, prev: anchor.web3.PublicKey | null) =>
+      program.methods
+        .createLinkedProof(sha256, digest, prevProof)
+        .accounts({
+          proof,
+          prev,
+          config: configPDA,
+          registry: registryPDA,
+          owner: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await link(entryHashes[0], firstPDA, null, null);
+
+    try {
+      await link(entryHashes[1], secondPDA, firstPDA, null);
+      assert.fail("Should have required the previous proof account");
+    } catch (err) {
+      assert.include(String(err), "InvalidPrevProof");
+    }
+
+    await link(entryHashes[1], secondPDA, firstPDA, firstPDA);
+
+    const first = await program.account.proof.fetch(firstPDA);
+    const second = await program.account.proof.fetch(secondPDA);
+    assert.isNull(first.prev);
+    assert.ok(second.prev.equals(firstPDA));
+
+    const expected = createHash("sha256")
+      .update(Buffer.from(first.chainHash))
+      .update(Buffer.from([0]))
+      .update(entryHashes[1])
+      .digest();
+    assert.deepEqual(Buffer.from(second.chainHash), expected);
+  });
+
+  it("Links a revised document's proof to the version it supersedes", async () => {
+    const [originalHash, amendedHash, forkHash] = ["contract v1", "contract v2", "contract v2 fork"].map((text) =>
+      createHash("sha256").update(text).digest()
+    );
+    const [originalPDA, amendedPDA, forkPDA] = [originalHash, amendedHash, forkHash].map(
+      (digest) =>
+        anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("proof"), ...proofSeeds(digest)], program.programId)[0]
+    );
+    const supersede = (digest: Buffer, proof: anchor.web3.PublicKey) =>
+      program.methods
+        .supersedeProof(sha256, digest)
+        .accounts({
+          proof,
+          oldProof: originalPDA,
+          config: configPDA,
+          registry: registryPDA,
+          owner: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await program.methods
+      .createProof(sha256, originalHash, null, null, null, null, null)
+      .accounts({
+        proof: originalPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await supersede(amendedHash, amendedPDA);
+
+    const original = await program.account.proof.fetch(originalPDA);
+    const amended = await program.account.proof.fetch(amendedPDA);
+    assert.ok(original.supersededBy.equals(amendedPDA));
+    assert.ok(amended.supersedes.equals(originalPDA));
+    assert.isNull(amended.supersededBy);
+
+    try {
+      await supersede(forkHash, forkPDA);
+      assert.fail("Should have refused to fork the version chain");
+    } catch (err) {
+      assert.include(String(err), "AlreadySuperseded");
+    }
+  });
+
+  it("Binds a claimable proof to whoever reveals its secret", async () => {
+    const digest = createHash("sha256").update("anonymous manuscript").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(digest)],
+      program.programId
+    );
+    const secret = createHash("sha256").update("author's claim secret").digest();
+    const commitment = createHash("sha256").update(secret).digest();
+    const author = anchor.web3.Keypair.generate();
+    const claim = (secret: Buffer) =>
+      program.methods
+        .claimProof([...secret])
+        .accounts({ proof: proofPDA, claimer: author.publicKey })
+        .signers([author])
+        .rpc();
+
+    await program.methods
+      .createClaimableProof(sha256, digest, [...commitment])
+      .accounts({
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    let proof = await program.account.proof.fetch(proofPDA);
+    assert.ok(proof.owner.equals(provider.wallet.publicKey));
+    assert.deepEqual(Buffer.from(proof.claimSecretHash), commitment);
+
+    try {
+      await claim(Buffer.alloc(32, 1));
+      assert.fail("Should have rejected the wrong secret");
+    } catch (err) {
+      assert.include(String(err), "InvalidClaimSecret");
+    }
+
+    await claim(secret);
+    proof = await program.account.proof.fetch(proofPDA);
+    assert.ok(proof.owner.equals(author.publicKey));
+    assert.isNull(proof.claimSecretHash);
+    assert.isAbove(proof.transferredAt.toNumber(), 0);
+
+    try {
+      await claim(secret);
+      assert.fail("Should have rejected a second claim");
+    } catch (err) {
+      assert.include(String(err), "NotClaimable");
+    }
+  });
+
+  // ─── VOID DROP TESTS ───────────────────────────────────
+
+  const orgSlug = "test-org";
+  const orgName = "Test Organization";
+  const orgDescription = "Submit anonymous tips here";
+  // Fresh ECDH public key (65 bytes: 0x04 prefix + 32 x + 32 y); the program
+  // rejects points that are not on the curve
+  const p256 = { p256: {} };
+  const p256Key = () => {
+    const ecdh = createECDH("prime256v1");
+    ecdh.generateKeys();
+    return new Uint8Array(ecdh.getPublicKey());
+  };
+  const fakeEncryptionKey = p256Key();
+
+  let orgPDA: anchor.web3.PublicKey;
+  let treasuryPDA: anchor.web3.PublicKey;
+
+  const arweave = (locator: string) => ({ kind: { arweave: {} }, locator });
+
+  // SHA-256 of the (mock) encrypted payload every tip and message points to
+  const contentHash = [...createHash("sha256").update("encrypted payload").digest()];
+
+  const submitterStatePDA = (org: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submitter"), org.toBuffer(), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  it("Creates an organization", async () => {
+    [orgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(orgSlug)],
+      program.programId
+    );
+    [treasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), orgPDA.toBuffer()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createOrganization(orgSlug, orgName, orgDescription, [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: orgPDA,
+        treasury: treasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("  Org created:", tx);
+
+    const created = (await eventsOf(tx)).find((event) => event.name === "organizationCreated");
+    assert.ok(created, "Should emit OrganizationCreated");
+    assert.ok(created.data.organization.equals(orgPDA));
+    assert.equal(created.data.slug, orgSlug);
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.slug, orgSlug);
+    assert.equal(org.name, orgName);
+    assert.equal(org.description, orgDescription);
+    assert.equal(org.admin.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.equal(org.submissionCount.toNumber(), 0);
+    assert.equal(org.active, true);
+    assert.equal(org.schemaVersion, CURRENT_SCHEMA);
+    assert.equal(org.keyCount, 1);
+
+    const orgRegistry = await program.account.orgRegistry.fetch(orgRegistryPDA);
+    assert.equal(org.index.toNumber(), orgRegistry.orgCount.toNumber() - 1);
+    assert.equal(created.data.index.toNumber(), org.index.toNumber());
+    assert.deepEqual(
+      Buffer.from(org.encryptionKeys[0]),
+      Buffer.from(fakeEncryptionKey)
+    );
+
+    console.log("  Slug:", org.slug);
+    console.log("  Name:", org.name);
+    console.log("  Admin:", org.admin.toBase58());
+  });
+
+  it("Numbers organizations in creation order", async () => {
+    const create = async (slug: string) => {
+      const [organization] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("org"), Buffer.from(slug)],
+        program.programId
+      );
+      const [treasury] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), organization.toBuffer()],
+        program.programId
+      );
+      const sig = await program.methods
+        .createOrganization(slug, "Indexed Org", "Creation order", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0)
+        .accounts({
+          organization,
+          treasury,
+          orgRegistry: orgRegistryPDA,
+          admin: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc({ commitment: "confirmed" });
+      const created = (await eventsOf(sig)).find((event) => event.name === "organizationCreated");
+      const org = await program.account.organization.fetch(organization);
+      assert.equal(created.data.index.toNumber(), org.index.toNumber());
+      return org.index.toNumber();
+    };
+
+    const before = (await program.account.orgRegistry.fetch(orgRegistryPDA)).orgCount.toNumber();
+    const first = await create("indexed-org-a");
+    const second = await create("indexed-org-b");
+    assert.equal(first, before);
+    assert.equal(second, first + 1);
+    assert.equal((await program.account.orgRegistry.fetch(orgRegistryPDA)).orgCount.toNumber(), before + 2);
+  });
+
+  it("Submits a tip to the organization", async () => {
+    const org = await program.account.organization.fetch(orgPDA);
+    const submissionId = org.submissionCount.toNumber();
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("submission"),
+        orgPDA.toBuffer(),
+        new anchor.BN(submissionId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    const fakeArweaveHash = "aBcDeFgHiJkLmNoPqRsTuVwXyZ0123456789abcdef";
+
+    const tx = await program.methods
+      .submitTip(arweave(fakeArweaveHash), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: orgPDA,
+        treasury: treasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(orgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("  Submission tx:", tx);
+
+    const submitted = (await eventsOf(tx)).find((event) => event.name === "tipSubmitted");
+    assert.ok(submitted, "Should emit TipSubmitted");
+    assert.ok(submitted.data.submission.equals(submissionPDA));
+    assert.equal(submitted.data.id.toNumber(), 0);
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.equal(sub.id.toNumber(), 0);
+    assert.equal(sub.organization.toBase58(), orgPDA.toBase58());
+    assert.equal(sub.storage.locator, fakeArweaveHash);
+    assert.deepEqual([...sub.contentHash], contentHash);
+    assert.equal(sub.submitter.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.isAbove(sub.timestamp.toNumber(), 0);
+
+    // Check org submission count incremented
+    const orgAfter = await program.account.organization.fetch(orgPDA);
+    assert.equal(orgAfter.submissionCount.toNumber(), 1);
+
+    console.log("  Submission ID:", sub.id.toNumber());
+    console.log("  Arweave hash:", sub.storage.locator);
+  });
+
+  it("Submits a second tip (increments count)", async () => {
+    const org = await program.account.organization.fetch(orgPDA);
+    const submissionId = org.submissionCount.toNumber();
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("submission"),
+        orgPDA.toBuffer(),
+        new anchor.BN(submissionId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    const globalBefore = await program.account.globalIndex.fetch(globalIndexPDA);
+
+    const tx = await program.methods
+      .submitTip(arweave("secondSubmissionArweaveHash12345678901234567"), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: orgPDA,
+        treasury: treasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(orgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.equal(sub.id.toNumber(), 1);
+
+    const orgAfter = await program.account.organization.fetch(orgPDA);
+    assert.equal(orgAfter.submissionCount.toNumber(), 2);
+
+    // Every tip also takes the next protocol-wide ID
+    const globalAfter = await program.account.globalIndex.fetch(globalIndexPDA);
+    assert.equal(globalAfter.totalSubmissions.toNumber(), globalBefore.totalSubmissions.toNumber() + 1);
+
+    const global = (await eventsOf(tx)).find((event) => event.name === "globalSubmission");
+    assert.ok(global, "Should emit GlobalSubmission");
+    assert.equal(global.data.globalId.toNumber(), globalBefore.totalSubmissions.toNumber());
+    assert.ok(global.data.organization.equals(orgPDA));
+    assert.equal(global.data.localId.toNumber(), 1);
+  });
+
+  it("Keeps a frozen submission from being closed until it is thawed", async () => {
+    const [firstSubmissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const accounts = {
+      submission: firstSubmissionPDA,
+      organization: orgPDA,
+      admin: provider.wallet.publicKey,
+    };
+
+    await program.methods.freezeSubmission().accounts(accounts).rpc();
+    let sub = await program.account.submission.fetch(firstSubmissionPDA);
+    assert.isTrue(sub.frozen);
+
+    try {
+      await program.methods.closeSubmission().accounts(accounts).rpc();
+      assert.fail("Should have refused to close a frozen submission");
+    } catch (err) {
+      assert.include(String(err), "SubmissionFrozen");
+    }
+
+    await program.methods.thawSubmission().accounts(accounts).rpc();
+    sub = await program.account.submission.fetch(firstSubmissionPDA);
+    assert.isFalse(sub.frozen);
+  });
+
+  it("Closes a processed submission without resetting the counter", async () => {
+    const [firstSubmissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Export first, so the record survives in the logs once the account is gone
+    const sub = await program.account.submission.fetch(firstSubmissionPDA);
+    const exportSig = await program.methods
+      .exportSubmission()
+      .accounts({
+        submission: firstSubmissionPDA,
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+    const exported = (await eventsOf(exportSig)).find((event) => event.name === "submissionExported");
+    assert.ok(exported, "Export should emit SubmissionExported");
+    assert.equal(exported.data.id.toNumber(), sub.id.toNumber());
+    assert.equal(exported.data.storage.locator, sub.storage.locator);
+    assert.ok(exported.data.submitter.equals(sub.submitter));
+    assert.deepEqual(exported.data.status, sub.status);
+    assert.equal(exported.data.schemaVersion, sub.schemaVersion);
+    assert.equal(exported.data.rentRecipient?.toBase58(), sub.rentRecipient?.toBase58());
+
+    await program.methods
+      .closeSubmission()
+      .accounts({
+        submission: firstSubmissionPDA,
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(firstSubmissionPDA);
+    assert.isNull(closed, "Submission account should be closed");
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.submissionCount.toNumber(), 2);
+  });
+
+  it("Replies to a submission", async () => {
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [replyPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reply"), submissionPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .replyToSubmission(arweave("replyArweaveHash12345678901234567890123456"))
+      .accounts({
+        reply: replyPDA,
+        submission: submissionPDA,
+        organization: orgPDA,
+        member: null,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const reply = await program.account.submissionReply.fetch(replyPDA);
+    assert.equal(reply.submission.toBase58(), submissionPDA.toBase58());
+    assert.equal(reply.storage.locator, "replyArweaveHash12345678901234567890123456");
+    assert.isAbove(reply.timestamp.toNumber(), 0);
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.equal(sub.repliedAt.toNumber(), reply.timestamp.toNumber());
+    assert.equal(sub.readAt.toNumber(), reply.timestamp.toNumber());
+    assert.isNull(sub.resolvedAt);
+  });
+
+  it("Moves a submission through triage statuses", async () => {
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    let sub = await program.account.submission.fetch(submissionPDA);
+    assert.deepEqual(sub.status, { new: {} });
+
+    await program.methods
+      .setSubmissionStatus({ underReview: {} })
+      .accounts({
+        submission: submissionPDA,
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    sub = await program.account.submission.fetch(submissionPDA);
+    assert.deepEqual(sub.status, { underReview: {} });
+    const readAt = sub.readAt.toNumber();
+
+    await program.methods
+      .setSubmissionStatus({ resolved: {} })
+      .accounts({
+        submission: submissionPDA,
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    sub = await program.account.submission.fetch(submissionPDA);
+    assert.deepEqual(sub.status, { resolved: {} });
+    assert.equal(sub.readAt.toNumber(), readAt, "First read time should stick");
+    assert.isAtLeast(sub.resolvedAt.toNumber(), readAt);
+
+    await program.methods
+      .setSubmissionStatus({ underReview: {} })
+      .accounts({
+        submission: submissionPDA,
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+    sub = await program.account.submission.fetch(submissionPDA);
+    assert.isNotNull(sub.resolvedAt, "Reopening keeps the last resolution time");
+  });
+
+  it("Resizes a submission's locator capacity", async () => {
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const resize = (newMax: number) =>
+      program.methods
+        .resizeSubmissionHash(newMax)
+        .accounts({
+          submission: submissionPDA,
+          organization: orgPDA,
+          admin: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    try {
+      await resize(257);
+      assert.fail("Should have rejected a capacity over 256");
+    } catch (err) {
+      assert.include(String(err), "LocatorCapacityTooLarge");
+    }
+
+    try {
+      await resize(10);
+      assert.fail("Should have rejected shrinking below the stored locator");
+    } catch (err) {
+      assert.include(String(err), "LocatorCapacityTooSmall");
+    }
+
+    await resize(256);
+    let info = await provider.connection.getAccountInfo(submissionPDA);
+    assert.equal(info.data.length, 380 - 128 + 256);
+
+    // Shrinking refunds the excess rent, leaving the account just rent-exempt
+    await resize(128);
+    info = await provider.connection.getAccountInfo(submissionPDA);
+    assert.equal(info.data.length, 380);
+    assert.equal(info.lamports, await provider.connection.getMinimumBalanceForRentExemption(380));
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.deepEqual(sub.status, { underReview: {} });
+  });
+
+  it("Migrates a schema 1 submission to the current layout", async () => {
+    // The fixture in tests/fixtures/submission-v1.json is submission 0 of this org
+    const legacySlug = "legacy-org";
+    const [legacyOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(legacySlug)],
+      program.programId
+    );
+    const [legacyTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), legacyOrgPDA.toBuffer()],
+      program.programId
+    );
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), legacyOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const migrate = () =>
+      program.methods
+        .migrateSubmission()
+        .accounts({
+          submission: submissionPDA,
+          organization: legacyOrgPDA,
+          admin: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await program.methods
+      .createOrganization(legacySlug, "Legacy Org", "Created before schema 2", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: legacyOrgPDA,
+        treasury: legacyTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    let info = await provider.connection.getAccountInfo(submissionPDA);
+    assert.equal(info.data.length, 269);
+
+    await migrate();
+    info = await provider.connection.getAccountInfo(submissionPDA);
+    assert.equal(info.data.length, 380);
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.equal(sub.schemaVersion, CURRENT_SCHEMA);
+    assert.equal(sub.statusUpdatedAt.toNumber(), 0);
+    assert.isNull(sub.parentId);
+    assert.ok(sub.organization.equals(legacyOrgPDA));
+    assert.deepEqual(sub.contentHash, contentHash);
+
+    // Already current: a second call changes nothing
+    await migrate();
+    info = await provider.connection.getAccountInfo(submissionPDA);
+    assert.equal(info.data.length, 380);
+  });
+
+  it("Migrates a schema 1 organization to the current layout", async () => {
+    // The fixture in tests/fixtures/organization-v1.json predates every org field
+    // from liveness tracking on
+    const [legacyOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from("schema1-org")],
+      program.programId
+    );
+    const migrate = (admin: anchor.web3.Keypair) =>
+      program.methods
+        .migrateOrganization()
+        .accounts({
+          organization: legacyOrgPDA,
+          orgRegistry: orgRegistryPDA,
+          admin: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: legacyAdmin.publicKey,
+          lamports: 100_000_000,
+        })
+      )
+    );
+
+    let info = await provider.connection.getAccountInfo(legacyOrgPDA);
+    assert.equal(info.data.length, 643);
+
+    // Only the org's admin may migrate it
+    const outsider = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: outsider.publicKey,
+          lamports: 10_000_000,
+        })
+      )
+    );
+    try {
+      await migrate(outsider);
+      assert.fail("Should have rejected a migration by someone other than the admin");
+    } catch (err) {
+      assert.include(String(err), "InsufficientRole");
+    }
+
+    const orgCount = (await program.account.orgRegistry.fetch(orgRegistryPDA)).orgCount;
+    await migrate(legacyAdmin);
+    info = await provider.connection.getAccountInfo(legacyOrgPDA);
+    assert.equal(info.data.length, 1100);
+    const org = await program.account.organization.fetch(legacyOrgPDA);
+    assert.equal(org.schemaVersion, CURRENT_SCHEMA);
+    assert.equal(org.slug, "schema1-org");
+    assert.equal(org.handle, "schema1-org");
+    assert.equal(org.keyCount, 1);
+    assert.ok(org.admin.equals(legacyAdmin.publicKey));
+    assert.equal(org.submissionCount.toNumber(), 3);
+    assert.equal(org.openSubmissions.toNumber(), 2);
+    assert.equal(org.maxHashLen, 128);
+    assert.equal(org.index.toNumber(), orgCount.toNumber());
+    assert.isNull(org.validatorProgram);
+
+    // Already current: a second call changes nothing
+    await migrate(legacyAdmin);
+    assert.equal((await program.account.organization.fetch(legacyOrgPDA)).index.toNumber(), orgCount.toNumber());
+  });
+
+  it("Updates an organization's description only", async () => {
+    await program.methods
+      .updateOrganization(null, "Updated tip guidelines")
+      .accounts({
+        organization: orgPDA,
+        member: null,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.name, orgName);
+    assert.equal(org.description, "Updated tip guidelines");
+  });
+
+  it("Sets and clears an organization's submission guidelines", async () => {
+    const setGuidelines = (guidelines: string | null) =>
+      program.methods
+        .setGuidelines(guidelines)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+
+    let org = await program.account.organization.fetch(orgPDA);
+    assert.isNull(org.guidelines);
+
+    const guidelines = "Documents and recordings about public officials. No personal disputes.";
+    await setGuidelines(guidelines);
+    org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.guidelines, guidelines);
+    assert.equal(org.description, "Updated tip guidelines");
+
+    try {
+      await setGuidelines("x".repeat(201));
+      assert.fail("Should have rejected guidelines over 200 chars");
+    } catch (err) {
+      assert.include(String(err), "GuidelinesTooLong");
+    }
+
+    await setGuidelines(null);
+    org = await program.account.organization.fetch(orgPDA);
+    assert.isNull(org.guidelines);
+  });
+
+  it("Rebrands an organization's handle without changing its slug", async () => {
+    const setHandle = (handle: string) =>
+      program.methods
+        .setHandle(handle)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+
+    let org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.handle, orgSlug);
+
+    await setHandle("x");
+    org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.handle, "x");
+    assert.equal(org.slug, orgSlug);
+
+    try {
+      await setHandle("x".repeat(33));
+      assert.fail("Should have rejected a handle over 32 chars");
+    } catch (err) {
+      assert.include(String(err), "InvalidHandleLength");
+    }
+
+    try {
+      await setHandle("Test Org");
+      assert.fail("Should have rejected a handle that isn't URL-safe");
+    } catch (err) {
+      assert.include(String(err), "InvalidSlug");
+    }
+
+    await setHandle(orgSlug);
+  });
+
+  it("Rotates an organization's encryption key", async () => {
+    const newKey = p256Key();
+
+    await program.methods
+      .rotateOrgKey([[...newKey]])
+      .accounts({
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.deepEqual(Buffer.from(org.encryptionKeys[0]), Buffer.from(newKey));
+    assert.equal(org.keyVersion, 1);
+    assert.isAbove(org.keyRotatedAt.toNumber(), 0);
+    assert.deepEqual(
+      Buffer.from(org.keyFingerprint),
+      createHash("sha256").update(Buffer.from(newKey)).digest().subarray(0, 8)
+    );
+  });
+
+  it("Splits an organization's decryption across several editor keys", async () => {
+    const slug = "split-key-org";
+    const [splitOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(slug)],
+      program.programId
+    );
+    const [splitTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), splitOrgPDA.toBuffer()],
+      program.programId
+    );
+    const keys = [p256Key(), p256Key(), p256Key()].map((key) => [...key]);
+
+    await program.methods
+      .createOrganization(slug, "Split Key Org", "Tips need three editors", keys, p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: splitOrgPDA,
+        treasury: splitTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const org = await program.account.organization.fetch(splitOrgPDA);
+    assert.equal(org.keyCount, 3);
+    keys.forEach((key, i) => assert.deepEqual(Buffer.from(org.encryptionKeys[i]), Buffer.from(key)));
+    assert.deepEqual(
+      Buffer.from(org.keyFingerprint),
+      createHash("sha256").update(Buffer.concat(keys.map((key) => Buffer.from(key)))).digest().subarray(0, 8)
+    );
+
+    // Dropping an editor zeroes their slot
+    await program.methods
+      .rotateOrgKey(keys.slice(0, 2))
+      .accounts({ organization: splitOrgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+    const rotated = await program.account.organization.fetch(splitOrgPDA);
+    assert.equal(rotated.keyCount, 2);
+    assert.deepEqual(Buffer.from(rotated.encryptionKeys[2]), Buffer.alloc(65));
+
+    for (const bad of [[], [...keys, editorKey(9)]]) {
+      try {
+        await program.methods
+          .rotateOrgKey(bad)
+          .accounts({ organization: splitOrgPDA, admin: provider.wallet.publicKey })
+          .rpc();
+        assert.fail("Should have rejected the key count");
+      } catch (err) {
+        assert.include(String(err), "InvalidKeyCount");
+      }
+    }
+  });
+
+  it("Lets members act according to their role", async () => {
+    const editor = anchor.web3.Keypair.generate();
+    const [editorMemberPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("member"), orgPDA.toBuffer(), editor.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .addMember(editor.publicKey, { editor: {} })
+      .accounts({
+        member: editorMemberPDA,
+        organization: orgPDA,
+        authorityMember: null,
+        authority: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .updateOrganization(null, "Edited by the desk editor")
+      .accounts({ organization: orgPDA, member: editorMemberPDA, authority: editor.publicKey })
+      .signers([editor])
+      .rpc();
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.description, "Edited by the desk editor");
+
+    try {
+      await program.methods
+        .deactivateOrganization()
+        .accounts({ organization: orgPDA, member: editorMemberPDA, authority: editor.publicKey })
+        .signers([editor])
+        .rpc();
+      assert.fail("Should have rejected an editor deactivating the org");
+    } catch (err) {
+      assert.include(String(err), "InsufficientRole");
+    }
+
+    await program.methods
+      .removeMember()
+      .accounts({
+        member: editorMemberPDA,
+        organization: orgPDA,
+        authorityMember: null,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(editorMemberPDA));
+  });
+
+  it("Proposes and cancels an admin transfer", async () => {
+    const candidate = anchor.web3.Keypair.generate().publicKey;
+
+    await program.methods
+      .proposeAdminTransfer(candidate)
+      .accounts({
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    let org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.pendingAdmin.toBase58(), candidate.toBase58());
+    assert.equal(org.admin.toBase58(), provider.wallet.publicKey.toBase58());
+
+    const cancelSig = await program.methods
+      .cancelAdminTransfer()
+      .accounts({
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    org = await program.account.organization.fetch(orgPDA);
+    assert.isNull(org.pendingAdmin);
+
+    const cancelled = (await eventsOf(cancelSig)).find(
+      (event) => event.name === "adminTransferCancelled"
+    );
+    assert.ok(cancelled, "Cancel should emit AdminTransferCancelled");
+    assert.ok(cancelled.data.pendingAdmin.equals(candidate));
+  });
+
+  it("Deactivates an organization", async () => {
+    await program.methods
+      .deactivateOrganization()
+      .accounts({
+        organization: orgPDA,
+        member: null,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.active, false);
+  });
+
+  it("Rejects submissions to inactive orgs", async () => {
+    const org = await program.account.organization.fetch(orgPDA);
+    const submissionId = org.submissionCount.toNumber();
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("submission"),
+        orgPDA.toBuffer(),
+        new anchor.BN(submissionId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .submitTip(arweave("shouldFailArweaveHash1234567890123456789012"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected submission to inactive org");
+    } catch {
+      assert.ok(true, "Correctly rejected submission to inactive org");
+    }
+  });
+
+  it("Reactivates an organization", async () => {
+    await program.methods
+      .reactivateOrganization()
+      .accounts({
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    let org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.active, true);
+
+    await program.methods
+      .deactivateOrganization()
+      .accounts({
+        organization: orgPDA,
+        member: null,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.active, false);
+  });
+
+  it("Lets anyone deactivate an org whose admin went silent", async () => {
+    const slug = "liveness-org";
+    const [livenessOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(slug)],
+      program.programId
+    );
+    const [livenessTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), livenessOrgPDA.toBuffer()],
+      program.programId
+    );
+    const checkLiveness = () =>
+      program.methods.checkOrgLiveness().accounts({ organization: livenessOrgPDA }).rpc();
+
+    await program.methods
+      .createOrganization(slug, "Liveness Org", "Goes dark without its admin", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 3, 0)
+      .accounts({
+        organization: livenessOrgPDA,
+        treasury: livenessTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await checkLiveness();
+      assert.fail("Should have rejected while the admin is still active");
+    } catch (err) {
+      assert.include(String(err), "AdminStillActive");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+    await checkLiveness();
+    let org = await program.account.organization.fetch(livenessOrgPDA);
+    assert.isFalse(org.active);
+
+    // Any admin action, such as reactivating, resets the clock
+    await program.methods
+      .reactivateOrganization()
+      .accounts({ organization: livenessOrgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+    org = await program.account.organization.fetch(livenessOrgPDA);
+    assert.isTrue(org.active);
+    assert.isAbove(org.lastAdminActivity.toNumber(), org.createdAt.toNumber());
+  });
+
+  it("Rejects duplicate org slug", async () => {
+    const [dupOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(orgSlug)],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .createOrganization(orgSlug, "Duplicate Org", "Same slug as test-org", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+        .accounts({
+          organization: dupOrgPDA,
+          treasury: treasuryPDA,
+          orgRegistry: orgRegistryPDA,
+          admin: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected duplicate slug");
+    } catch {
+      assert.ok(true, "Correctly rejected duplicate org slug");
+    }
+  });
+
+  it("Rejects malformed slugs and too-short profiles", async () => {
+    const cases: [string, string, string][] = [
+      ["Washington Post", "Rejected at creation", "InvalidSlug"],
+      ["-leading-hyphen", "Rejected at creation", "InvalidSlug"],
+      ["trailing-hyphen-", "Rejected at creation", "InvalidSlug"],
+      ["ab", "Rejected at creation", "SlugTooShort"],
+      ["short-desc-org", "tips", "DescriptionTooShort"],
+    ];
+    for (const [badSlug, description, expected] of cases) {
+      const [badOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("org"), Buffer.from(badSlug)],
+        program.programId
+      );
+      const [badTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), badOrgPDA.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createOrganization(badSlug, "Bad Slug Org", description, [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+          .accounts({
+            organization: badOrgPDA,
+            treasury: badTreasuryPDA,
+            orgRegistry: orgRegistryPDA,
+            admin: provider.wallet.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail(`Should have rejected slug "${badSlug}"`);
+      } catch (err) {
+        assert.include(String(err), expected);
+      }
+    }
+
+    try {
+      await program.methods
+        .updateOrganization(null, "tips")
+        .accounts({ organization: orgPDA, member: null, authority: provider.wallet.publicKey })
+        .rpc();
+      assert.fail("Should have rejected a too-short description update");
+    } catch (err) {
+      assert.include(String(err), "DescriptionTooShort");
+    }
+  });
+
+  it("Checks the encryption key against its declared algorithm", async () => {
+    const slug = "x25519-org";
+    const [x25519OrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(slug)],
+      program.programId
+    );
+    const [x25519TreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), x25519OrgPDA.toBuffer()],
+      program.programId
+    );
+    const create = (key: Uint8Array, keyAlgo: object = { x25519: {} }) =>
+      program.methods
+        .createOrganization(slug, "X25519 Org", "Curve25519 drop box", [[...key]], keyAlgo, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+        .accounts({
+          organization: x25519OrgPDA,
+          treasury: x25519TreasuryPDA,
+          orgRegistry: orgRegistryPDA,
+          admin: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    // A 65-byte P-256 point is too long for an X25519 key
+    try {
+      await create(fakeEncryptionKey);
+      assert.fail("Should have rejected a key longer than the algorithm allows");
+    } catch (err) {
+      assert.include(String(err), "InvalidEncryptionKey");
+    }
+
+    // A P-256 key with the right prefix but a point off the curve
+    const offCurveKey = Uint8Array.from(fakeEncryptionKey);
+    offCurveKey[64] ^= 1;
+    try {
+      await create(offCurveKey, p256);
+      assert.fail("Should have rejected a point that is not on the curve");
+    } catch (err) {
+      assert.include(String(err), "InvalidEncryptionKey");
+    }
+
+    const x25519Key = new Uint8Array(65);
+    x25519Key.fill(9, 0, 32);
+    await create(x25519Key);
+    const org = await program.account.organization.fetch(x25519OrgPDA);
+    assert.deepEqual(org.keyAlgo, { x25519: {} });
+    assert.deepEqual(
+      Buffer.from(org.keyFingerprint),
+      createHash("sha256").update(x25519Key.subarray(0, 32)).digest().subarray(0, 8)
+    );
+  });
+
+  it("Stamps a proof linked to the organization", async () => {
+    const articleHash = createHash("sha256").update("published article").digest();
+    const [proofPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(articleHash)],
+      program.programId
+    );
+
+    await program.methods
+      .createOrgProof(sha256, articleHash, "https://example.org/article", "text/html")
+      .accounts({
+        proof: proofPDA,
+        config: configPDA,
+        registry: registryPDA,
+        organization: orgPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proof = await program.account.proof.fetch(proofPDA);
+    assert.equal(proof.organization.toBase58(), orgPDA.toBase58());
+    assert.equal(proof.owner.toBase58(), provider.wallet.publicKey.toBase58());
+  });
+
+  it("Charges a submission fee into the org treasury", async () => {
+    const feeSlug = "fee-org";
+    const fee = new anchor.BN(1_000_000);
+    const [feeOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(feeSlug)],
+      program.programId
+    );
+    const [feeTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), feeOrgPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createOrganization(feeSlug, "Fee Org", "Paid tips only", [[...fakeEncryptionKey]], p256, fee, 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: feeOrgPDA,
+        treasury: feeTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), feeOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const balanceBefore = await provider.connection.getBalance(feeTreasuryPDA);
+
+    await program.methods
+      .submitTip(arweave("paidSubmissionArweaveHash123456789012345678"), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: feeOrgPDA,
+        treasury: feeTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(feeOrgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(feeTreasuryPDA);
+    assert.equal(balanceAfter - balanceBefore, fee.toNumber());
+
+    await program.methods
+      .withdrawFees()
+      .accounts({
+        organization: feeOrgPDA,
+        treasury: feeTreasuryPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const balanceSwept = await provider.connection.getBalance(feeTreasuryPDA);
+    assert.equal(balanceSwept, balanceBefore);
+  });
+
+  it("Splits a tip's lamport fee with the protocol treasury", async () => {
+    const fee = 1_000_000;
+    const [feeOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from("fee-org")],
+      program.programId
+    );
+    const [feeTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), feeOrgPDA.toBuffer()],
+      program.programId
+    );
+    const protocolTreasury = anchor.web3.Keypair.generate().publicKey;
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: protocolTreasury,
+          lamports: anchor.web3.LAMPORTS_PER_SOL,
+        })
+      )
+    );
+    const setProtocolFee = (bps: number) =>
+      program.methods
+        .setProtocolFee(bps, protocolTreasury)
+        .accounts({ config: configPDA, authority: provider.wallet.publicKey })
+        .rpc();
+
+    try {
+      await setProtocolFee(1001);
+      assert.fail("Should have rejected a protocol fee above 10%");
+    } catch (err) {
+      assert.include(String(err), "FeeTooHigh");
+    }
+    await setProtocolFee(250);
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), feeOrgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const orgBefore = await provider.connection.getBalance(feeTreasuryPDA);
+    const protocolBefore = await provider.connection.getBalance(protocolTreasury);
+
+    await program.methods
+      .submitTip(arweave("splitFeeSubmissionArweaveHash12345678901234"), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: feeOrgPDA,
+        treasury: feeTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(feeOrgPDA),
+        submitter: provider.wallet.publicKey,
+        protocolTreasury,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const protocolCut = (await provider.connection.getBalance(protocolTreasury)) - protocolBefore;
+    const orgCut = (await provider.connection.getBalance(feeTreasuryPDA)) - orgBefore;
+    assert.equal(protocolCut, 25_000);
+    assert.equal(protocolCut + orgCut, fee);
+
+    await setProtocolFee(0);
+  });
+
+  it("Charges an SPL-token submission fee to the admin's token account", async () => {
+    const tokenSlug = "token-fee-org";
+    const tokenFee = 2_500_000;
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const [tokenOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(tokenSlug)],
+      program.programId
+    );
+    const [tokenTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), tokenOrgPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createOrganization(tokenSlug, "Token Fee Org", "USDC tips", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: tokenOrgPDA,
+        treasury: tokenTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // The provider wallet is both admin and submitter, so fees move between two of its token accounts
+    const mint = await createMint(provider.connection, payer, provider.wallet.publicKey, null, 6);
+    const otherMint = await createMint(provider.connection, payer, provider.wallet.publicKey, null, 6);
+    const submitterTokens = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint,
+      provider.wallet.publicKey
+    );
+    const orgFeeTokens = await createAccount(
+      provider.connection,
+      payer,
+      mint,
+      provider.wallet.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    await mintTo(provider.connection, payer, mint, submitterTokens, payer, 10_000_000);
+
+    await program.methods
+      .updateTokenFee(mint, new anchor.BN(tokenFee))
+      .accounts({
+        organization: tokenOrgPDA,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), tokenOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .submitTip(arweave("tokenSubmissionArweaveHash12345678901234567"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: tokenOrgPDA,
+          treasury: tokenTreasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(tokenOrgPDA),
+          submitter: provider.wallet.publicKey,
+          feeMint: otherMint,
+          submitterTokenAccount: submitterTokens,
+          orgFeeAccount: orgFeeTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected the wrong fee mint");
+    } catch (err) {
+      assert.include(String(err), "WrongFeeMint");
+    }
+
+    await program.methods
+      .submitTip(arweave("tokenSubmissionArweaveHash12345678901234567"), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: tokenOrgPDA,
+        treasury: tokenTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(tokenOrgPDA),
+        submitter: provider.wallet.publicKey,
+        feeMint: mint,
+        submitterTokenAccount: submitterTokens,
+        orgFeeAccount: orgFeeTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const collected = await getAccount(provider.connection, orgFeeTokens);
+    assert.equal(Number(collected.amount), tokenFee);
+  });
+
+  it("Rejects a second tip from the same wallet inside the cooldown", async () => {
+    const cooldownSlug = "cooldown-org";
+    const [cooldownOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(cooldownSlug)],
+      program.programId
+    );
+    const [cooldownTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), cooldownOrgPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createOrganization(cooldownSlug, "Cooldown Org", "One tip per hour", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 3600, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: cooldownOrgPDA,
+        treasury: cooldownTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const submissionAt = (id: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), cooldownOrgPDA.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    await program.methods
+      .submitTip(arweave("firstCooldownArweaveHash1234567890123456789"), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionAt(0),
+        config: configPDA,
+        organization: cooldownOrgPDA,
+        treasury: cooldownTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(cooldownOrgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const state = await program.account.submitterState.fetch(submitterStatePDA(cooldownOrgPDA));
+    assert.isAbove(state.lastSubmissionTs.toNumber(), 0);
+
+    try {
+      await program.methods
+        .submitTip(arweave("secondCooldownArweaveHash123456789012345678"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionAt(1),
+          config: configPDA,
+          organization: cooldownOrgPDA,
+          treasury: cooldownTreasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(cooldownOrgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a tip inside the cooldown");
+    } catch (err) {
+      assert.include(String(err), "SubmissionTooSoon");
+    }
+  });
+
+  it("Accepts an IPFS CIDv1 as a submission pointer", async () => {
+    const ipfsSlug = "ipfs-org";
+    const cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdibafybeigdyrzt5sfp7";
+    const [ipfsOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(ipfsSlug)],
+      program.programId
+    );
+    const [ipfsTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), ipfsOrgPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createOrganization(ipfsSlug, "IPFS Org", "Tips pinned to IPFS", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: ipfsOrgPDA,
+        treasury: ipfsTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), ipfsOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Longer than any Arweave id, so it only fits when declared as IPFS
+    try {
+      await program.methods
+        .submitTip(arweave(cid), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: ipfsOrgPDA,
+          treasury: ipfsTreasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(ipfsOrgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a CID-length locator declared as Arweave");
+    } catch (err) {
+      assert.include(String(err), "LocatorTooLong");
+    }
+
+    await program.methods
+      .submitTip({ kind: { ipfs: {} }, locator: cid }, contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: ipfsOrgPDA,
+        treasury: ipfsTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(ipfsOrgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.deepEqual(sub.storage.kind, { ipfs: {} });
+    assert.equal(sub.storage.locator, cid);
+  });
+
+  it("Only accepts allowlisted submitters when allowlist-only", async () => {
+    const privateSlug = "private-org";
+    const [privateOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(privateSlug)],
+      program.programId
+    );
+    const [privateTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), privateOrgPDA.toBuffer()],
+      program.programId
+    );
+    const [allowPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("allow"), privateOrgPDA.toBuffer(), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), privateOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createOrganization(privateSlug, "Private Org", "Vetted sources only", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: privateOrgPDA,
+        treasury: privateTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .setAllowlistOnly(true)
+      .accounts({ organization: privateOrgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+
+    const submitAccounts = {
+      submission: submissionPDA,
+      config: configPDA,
+      organization: privateOrgPDA,
+      treasury: privateTreasuryPDA,
+      globalIndex: globalIndexPDA,
+      submitterState: submitterStatePDA(privateOrgPDA),
+      submitter: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    try {
+      await program.methods
+        .submitTip(arweave("unvettedArweaveHash12345678901234567890123"), contentHash, null, 0, null)
+        .accounts(submitAccounts)
+        .rpc();
+      assert.fail("Should have rejected a submitter without an allowlist entry");
+    } catch (err) {
+      assert.include(String(err), "NotAllowlisted");
+    }
+
+    await program.methods
+      .addToAllowlist(provider.wallet.publicKey)
+      .accounts({
+        allowlist: allowPDA,
+        organization: privateOrgPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .submitTip(arweave("vettedArweaveHash1234567890123456789012345"), contentHash, null, 0, null)
+      .accounts({ ...submitAccounts, allowlist: allowPDA })
+      .rpc();
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.equal(sub.organization.toBase58(), privateOrgPDA.toBase58());
+
+    await program.methods
+      .removeFromAllowlist()
+      .accounts({ allowlist: allowPDA, organization: privateOrgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+
+    const removed = await provider.connection.getAccountInfo(allowPDA);
+    assert.isNull(removed, "Allowlist entry should be closed");
+  });
+
+  it("Rejects tips once the submission cap is reached", async () => {
+    // ipfs-org already holds one submission
+    const [ipfsOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from("ipfs-org")],
+      program.programId
+    );
+    const [ipfsTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), ipfsOrgPDA.toBuffer()],
+      program.programId
+    );
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), ipfsOrgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .updateMaxSubmissions(new anchor.BN(1))
+      .accounts({ organization: ipfsOrgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .submitTip(arweave("overCapArweaveHash123456789012345678901234"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: ipfsOrgPDA,
+          treasury: ipfsTreasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(ipfsOrgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a tip over the cap");
+    } catch (err) {
+      assert.include(String(err), "SubmissionCapReached");
+    }
+  });
+
+  it("Rejects tips after the submission window closes", async () => {
+    const windowSlug = "closed-window-org";
+    const [windowOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(windowSlug)],
+      program.programId
+    );
+    const [windowTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), windowOrgPDA.toBuffer()],
+      program.programId
+    );
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), windowOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    // Window closed at the start of the epoch, long ago
+    await program.methods
+      .createOrganization(windowSlug, "Closed Window Org", "Campaign ended", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(1), 0, 0)
+      .accounts({
+        organization: windowOrgPDA,
+        treasury: windowTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .submitTip(arweave("lateTipArweaveHash1234567890123456789012345"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: windowOrgPDA,
+          treasury: windowTreasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(windowOrgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a tip outside the window");
+    } catch (err) {
+      assert.include(String(err), "SubmissionWindowClosed");
+    }
+  });
+
+  it("Writes a zero-copy submission for fewer compute units", async () => {
+    const locator = "zeroCopyArweaveHash123456789012345678901234";
+    const nextSubmissionPDA = async () => {
+      const org = await program.account.organization.fetch(orgPDA);
+      return anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    };
+    const accounts = (submission: anchor.web3.PublicKey) => ({
+      submission,
+      config: configPDA,
+      organization: orgPDA,
+      treasury: treasuryPDA,
+      globalIndex: globalIndexPDA,
+      submitterState: submitterStatePDA(orgPDA),
+      submitter: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+    const unitsUsed = async (sig: string) => {
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return tx.meta.computeUnitsConsumed;
+    };
+
+    const borshSig = await program.methods
+      .submitTip(arweave(locator), contentHash, null, 0, null)
+      .accounts(accounts(await nextSubmissionPDA()))
+      .rpc({ commitment: "confirmed" });
+
+    const zcPDA = await nextSubmissionPDA();
+    // A 70-char CID fits IPFS but not the zero-copy locator buffer
+    try {
+      await program.methods
+        .submitTipZc({ kind: { ipfs: {} }, locator: "b".repeat(70) }, 0, null)
+        .accounts(accounts(zcPDA))
+        .rpc();
+      assert.fail("Should have rejected a locator over the zero-copy cap");
+    } catch (err) {
+      assert.include(String(err), "ZcLocatorTooLong");
+    }
+
+    const zcSig = await program.methods
+      .submitTipZc(arweave(locator), 0, null)
+      .accounts(accounts(zcPDA))
+      .rpc({ commitment: "confirmed" });
+
+    const sub = await program.account.submissionZc.fetch(zcPDA);
+    assert.equal(Buffer.from(sub.locator.slice(0, sub.locatorLen)).toString(), locator);
+    assert.ok(sub.organization.equals(orgPDA));
+
+    // Zero-copy submissions can be exported too before a purge reclaims them
+    const exportSig = await program.methods
+      .exportSubmissionZc()
+      .accounts({ submission: zcPDA, organization: orgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+    const exported = (await eventsOf(exportSig)).find((event) => event.name === "submissionZcExported");
+    assert.ok(exported, "Export should emit SubmissionZcExported");
+    assert.equal(exported.data.id.toNumber(), sub.id.toNumber());
+    assert.equal(exported.data.locator, locator);
+    assert.equal(exported.data.status, sub.status);
+
+    const borshUnits = await unitsUsed(borshSig);
+    const zcUnits = await unitsUsed(zcSig);
+    console.log("  submit_tip CU:", borshUnits, "submit_tip_zc CU:", zcUnits);
+    assert.isBelow(zcUnits, borshUnits);
+  });
+
+  it("Flags tips sent by the protocol relayer", async () => {
+    const setRelayer = (relayer: anchor.web3.PublicKey) =>
+      program.methods
+        .setRelayer(relayer)
+        .accounts({ config: configPDA, authority: provider.wallet.publicKey })
+        .rpc();
+
+    await setRelayer(provider.wallet.publicKey);
+
+    const org = await program.account.organization.fetch(orgPDA);
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .submitTip(arweave("relayedTipArweaveHash123456789012345678901"), contentHash, null, 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: orgPDA,
+        treasury: treasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(orgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.isTrue(sub.viaRelay);
+
+    await setRelayer(anchor.web3.PublicKey.default);
+  });
+
+  it("Rate-limits relayed tips per source IP hash", async () => {
+    const setRelayer = (relayer: anchor.web3.PublicKey) =>
+      program.methods
+        .setRelayer(relayer)
+        .accounts({ config: configPDA, authority: provider.wallet.publicKey })
+        .rpc();
+    const setIpLimit = (windowSecs: number, limit: number) =>
+      program.methods
+        .updateIpRateLimit(windowSecs, limit)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    // The relayer hashes the source IP with its own salt before it reaches the chain
+    const ipHash = (ip: string) => [...createHash("sha256").update(`relayer-salt:${ip}`).digest()];
+    const relay = async (ip: number[] | null) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const ipRateState = ip
+        ? anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("iprate"), orgPDA.toBuffer(), Buffer.from(ip)],
+            program.programId
+          )[0]
+        : null;
+      await program.methods
+        .submitTip(arweave("ipLimitedTipArweaveHash12345678901234567890"), contentHash, null, 0, ip)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          ipRateState,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    };
+
+    await setRelayer(provider.wallet.publicKey);
+    await setIpLimit(3600, 1);
+
+    try {
+      await relay(null);
+      assert.fail("Should have required an IP hash for a relayed tip");
+    } catch (err) {
+      assert.include(String(err), "IpHashRequired");
+    }
+
+    await relay(ipHash("203.0.113.7"));
+    try {
+      await relay(ipHash("203.0.113.7"));
+      assert.fail("Should have rejected a second tip from the same IP");
+    } catch (err) {
+      assert.include(String(err), "IpRateLimitExceeded");
+    }
+    // Other sources are unaffected
+    await relay(ipHash("198.51.100.23"));
+
+    // The window is shared by every relayed tip path
+    const org = await program.account.organization.fetch(orgPDA);
+    const [zcPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const ip = ipHash("203.0.113.7");
+    try {
+      await program.methods
+        .submitTipZc(arweave("ipLimitedZcTipArweaveHash123456789012345678"), 0, ip)
+        .accounts({
+          submission: zcPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          ipRateState: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("iprate"), orgPDA.toBuffer(), Buffer.from(ip)],
+            program.programId
+          )[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a zero-copy tip from the same IP");
+    } catch (err) {
+      assert.include(String(err), "IpRateLimitExceeded");
+    }
+
+    await setIpLimit(0, 0);
+    await setRelayer(anchor.web3.PublicKey.default);
+  });
+
+  it("Lets a submitter withdraw an untriaged submission", async () => {
+    const tip = async () => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .submitTip(arweave("withdrawnTipArweaveHash12345678901234567890"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return submissionPDA;
+    };
+    const cancel = (submission: anchor.web3.PublicKey) =>
+      program.methods
+        .cancelSubmission()
+        .accounts({ submission, organization: orgPDA, submitter: provider.wallet.publicKey })
+        .rpc();
+
+    const triaged = await tip();
+    await program.methods
+      .setSubmissionStatus({ underReview: {} })
+      .accounts({ submission: triaged, organization: orgPDA, admin: provider.wallet.publicKey })
+      .rpc();
+    try {
+      await cancel(triaged);
+      assert.fail("Should have rejected withdrawing a triaged submission");
+    } catch (err) {
+      assert.include(String(err), "SubmissionAlreadyProcessed");
+    }
+
+    const fresh = await tip();
+    const openBefore = (await program.account.organization.fetch(orgPDA)).openSubmissions.toNumber();
+    await cancel(fresh);
+
+    assert.isNull(await provider.connection.getAccountInfo(fresh), "Withdrawn submission should be closed");
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.openSubmissions.toNumber(), openBefore - 1);
+  });
+
+  it("Threads a submitter's follow-up under their earlier submission", async () => {
+    const parentId = new anchor.BN(1);
+    const [parentPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), parentId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const followUp = async (submitter: anchor.web3.Keypair | null) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const wallet = submitter ? submitter.publicKey : provider.wallet.publicKey;
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [statePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submitter"), orgPDA.toBuffer(), wallet.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .followUpSubmission(arweave("followUpArweaveHash1234567890123456789012"), contentHash, parentId, null)
+        .accounts({
+          submission: submissionPDA,
+          parent: parentPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: statePDA,
+          submitter: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers(submitter ? [submitter] : [])
+        .rpc();
+      return submissionPDA;
+    };
+
+    // Only the source who started the thread may continue it
+    const outsider = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: outsider.publicKey,
+          lamports: 10_000_000,
+        })
+      )
+    );
+    try {
+      await followUp(outsider);
+      assert.fail("Should have rejected a follow-up to someone else's submission");
+    } catch (err) {
+      assert.include(String(err), "InvalidParentSubmission");
+    }
+
+    const submissionPDA = await followUp(null);
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.equal(sub.parentId.toNumber(), parentId.toNumber());
+    assert.deepEqual(sub.status, { new: {} });
+
+    const parent = await program.account.submission.fetch(parentPDA);
+    assert.equal(sub.category, parent.category);
+    assert.isNull(parent.parentId);
+  });
+
+  it("Lets anyone delete a submission after its expiry", async () => {
+    const janitor = anchor.web3.Keypair.generate();
+    const tip = async (expiresAt: anchor.BN) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .submitTip(arweave("ephemeralTipArweaveHash12345678901234567890"), contentHash, expiresAt, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return submissionPDA;
+    };
+    const expire = (submission: anchor.web3.PublicKey, rentRecipient: anchor.web3.PublicKey | null) =>
+      program.methods
+        .expireSubmission(false)
+        .accounts({
+          submission,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          rentRecipient,
+          tombstone: null,
+          caller: janitor.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([janitor])
+        .rpc();
+
+    const lasting = await tip(new anchor.BN(Math.floor(Date.now() / 1000) + 3600));
+    try {
+      await expire(lasting, provider.wallet.publicKey);
+      assert.fail("Should have rejected expiring a live submission");
+    } catch (err) {
+      assert.include(String(err), "SubmissionNotExpired");
+    }
+
+    const ephemeral = await tip(new anchor.BN(1));
+    try {
+      await expire(ephemeral, null);
+      assert.fail("Should have required the submission's rent recipient");
+    } catch (err) {
+      assert.include(String(err), "WrongRentRecipient");
+    }
+
+    // The submitter fronted the rent, so it goes back to them, not the treasury
+    const treasuryBefore = await provider.connection.getBalance(treasuryPDA);
+    await expire(ephemeral, provider.wallet.publicKey);
+
+    assert.isNull(await provider.connection.getAccountInfo(ephemeral), "Expired submission should be closed");
+    assert.equal(await provider.connection.getBalance(treasuryPDA), treasuryBefore);
+  });
+
+  it("Leaves a tombstone for an expired submission on request", async () => {
+    const org = await program.account.organization.fetch(orgPDA);
+    const id = org.submissionCount;
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [tombstonePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("tombstone"), Buffer.from("submission"), orgPDA.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .submitTip(arweave("tombstoneTipArweaveHash12345678901234567890"), contentHash, new anchor.BN(1), 0, null)
+      .accounts({
+        submission: submissionPDA,
+        config: configPDA,
+        organization: orgPDA,
+        treasury: treasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(orgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const expire = (tombstone: anchor.web3.PublicKey | null) =>
+      program.methods
+        .expireSubmission(true)
+        .accounts({
+          submission: submissionPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          rentRecipient: provider.wallet.publicKey,
+          tombstone,
+          caller: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    try {
+      await expire(null);
+      assert.fail("Should have rejected a tombstone flag without the account");
+    } catch (err) {
+      assert.include(String(err), "TombstoneMismatch");
+    }
+
+    await expire(tombstonePDA);
+
+    assert.isNull(await provider.connection.getAccountInfo(submissionPDA), "Expired submission should be closed");
+    const tombstone = await program.account.tombstone.fetch(tombstonePDA);
+    assert.equal(tombstone.id.toNumber(), id.toNumber());
+    assert.deepEqual(tombstone.kind, { submission: {} });
+    assert.isAbove(tombstone.closedAt.toNumber(), 0);
+  });
+
+  it("Funds a bounty pool and pays a submitter from it", async () => {
+    const [vaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bounty"), orgPDA.toBuffer()],
+      program.programId
+    );
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const pay = (amount: number) =>
+      program.methods
+        .payBounty(new anchor.BN(amount))
+        .accounts({
+          organization: orgPDA,
+          vault: vaultPDA,
+          submission: submissionPDA,
+          submitter: provider.wallet.publicKey,
+          admin: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    await program.methods
+      .fundBounty(new anchor.BN(10_000_000))
+      .accounts({
+        organization: orgPDA,
+        vault: vaultPDA,
+        funder: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await pay(10_000_001);
+      assert.fail("Should have rejected paying more than the pool holds");
+    } catch (err) {
+      assert.include(String(err), "InsufficientBounty");
+    }
+
+    const vaultBefore = await provider.connection.getBalance(vaultPDA);
+    await pay(4_000_000);
+    const vaultAfter = await provider.connection.getBalance(vaultPDA);
+    assert.equal(vaultBefore - vaultAfter, 4_000_000);
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.totalPaid.toNumber(), 4_000_000);
+  });
+
+  it("Returns organization stats from a view", async () => {
+    const stats = await program.methods
+      .getOrgStats()
+      .accounts({ organization: orgPDA })
+      .view();
+    const org = await program.account.organization.fetch(orgPDA);
+
+    assert.equal(stats.submissionCount.toNumber(), org.submissionCount.toNumber());
+    assert.equal(stats.createdAt.toNumber(), org.createdAt.toNumber());
+    assert.isTrue(stats.active);
+    assert.isFalse(stats.verified);
+  });
+
+  it("Only accepts tips in the org's enabled categories", async () => {
+    const setCategories = (mask: number[]) =>
+      program.methods
+        .updateCategories(mask)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    const tip = async (category: number) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .submitTip(arweave("categorizedTipArweaveHash1234567890123456789"), contentHash, null, category, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return submissionPDA;
+    };
+
+    // Only category 1 (e.g. "safety")
+    await setCategories([0b10, 0, 0, 0, 0, 0, 0, 0]);
+    try {
+      await tip(0);
+      assert.fail("Should have rejected a disabled category");
+    } catch (err) {
+      assert.include(String(err), "CategoryNotAccepted");
+    }
+
+    const sub = await program.account.submission.fetch(await tip(1));
+    assert.equal(sub.category, 1);
+
+    await setCategories(Array(8).fill(0xff));
+  });
+
+  it("Requires a proof reference on tips when the org opts in", async () => {
+    const setRequireProof = (requireProof: boolean) =>
+      program.methods
+        .setRequireProof(requireProof)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    const [evidencePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), ...proofSeeds(hash)],
+      program.programId
+    );
+    const tip = async (proof: anchor.web3.PublicKey | null) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .submitTip(arweave("notarizedTipArweaveHash123456789012345678901"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          proof,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return submissionPDA;
+    };
+
+    await setRequireProof(true);
+    try {
+      await tip(null);
+      assert.fail("Should have rejected a tip without a proof");
+    } catch (err) {
+      assert.include(String(err), "ProofRequired");
+    }
+
+    const sub = await program.account.submission.fetch(await tip(evidencePDA));
+    assert.ok(sub.proof.equals(evidencePDA));
+
+    // The other tip paths take the proof as well
+    const org = await program.account.organization.fetch(orgPDA);
+    const [zcPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .submitTipZc(arweave("notarizedZcTipArweaveHash12345678901234567"), 0, null)
+      .accounts({
+        submission: zcPDA,
+        config: configPDA,
+        organization: orgPDA,
+        treasury: treasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(orgPDA),
+        submitter: provider.wallet.publicKey,
+        proof: evidencePDA,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    assert.ok((await program.account.submissionZc.fetch(zcPDA)).organization.equals(orgPDA));
+
+    await setRequireProof(false);
+  });
+
+  it("Rejects tips that skip the org's validator program", async () => {
+    const setValidatorProgram = (validatorProgram: anchor.web3.PublicKey | null) =>
+      program.methods
+        .setValidatorProgram(validatorProgram)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    const validator = anchor.web3.Keypair.generate().publicKey;
+
+    await setValidatorProgram(validator);
+    let org = await program.account.organization.fetch(orgPDA);
+    assert.ok(org.validatorProgram.equals(validator));
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .submitTip(arweave("validatedTipArweaveHash12345678901234567890"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have required the validator program");
+    } catch (err) {
+      assert.include(String(err), "ValidatorRequired");
+    }
+
+    // The other tip paths run the validator too
+    try {
+      await program.methods
+        .submitTipZc(arweave("validatedZcTipArweaveHash1234567890123456"), 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have required the validator program");
+    } catch (err) {
+      assert.include(String(err), "ValidatorRequired");
+    }
+
+    await setValidatorProgram(null);
+    org = await program.account.organization.fetch(orgPDA);
+    assert.isNull(org.validatorProgram);
+  });
+
+  it("Rejects a storage locator the org has already received", async () => {
+    const setDedup = (dedup: boolean) =>
+      program.methods
+        .setDedup(dedup)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    const locator = "dedupTipArweaveHash123456789012345678901234";
+    const [dedupPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("subdedup"), orgPDA.toBuffer(), createHash("sha256").update(locator).digest()],
+      program.programId
+    );
+    const tip = async (submissionDedup: anchor.web3.PublicKey | null) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .submitTip(arweave(locator), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          submissionDedup,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return submissionPDA;
+    };
+
+    await setDedup(true);
+    try {
+      await tip(null);
+      assert.fail("Should have required the dedup record");
+    } catch (err) {
+      assert.include(String(err), "DedupRecordRequired");
+    }
+
+    await tip(dedupPDA);
+    const record = await program.account.submissionDedup.fetch(dedupPDA);
+    assert.ok(record.organization.equals(orgPDA));
+
+    try {
+      await tip(dedupPDA);
+      assert.fail("Should have rejected the repeated locator");
+    } catch (err) {
+      assert.include(String(err), "DuplicateSubmission");
+    }
+
+    // The record is shared by every tip path
+    const org = await program.account.organization.fetch(orgPDA);
+    const [zcPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .submitTipZc(arweave(locator), 0, null)
+        .accounts({
+          submission: zcPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          submissionDedup: dedupPDA,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected the repeated locator");
+    } catch (err) {
+      assert.include(String(err), "DuplicateSubmission");
+    }
+
+    await setDedup(false);
+  });
+
+  it("Sizes submissions to the org's locator limit", async () => {
+    const slug = "short-id-org";
+    const [shortOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(slug)],
+      program.programId
+    );
+    const [shortTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), shortOrgPDA.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .createOrganization(slug, "Short ID Org", "Arweave ids only", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 43)
+      .accounts({
+        organization: shortOrgPDA,
+        treasury: shortTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    assert.equal((await program.account.organization.fetch(shortOrgPDA)).maxHashLen, 43);
+
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), shortOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const accounts = {
+      submission: submissionPDA,
+      config: configPDA,
+      organization: shortOrgPDA,
+      treasury: shortTreasuryPDA,
+      globalIndex: globalIndexPDA,
+      submitterState: submitterStatePDA(shortOrgPDA),
+      submitter: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    const tip = (locator: string) =>
+      program.methods
+        .submitTip(arweave(locator), contentHash, null, 0, null)
+        .accounts(accounts)
+        .rpc();
+
+    try {
+      await tip("x".repeat(44));
+      assert.fail("Should have rejected a locator over the org's limit");
+    } catch (err) {
+      assert.include(String(err), "LocatorExceedsOrgLimit");
+    }
+
+    try {
+      await program.methods.submitTipZc(arweave("x".repeat(44)), 0, null).accounts(accounts).rpc();
+      assert.fail("Should have rejected a zero-copy locator over the org's limit");
+    } catch (err) {
+      assert.include(String(err), "LocatorExceedsOrgLimit");
+    }
+
+    await tip("shortIdTipArweaveHash1234567890123456789012");
+    const info = await provider.connection.getAccountInfo(submissionPDA);
+    assert.equal(info.data.length, 380 - 128 + 43);
+  });
+
+  it("Throttles an org to its tip rate limit", async () => {
+    const setRateLimit = (windowSecs: number, windowLimit: number) =>
+      program.methods
+        .updateRateLimit(windowSecs, windowLimit)
+        .accounts({ organization: orgPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    const tip = async () => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .submitTip(arweave("rateLimitedTipArweaveHash1234567890123456789"), contentHash, null, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: submitterStatePDA(orgPDA),
+          submitter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    };
+
+    try {
+      await setRateLimit(0, 1);
+      assert.fail("Should have rejected a limit without a window");
+    } catch (err) {
+      assert.include(String(err), "InvalidRateLimit");
+    }
+
+    // One tip per hour, org-wide
+    await setRateLimit(3600, 1);
+    await tip();
+    try {
+      await tip();
+      assert.fail("Should have rejected a second tip inside the window");
+    } catch (err) {
+      assert.include(String(err), "RateLimitExceeded");
+    }
+
+    const org = await program.account.organization.fetch(orgPDA);
+    assert.equal(org.windowCount, 1);
+
+    await setRateLimit(0, 0);
+  });
+
+  it("Records the source, not the relayer, on a delegated tip", async () => {
+    const source = anchor.web3.Keypair.generate();
+    const storage = arweave("delegatedTipArweaveHash123456789012345678901");
+    const signed = (message: Buffer) =>
+      anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: source.secretKey,
+        message,
+      });
+    const [sourceStatePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submitter"), orgPDA.toBuffer(), source.publicKey.toBuffer()],
+      program.programId
+    );
+    // org || next submission ID (u64 LE) || storage kind || category || content hash || proof || locator
+    const delegateMessage = (id: anchor.BN, category: number) =>
+      Buffer.concat([
+        orgPDA.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+        Buffer.from([0 /* arweave */, category]),
+        Buffer.from(contentHash),
+        Buffer.alloc(32) /* no proof */,
+        Buffer.from(storage.locator),
+      ]);
+    const nextId = async () => (await program.account.organization.fetch(orgPDA)).submissionCount;
+    const submit = async (ed25519Ix: anchor.web3.TransactionInstruction) => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      // Single-signature layout: 16-byte header, 32-byte pubkey, 64-byte signature
+      const signature = ed25519Ix.data.subarray(48, 112);
+      await program.methods
+        .submitTipDelegated(storage, contentHash, 0, [...signature], [...source.publicKey.toBytes()], null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          submitterState: sourceStatePDA,
+          relayer: provider.wallet.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([ed25519Ix])
+        .rpc();
+      return submissionPDA;
+    };
+
+    // The signature must also cover the content hash, or the relayer could swap the payload
+    try {
+      await submit(signed(Buffer.concat([orgPDA.toBuffer(), Buffer.from(storage.locator)])));
+      assert.fail("Should have rejected a signature over the wrong message");
+    } catch (err) {
+      assert.include(String(err), "InvalidDelegateSignature");
+    }
+
+    // Every submitted field is signed, so the relayer can't change the category
+    try {
+      await submit(signed(delegateMessage(await nextId(), 1)));
+      assert.fail("Should have rejected a tampered category");
+    } catch (err) {
+      assert.include(String(err), "InvalidDelegateSignature");
+    }
+
+    const ed25519Ix = signed(delegateMessage(await nextId(), 0));
+    const submissionPDA = await submit(ed25519Ix);
+    const sub = await program.account.submission.fetch(submissionPDA);
+    assert.ok(sub.submitter.equals(source.publicKey));
+    assert.isTrue(sub.viaRelay);
+
+    // The signed ID has been used, so the same signature can't land a second tip
+    try {
+      await submit(ed25519Ix);
+      assert.fail("Should have rejected a replayed signature");
+    } catch (err) {
+      assert.include(String(err), "InvalidDelegateSignature");
+    }
+  });
+
+  it("Lets only the protocol authority verify an organization", async () => {
+    const impostor = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .verifyOrganization(true)
+        .accounts({ organization: orgPDA, config: configPDA, authority: impostor.publicKey })
+        .signers([impostor])
+        .rpc();
+      assert.fail("Should have rejected a non-authority signer");
+    } catch (err) {
+      assert.include(String(err), "ConstraintHasOne");
+    }
+
+    await program.methods
+      .verifyOrganization(true)
+      .accounts({ organization: orgPDA, config: configPDA, authority: provider.wallet.publicKey })
+      .rpc();
+    let org = await program.account.organization.fetch(orgPDA);
+    assert.isTrue(org.verified);
+
+    await program.methods
+      .verifyOrganization(false)
+      .accounts({ organization: orgPDA, config: configPDA, authority: provider.wallet.publicKey })
+      .rpc();
+    org = await program.account.organization.fetch(orgPDA);
+    assert.isFalse(org.verified);
+  });
+
+  it("Sponsors a tip from a verified org's pool for a submitter with no SOL", async () => {
+    const setRelayer = (relayer: anchor.web3.PublicKey) =>
+      program.methods
+        .setRelayer(relayer)
+        .accounts({ config: configPDA, authority: provider.wallet.publicKey })
+        .rpc();
+    const verify = (verified: boolean) =>
+      program.methods
+        .verifyOrganization(verified)
+        .accounts({ organization: orgPDA, config: configPDA, authority: provider.wallet.publicKey })
+        .rpc();
+    const setCap = (cap: number) =>
+      program.methods
+        .setSponsorCap(new anchor.BN(cap))
+        .accounts({ organization: orgPDA, sponsor: sponsorPDA, admin: provider.wallet.publicKey })
+        .rpc();
+    const [sponsorPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsor"), orgPDA.toBuffer()],
+      program.programId
+    );
+    const submitter = anchor.web3.Keypair.generate();
+    const tip = async () => {
+      const org = await program.account.organization.fetch(orgPDA);
+      const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [submitterState] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("submitter"), orgPDA.toBuffer(), submitter.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .submitTipSponsored(arweave("sponsoredTipArweaveHash12345678901234567890"), contentHash, 0, null)
+        .accounts({
+          submission: submissionPDA,
+          config: configPDA,
+          organization: orgPDA,
+          treasury: treasuryPDA,
+          globalIndex: globalIndexPDA,
+          sponsor: sponsorPDA,
+          submitterState,
+          allowlist: null,
+          submitter: submitter.publicKey,
+          relayer: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([submitter])
+        .rpc();
+      return submissionPDA;
+    };
+
+    await setRelayer(provider.wallet.publicKey);
+    await program.methods
+      .fundSponsorship(new anchor.BN(100_000_000))
+      .accounts({
+        organization: orgPDA,
+        sponsor: sponsorPDA,
+        funder: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await tip();
+      assert.fail("Should have rejected a sponsored tip to an unverified org");
+    } catch (err) {
+      assert.include(String(err), "OrgNotVerified");
+    }
+
+    await verify(true);
+    const sub = await program.account.submission.fetch(await tip());
+    assert.ok(sub.submitter.equals(submitter.publicKey));
+    assert.isTrue(sub.viaRelay);
+    assert.equal(await provider.connection.getBalance(submitter.publicKey), 0);
+
+    const sponsor = await program.account.sponsorVault.fetch(sponsorPDA);
+    assert.isAbove(sponsor.spent.toNumber(), 0);
+
+    await setCap(sponsor.spent.toNumber());
+    try {
+      await tip();
+      assert.fail("Should have rejected a tip past the sponsorship cap");
+    } catch (err) {
+      assert.include(String(err), "SponsorCapReached");
+    }
+
+    await setCap(0);
+    await verify(false);
+    await setRelayer(anchor.web3.PublicKey.default);
+  });
+
+  it("Files one abuse report per wallet per org or submission", async () => {
+    const reportPDA = (target: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("report"), target.toBuffer(), provider.wallet.publicKey.toBuffer()],
+        program.programId
+      )[0];
+
+    const orgReport = reportPDA(orgPDA);
+    await program.methods
+      .reportOrganization(2)
+      .accounts({
+        report: orgReport,
+        organization: orgPDA,
+        reporter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const report = await program.account.report.fetch(orgReport);
+    assert.ok(report.target.equals(orgPDA));
+    assert.ok(report.reporter.equals(provider.wallet.publicKey));
+    assert.equal(report.reasonCode, 2);
+
+    try {
+      await program.methods
+        .reportOrganization(3)
+        .accounts({
+          report: orgReport,
+          organization: orgPDA,
+          reporter: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a second report from the same wallet");
+    } catch (err) {
+      assert.include(String(err), "already in use");
+    }
+
+    const org = await program.account.organization.fetch(orgPDA);
+    const [submissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), orgPDA.toBuffer(), org.submissionCount.subn(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const subReport = reportPDA(submissionPDA);
+    await program.methods
+      .reportSubmission(1)
+      .accounts({
+        report: subReport,
+        submission: submissionPDA,
+        reporter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    assert.ok((await program.account.report.fetch(subReport)).target.equals(submissionPDA));
+  });
+
+  it("Purges a deactivated org once its submissions are closed", async () => {
+    const slug = "purge-org";
+    const [purgeOrgPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), Buffer.from(slug)],
+      program.programId
+    );
+    const [purgeTreasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), purgeOrgPDA.toBuffer()],
+      program.programId
+    );
+    const [purgeSubmissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), purgeOrgPDA.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .createOrganization(slug, "Purge Org", "Short-lived", [[...fakeEncryptionKey]], p256, new anchor.BN(0), 0, new anchor.BN(0), new anchor.BN(0), 0, 0)
+      .accounts({
+        organization: purgeOrgPDA,
+        treasury: purgeTreasuryPDA,
+        orgRegistry: orgRegistryPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .submitTip(arweave("purgedTipArweaveHash123456789012345678901234"), contentHash, null, 0, null)
+      .accounts({
+        submission: purgeSubmissionPDA,
+        config: configPDA,
+        organization: purgeOrgPDA,
+        treasury: purgeTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: submitterStatePDA(purgeOrgPDA),
+        submitter: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // A second source fronts the rent of its own tip, and gets it back on purge
+    const source = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: source.publicKey,
+          lamports: 20_000_000,
+        })
+      )
+    );
+    const [sourceSubmissionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), purgeOrgPDA.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .submitTip(arweave("purgedSourceTipArweaveHash12345678901234567"), contentHash, null, 0, null)
+      .accounts({
+        submission: sourceSubmissionPDA,
+        config: configPDA,
+        organization: purgeOrgPDA,
+        treasury: purgeTreasuryPDA,
+        globalIndex: globalIndexPDA,
+        submitterState: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("submitter"), purgeOrgPDA.toBuffer(), source.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        submitter: source.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([source])
+      .rpc();
+
+    const purge = (accounts: anchor.web3.PublicKey[]) =>
+      program.methods
+        .purgeOrganization()
+        .accounts({
+          organization: purgeOrgPDA,
+          treasury: purgeTreasuryPDA,
+          vault: null,
+          admin: provider.wallet.publicKey,
+        })
+        .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .rpc();
+
+    try {
+      await purge([purgeSubmissionPDA]);
+      assert.fail("Should have rejected purging an active org");
+    } catch (err) {
+      assert.include(String(err), "OrgStillActive");
+    }
+
+    await program.methods
+      .deactivateOrganization()
+      .accounts({ organization: purgeOrgPDA, member: null, authority: provider.wallet.publicKey })
+      .rpc();
+
+    try {
+      await purge([]);
+      assert.fail("Should have rejected purging with a submission still open");
+    } catch (err) {
+      assert.include(String(err), "SubmissionsRemaining");
+    }
+
+    try {
+      await purge([purgeSubmissionPDA, sourceSubmissionPDA]);
+      assert.fail("Should have required the source's wallet for its rent");
+    } catch (err) {
+      assert.include(String(err), "WrongRentRecipient");
+    }
+
+    const sourceRent = (await provider.connection.getAccountInfo(sourceSubmissionPDA)).lamports;
+    const before = await provider.connection.getBalance(source.publicKey);
+    await purge([purgeSubmissionPDA, sourceSubmissionPDA, source.publicKey]);
+    assert.equal(await provider.connection.getBalance(source.publicKey), before + sourceRent);
+    for (const closed of [purgeOrgPDA, purgeTreasuryPDA, purgeSubmissionPDA, sourceSubmissionPDA]) {
+      assert.isNull(await provider.connection.getAccountInfo(closed));
+    }
+  });
+
+  // ─── VOID BURN TESTS ───────────────────────────────────
+
+  const inboxKey = p256Key();
+
+  const [inboxPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("inbox"), provider.wallet.publicKey.toBuffer()],
+    program.programId
+  );
+
+  // Second wallet that sends DMs to the provider wallet's inbox
+  const stranger = anchor.web3.Keypair.generate();
+
+  const messagePDA = (id: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dm"), provider.wallet.publicKey.toBuffer(), new anchor.BN(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  // Messages must be encrypted to (and declare) the inbox's current key version
+  const inboxKeyVersion = async (inbox: anchor.web3.PublicKey) =>
+    (await program.account.inbox.fetch(inbox)).keyVersion;
+
+  const blockPDA = (owner: anchor.web3.PublicKey, sender: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("block"), owner.toBuffer(), sender.toBuffer()],
+      program.programId
+    )[0];
+
+  it("Activates an inbox", async () => {
+    await program.methods
+      .activateInbox([...inboxKey], p256, false)
+      .accounts({
+        inbox: inboxPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const inbox = await program.account.inbox.fetch(inboxPDA);
+    assert.equal(inbox.owner.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.equal(inbox.keyVersion, 0);
+    assert.equal(inbox.messageCount.toNumber(), 0);
+  });
+
+  it("Rotates an inbox's encryption key", async () => {
+    const newKey = p256Key();
+
+    await program.methods
+      .rotateInboxKey([...newKey])
+      .accounts({
+        inbox: inboxPDA,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const inbox = await program.account.inbox.fetch(inboxPDA);
+    assert.deepEqual(Buffer.from(inbox.encryptionKey), Buffer.from(newKey));
+    assert.equal(inbox.keyVersion, 1);
+    assert.isAbove(inbox.keyRotatedAt.toNumber(), 0);
+  });
+
+  it("Migrates a schema 1 inbox to the current layout", async () => {
+    // The fixture in tests/fixtures/inbox-v1.json predates message fees and allowlists
+    const [legacyInboxPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox"), legacyAdmin.publicKey.toBuffer()],
+      program.programId
+    );
+    const migrate = () =>
+      program.methods
+        .migrateInbox()
+        .accounts({
+          inbox: legacyInboxPDA,
+          owner: legacyAdmin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([legacyAdmin])
+        .rpc();
+
+    let info = await provider.connection.getAccountInfo(legacyInboxPDA);
+    assert.equal(info.data.length, 144);
+
+    await migrate();
+    info = await provider.connection.getAccountInfo(legacyInboxPDA);
+    assert.equal(info.data.length, 153);
+    const inbox = await program.account.inbox.fetch(legacyInboxPDA);
+    assert.equal(inbox.schemaVersion, CURRENT_SCHEMA);
+    assert.equal(inbox.messageCount.toNumber(), 2);
+    assert.equal(inbox.messageFee.toNumber(), 0);
+    assert.isFalse(inbox.allowlistOnly);
+    assert.isTrue(inbox.active);
+
+    // The upgraded inbox loads like any other
+    await program.methods
+      .rotateInboxKey([...fakeEncryptionKey])
+      .accounts({ inbox: legacyInboxPDA, owner: legacyAdmin.publicKey })
+      .signers([legacyAdmin])
+      .rpc();
+    assert.equal((await program.account.inbox.fetch(legacyInboxPDA)).keyVersion, 1);
+
+    // Already current: a second call changes nothing
+    await migrate();
+    info = await provider.connection.getAccountInfo(legacyInboxPDA);
+    assert.equal(info.data.length, 153);
+  });
+
+  it("Blocks and unblocks a sender", async () => {
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: stranger.publicKey,
+          lamports: 50_000_000,
+        })
+      )
+    );
+
+    const send = () =>
+      program.methods
+        .sendDirectMessage(arweave("strangerDmArweaveHash123456789012345678901"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(0),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+    await program.methods
+      .blockSender(stranger.publicKey)
+      .accounts({
+        block: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await send();
+      assert.fail("Should have rejected a blocked sender");
+    } catch (err) {
+      assert.include(String(err), "SenderBlocked");
+    }
+
+    await program.methods
+      .unblockSender()
+      .accounts({
+        block: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await send();
+    const msg = await program.account.directMessage.fetch(messagePDA(0));
+    assert.equal(msg.sender.toBase58(), stranger.publicKey.toBase58());
+  });
+
+  it("Lets anyone burn a message after its expiry", async () => {
+    // Expiry in the past, so the message is burnable immediately
+    await program.methods
+      .sendDirectMessage(arweave("expiringDmArweaveHash123456789012345678901"), contentHash, await inboxKeyVersion(inboxPDA), false, new anchor.BN(1), null, null, null, null)
+      .accounts({
+        message: messagePDA(1),
+        config: configPDA,
+        recipientInbox: inboxPDA,
+        senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+        senderStats: null,
+        recipient: null,
+        senderAllow: null,
+        sender: stranger.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([stranger])
+      .rpc();
+
+    try {
+      await program.methods
+        .burnExpiredMessage()
+        .accounts({
+          message: messagePDA(0),
+          sender: stranger.publicKey,
+          caller: provider.wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("Should have rejected a message without expiry");
+    } catch (err) {
+      assert.include(String(err), "NoExpiry");
+    }
+
+    await program.methods
+      .burnExpiredMessage()
+      .accounts({
+        message: messagePDA(1),
+        sender: stranger.publicKey,
+        caller: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(messagePDA(1));
+    assert.isNull(closed, "Expired message should be closed");
+  });
+
+  it("Closes a burned message", async () => {
+    try {
+      await program.methods
+        .closeMessage(false)
+        .accounts({
+          message: messagePDA(0),
+          rentRecipient: stranger.publicKey,
+          tombstone: null,
+          recipient: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected closing an unburned message");
+    } catch (err) {
+      assert.include(String(err), "MessageNotBurned");
+    }
+
+    await program.methods
+      .burnMessage()
+      .accounts({ message: messagePDA(0), recipient: provider.wallet.publicKey })
+      .rpc();
+
+    // Opt into a tombstone so the closed message stays provably "once existed"
+    const [tombstonePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("tombstone"), Buffer.from("dm"), provider.wallet.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .closeMessage(true)
+      .accounts({
+        message: messagePDA(0),
+        rentRecipient: stranger.publicKey,
+        tombstone: tombstonePDA,
+        recipient: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(messagePDA(0));
+    assert.isNull(closed, "Burned message should be closed");
+
+    const tombstone = await program.account.tombstone.fetch(tombstonePDA);
+    assert.equal(tombstone.id.toNumber(), 0);
+    assert.deepEqual(tombstone.kind, { message: {} });
+
+    const inbox = await program.account.inbox.fetch(inboxPDA);
+    assert.equal(inbox.messageCount.toNumber(), 2);
+  });
+
+  it("Deactivates and closes an inbox", async () => {
+    const [strangerInboxPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox"), stranger.publicKey.toBuffer()],
+      program.programId
+    );
+    const [strangerMessagePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dm"), stranger.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .activateInbox([...inboxKey], p256, false)
+      .accounts({
+        inbox: strangerInboxPDA,
+        owner: stranger.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([stranger])
+      .rpc();
+
+    await program.methods
+      .deactivateInbox()
+      .accounts({ inbox: strangerInboxPDA, owner: stranger.publicKey })
+      .signers([stranger])
+      .rpc();
+
+    try {
+      await program.methods
+        .sendDirectMessage(arweave("deadInboxArweaveHash1234567890123456789012"), contentHash, await inboxKeyVersion(strangerInboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: strangerMessagePDA,
+          config: configPDA,
+          recipientInbox: strangerInboxPDA,
+          senderBlock: blockPDA(stranger.publicKey, provider.wallet.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a message to an inactive inbox");
+    } catch (err) {
+      assert.include(String(err), "InboxInactive");
+    }
+
+    await program.methods
+      .closeInbox()
+      .accounts({ inbox: strangerInboxPDA, owner: stranger.publicKey })
+      .signers([stranger])
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(strangerInboxPDA);
+    assert.isNull(closed, "Inbox should be closed");
+  });
+
+  it("Records a read receipt on a labelled message", async () => {
+    const send = (label: string, attachments: number) =>
+      program.methods
+        .sendDirectMessage(arweave("readReceiptArweaveHash12345678901234567890"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, label, new anchor.BN(2048), attachments)
+        .accounts({
+          message: messagePDA(2),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+    try {
+      await send("x".repeat(33), 2);
+      assert.fail("Should have rejected a label over 32 chars");
+    } catch (err) {
+      assert.include(String(err), "LabelTooLong");
+    }
+
+    try {
+      await send("Follow-up on leak", 17);
+      assert.fail("Should have rejected more than 16 attachments");
+    } catch (err) {
+      assert.include(String(err), "TooManyAttachments");
+    }
+
+    await send("Follow-up on leak", 2);
+
+    let msg = await program.account.directMessage.fetch(messagePDA(2));
+    assert.equal(msg.label, "Follow-up on leak");
+    assert.equal(msg.contentBytes.toNumber(), 2048);
+    assert.equal(msg.attachmentCount, 2);
+    assert.isNull(msg.readAt);
+
+    await program.methods
+      .markRead()
+      .accounts({ message: messagePDA(2), sender: stranger.publicKey, recipient: provider.wallet.publicKey })
+      .rpc();
+
+    msg = await program.account.directMessage.fetch(messagePDA(2));
+    assert.isAbove(msg.readAt.toNumber(), 0);
+  });
+
+  it("Threads a reply under an earlier message", async () => {
+    const send = (id: number, parentId: anchor.BN) =>
+      program.methods
+        .sendDirectMessage(arweave("threadedDmArweaveHash1234567890123456789012"), contentHash, await inboxKeyVersion(inboxPDA), false, null, parentId, null, null, null)
+        .accounts({
+          message: messagePDA(id),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+    try {
+      await send(3, new anchor.BN(3));
+      assert.fail("Should have rejected a dangling parent");
+    } catch (err) {
+      assert.include(String(err), "InvalidParent");
+    }
+
+    await send(3, new anchor.BN(2));
+    const msg = await program.account.directMessage.fetch(messagePDA(3));
+    assert.equal(msg.parentId.toNumber(), 2);
+  });
+
+  it("Recalls an unread message but not a read one", async () => {
+    try {
+      await program.methods
+        .recallMessage()
+        .accounts({ message: messagePDA(2), sender: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Should have rejected recalling a read message");
+    } catch (err) {
+      assert.include(String(err), "AlreadyRead");
+    }
+
+    await program.methods
+      .recallMessage()
+      .accounts({ message: messagePDA(3), sender: stranger.publicKey })
+      .signers([stranger])
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(messagePDA(3));
+    assert.isNull(closed, "Recalled message should be closed");
+  });
+
+  it("Escrows a message stake until read or burned", async () => {
+    const stake = 1_000_000;
+    const setStake = (minStake: number) =>
+      program.methods
+        .setMinStake(new anchor.BN(minStake))
+        .accounts({ inbox: inboxPDA, owner: provider.wallet.publicKey })
+        .rpc();
+    const send = (id: number) =>
+      program.methods
+        .sendDirectMessage(arweave("stakedDmArweaveHash12345678901234567890123"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(id),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+    await setStake(100 * anchor.web3.LAMPORTS_PER_SOL);
+    try {
+      await send(4);
+      assert.fail("Should have rejected a sender who cannot cover the stake");
+    } catch (err) {
+      assert.include(String(err), "InsufficientStake");
+    }
+
+    await setStake(stake);
+
+    // Read: stake goes back to the sender
+    await send(4);
+    let msg = await program.account.directMessage.fetch(messagePDA(4));
+    assert.equal(msg.stake.toNumber(), stake);
+
+    const senderBefore = await provider.connection.getBalance(stranger.publicKey);
+    await program.methods
+      .markRead()
+      .accounts({ message: messagePDA(4), sender: stranger.publicKey, recipient: provider.wallet.publicKey })
+      .rpc();
+    const senderAfter = await provider.connection.getBalance(stranger.publicKey);
+    assert.equal(senderAfter - senderBefore, stake);
+    msg = await program.account.directMessage.fetch(messagePDA(4));
+    assert.equal(msg.stake.toNumber(), 0);
+
+    // Burn as spam: stake goes to the recipient
+    await send(5);
+    const messageBefore = await provider.connection.getBalance(messagePDA(5));
+    await program.methods
+      .burnMessage()
+      .accounts({ message: messagePDA(5), recipient: provider.wallet.publicKey })
+      .rpc();
+    const messageAfter = await provider.connection.getBalance(messagePDA(5));
+    assert.equal(messageBefore - messageAfter, stake);
+
+    await setStake(0);
+  });
+
+  it("Migrates a schema 1 message without stranding its stake", async () => {
+    // The fixture in tests/fixtures/message-v1.json is message 1 of the legacy
+    // inbox, with 5_000_000 lamports of stake escrowed on top of its rent
+    const stake = 5_000_000;
+    const legacyMessagePDA = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dm"), legacyAdmin.publicKey.toBuffer(), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+    const migrate = () =>
+      program.methods
+        .migrateMessage()
+        .accounts({
+          message: legacyMessagePDA,
+          payer: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    let info = await provider.connection.getAccountInfo(legacyMessagePDA);
+    assert.equal(info.data.length, 341);
+
+    await migrate();
+    info = await provider.connection.getAccountInfo(legacyMessagePDA);
+    assert.equal(info.data.length, 440);
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(440);
+    assert.equal(info.lamports, rent + stake);
+    const msg = await program.account.directMessage.fetch(legacyMessagePDA);
+    assert.equal(msg.schemaVersion, CURRENT_SCHEMA);
+    assert.equal(msg.label, "Schema 1");
+    assert.equal(msg.stake.toNumber(), stake);
+    assert.isNull(msg.rentRecipient);
+    assert.isNull(msg.ephemeralKey);
+
+    // Already current: a second call changes nothing
+    await migrate();
+    assert.equal((await provider.connection.getAccountInfo(legacyMessagePDA)).lamports, rent + stake);
+
+    // The stake can be released again: burning pays it to the recipient
+    const before = await provider.connection.getBalance(legacyAdmin.publicKey);
+    await program.methods
+      .burnMessage()
+      .accounts({ message: legacyMessagePDA, recipient: legacyAdmin.publicKey })
+      .signers([legacyAdmin])
+      .rpc();
+    assert.equal(await provider.connection.getBalance(legacyAdmin.publicKey), before + stake);
+  });
+
+  it("Burns a batch of messages, skipping ones already burned", async () => {
+    for (const id of [6, 7]) {
+      await program.methods
+        .sendDirectMessage(arweave("batchBurnArweaveHash1234567890123456789012"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(id),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+    }
+
+    const burn = (ids: number[]) =>
+      program.methods
+        .burnMessages()
+        .accounts({ recipient: provider.wallet.publicKey })
+        .remainingAccounts(ids.map((id) => ({ pubkey: messagePDA(id), isWritable: true, isSigner: false })))
+        .rpc();
+
+    try {
+      await burn(Array(16).fill(6));
+      assert.fail("Should have rejected more than 15 messages");
+    } catch (err) {
+      assert.include(String(err), "TooManyMessages");
+    }
+
+    // Message 5 was already burned as spam and is skipped
+    await burn([5, 6, 7]);
+    for (const id of [5, 6, 7]) {
+      const msg = await program.account.directMessage.fetch(messagePDA(id));
+      assert.isTrue(msg.burned);
+    }
+  });
+
+  it("Pages through an inbox with a verified message window", async () => {
+    const window = (start: number, ids: number[]) =>
+      program.methods
+        .getMessageWindow(new anchor.BN(start), ids.length)
+        .accounts({ inbox: inboxPDA })
+        .remainingAccounts(ids.map((id) => ({ pubkey: messagePDA(id), isWritable: false, isSigner: false })))
+        .view();
+
+    // Messages 0 and 1 were closed earlier; 5-7 were burned in the last test
+    const page = await window(0, [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert.deepEqual(page.map((m) => m.id.toNumber()), [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert.isNull(page[0].sender);
+    assert.isNull(page[1].sender);
+    assert.ok(page[2].sender.equals(stranger.publicKey));
+    assert.deepEqual(page.slice(5).map((m) => m.burned), [true, true, true]);
+
+    try {
+      await window(2, [2, 4]);
+      assert.fail("Should have rejected a non-contiguous window");
+    } catch (err) {
+      assert.include(String(err), "InvalidMessageWindow");
+    }
+
+    try {
+      await window(7, [7, 8]);
+      assert.fail("Should have rejected a window past message_count");
+    } catch (err) {
+      assert.include(String(err), "InvalidMessageWindow");
+    }
+  });
+
+  it("Rejects a message to the sender's own inbox", async () => {
+    try {
+      await program.methods
+        .sendDirectMessage(arweave("selfDmArweaveHash1234567890123456789012345"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(8),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, provider.wallet.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected messaging yourself");
+    } catch (err) {
+      assert.include(String(err), "CannotMessageSelf");
+    }
+  });
+
+  it("Broadcasts one message to several inboxes", async () => {
+    const listener = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: listener.publicKey,
+          lamports: 10_000_000,
+        })
+      )
+    );
+
+    const inboxOf = (owner: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("inbox"), owner.toBuffer()],
+        program.programId
+      )[0];
+    const firstMessageOf = (owner: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("dm"), owner.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    for (const owner of [stranger, listener]) {
+      await program.methods
+        .activateInbox([...inboxKey], p256, false)
+        .accounts({
+          inbox: inboxOf(owner.publicKey),
+          owner: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    }
+
+    const recipient = (owner: anchor.web3.PublicKey, message: anchor.web3.PublicKey) => [
+      { pubkey: inboxOf(owner), isWritable: true, isSigner: false },
+      { pubkey: message, isWritable: true, isSigner: false },
+      { pubkey: blockPDA(owner, provider.wallet.publicKey), isWritable: false, isSigner: false },
+    ];
+    const broadcast = (remaining: anchor.web3.AccountMeta[]) =>
+      program.methods
+        .sendBroadcast(arweave("broadcastArweaveHash12345678901234567890123"), contentHash)
+        .accounts({
+          config: configPDA,
+          sender: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(remaining)
+        .rpc();
+
+    const toStranger = recipient(stranger.publicKey, firstMessageOf(stranger.publicKey));
+    const toListener = recipient(listener.publicKey, firstMessageOf(listener.publicKey));
+    try {
+      await broadcast(Array(11).fill(toStranger).flat());
+      assert.fail("Should have rejected an oversized broadcast");
+    } catch (err) {
+      assert.include(String(err), "TooManyRecipients");
+    }
+
+    try {
+      await broadcast([...toStranger, ...recipient(provider.wallet.publicKey, messagePDA(8))]);
+      assert.fail("Should have rejected broadcasting to yourself");
+    } catch (err) {
+      assert.include(String(err), "CannotMessageSelf");
+    }
+
+    // Sending lamports to a message address ahead of time must not block the broadcast
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: firstMessageOf(listener.publicKey),
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(0),
+        })
+      )
+    );
+
+    await broadcast([...toStranger, ...toListener]);
+
+    const theirs = await program.account.directMessage.fetch(firstMessageOf(stranger.publicKey));
+    const others = await program.account.directMessage.fetch(firstMessageOf(listener.publicKey));
+    assert.ok(theirs.recipient.equals(stranger.publicKey));
+    assert.ok(others.recipient.equals(listener.publicKey));
+    assert.equal(theirs.storage.locator, others.storage.locator);
+    assert.deepEqual([...theirs.contentHash], contentHash);
+
+    const strangerInbox = await program.account.inbox.fetch(inboxOf(stranger.publicKey));
+    assert.equal(strangerInbox.messageCount.toNumber(), 1);
+  });
+
+  it("Forces burn-after-reading when the inbox has a default burn policy", async () => {
+    const setDefaultBurn = (defaultBurn: boolean) =>
+      program.methods
+        .setDefaultBurn(defaultBurn)
+        .accounts({ inbox: inboxPDA, owner: provider.wallet.publicKey })
+        .rpc();
+
+    await setDefaultBurn(true);
+    const inbox = await program.account.inbox.fetch(inboxPDA);
+    const id = inbox.messageCount.toNumber();
+
+    // The sender asks to keep the message, but the recipient's policy wins
+    await program.methods
+      .sendDirectMessage(arweave("defaultBurnArweaveHash12345678901234567890"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+      .accounts({
+        message: messagePDA(id),
+        config: configPDA,
+        recipientInbox: inboxPDA,
+        senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+        senderStats: null,
+        recipient: null,
+        senderAllow: null,
+        sender: stranger.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([stranger])
+      .rpc();
+    let msg = await program.account.directMessage.fetch(messagePDA(id));
+    assert.isTrue(msg.burnAfterReading);
+    assert.isFalse(msg.burned);
+
+    // Reading a burn-after-reading message burns it in the same instruction
+    const readSig = await program.methods
+      .markRead()
+      .accounts({ message: messagePDA(id), sender: stranger.publicKey, recipient: provider.wallet.publicKey })
+      .rpc({ commitment: "confirmed" });
+    msg = await program.account.directMessage.fetch(messagePDA(id));
+    assert.isAbove(msg.readAt.toNumber(), 0);
+    assert.isTrue(msg.burned);
+    const names = (await eventsOf(readSig)).map((event) => event.name);
+    assert.includeMembers(names, ["messageRead", "messageBurned"]);
+
+    await setDefaultBurn(false);
+  });
+
+  it("Counts a sender's messages to an inbox when stats are passed", async () => {
+    const [statsPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sstats"), provider.wallet.publicKey.toBuffer(), stranger.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (let i = 0; i < 2; i++) {
+      const inbox = await program.account.inbox.fetch(inboxPDA);
+      await program.methods
+        .sendDirectMessage(arweave("knownSenderArweaveHash12345678901234567890"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(inbox.messageCount.toNumber()),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: statsPDA,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+    }
+
+    const stats = await program.account.senderStats.fetch(statsPDA);
+    assert.equal(stats.messageCount.toNumber(), 2);
+    assert.isAbove(stats.firstContactAt.toNumber(), 0);
+  });
+
+  it("Pays the inbox's message fee to its owner", async () => {
+    const fee = 2_000_000;
+    const setFee = (messageFee: number) =>
+      program.methods
+        .updateMessageFee(new anchor.BN(messageFee))
+        .accounts({ inbox: inboxPDA, owner: provider.wallet.publicKey })
+        .rpc();
+    const send = async (recipient: anchor.web3.PublicKey | null) => {
+      const inbox = await program.account.inbox.fetch(inboxPDA);
+      await program.methods
+        .sendDirectMessage(arweave("paidMessageArweaveHash123456789012345678901"), contentHash, await inboxKeyVersion(inboxPDA), false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(inbox.messageCount.toNumber()),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+    };
+
+    await setFee(fee);
+    try {
+      await send(null);
+      assert.fail("Should have rejected a message without the fee");
+    } catch (err) {
+      assert.include(String(err), "InsufficientFee");
+    }
+
+    const ownerBefore = await provider.connection.getBalance(provider.wallet.publicKey);
+    await send(provider.wallet.publicKey);
+    const ownerAfter = await provider.connection.getBalance(provider.wallet.publicKey);
+    // The owner's wallet also pays this transaction's signature fees
+    assert.approximately(ownerAfter - ownerBefore, fee, 10_000);
+
+    await setFee(0);
+  });
+
+  it("Rejects a direct message encrypted to a stale key version", async () => {
+    const inbox = await program.account.inbox.fetch(inboxPDA);
+    try {
+      await program.methods
+        .sendDirectMessage(arweave("staleKeyDmArweaveHash123456789012345678901"), contentHash, inbox.keyVersion + 1, false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(inbox.messageCount.toNumber()),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow: null,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Should have rejected a stale key version");
+    } catch (err) {
+      assert.include(String(err), "StaleKeyVersion");
+    }
+  });
+
+  it("Accepts messages only from allowlisted senders when the inbox opts in", async () => {
+    const [allowPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox_allow"), provider.wallet.publicKey.toBuffer(), stranger.publicKey.toBuffer()],
+      program.programId
+    );
+    const setAllowlistOnly = (allowlistOnly: boolean) =>
+      program.methods
+        .setInboxAllowlistOnly(allowlistOnly)
+        .accounts({ inbox: inboxPDA, owner: provider.wallet.publicKey })
+        .rpc();
+    const send = async (senderAllow: anchor.web3.PublicKey | null) => {
+      const inbox = await program.account.inbox.fetch(inboxPDA);
+      await program.methods
+        .sendDirectMessage(arweave("allowlistDmArweaveHash12345678901234567890"), contentHash, inbox.keyVersion, false, null, null, null, null, null)
+        .accounts({
+          message: messagePDA(inbox.messageCount.toNumber()),
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          senderStats: null,
+          recipient: null,
+          senderAllow,
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+    };
+
+    await setAllowlistOnly(true);
+    try {
+      await send(null);
+      assert.fail("Should have rejected a sender without an allowlist entry");
+    } catch (err) {
+      assert.include(String(err), "SenderNotAllowlisted");
+    }
+
+    await program.methods
+      .addToInboxAllowlist(stranger.publicKey)
+      .accounts({
+        allowlist: allowPDA,
+        owner: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await send(allowPDA);
+
+    await program.methods
+      .removeFromInboxAllowlist()
+      .accounts({ allowlist: allowPDA, owner: provider.wallet.publicKey })
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(allowPDA));
+
+    await setAllowlistOnly(false);
+  });
+
+  it("Holds messages for a wallet without an inbox until it claims them", async () => {
+    const recipient = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: recipient.publicKey,
+          lamports: 50_000_000,
+        })
+      )
+    );
+    const [pendingInboxPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_inbox"), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+    const [pendingPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_dm"), recipient.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [recipientInboxPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("inbox"), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+    const [claimedPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dm"), recipient.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const ephemeralKey = new Uint8Array(65).fill(7);
+
+    try {
+      await program.methods
+        .sendToUnactivated(stranger.publicKey, arweave("pendingDmArweaveHash1234567890123456789012"), contentHash, [...ephemeralKey])
+        .accounts({
+          pendingInbox: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_inbox"), provider.wallet.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          pendingMessage: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_dm"), provider.wallet.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+            program.programId
+          )[0],
+          config: configPDA,
+          recipientInbox: inboxPDA,
+          senderBlock: blockPDA(provider.wallet.publicKey, stranger.publicKey),
+          sender: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("Should have sent to the existing inbox instead");
+    } catch (err) {
+      assert.include(String(err), "InboxAlreadyActive");
+    }
+
+    await program.methods
+      .sendToUnactivated(recipient.publicKey, arweave("pendingDmArweaveHash1234567890123456789012"), contentHash, [...ephemeralKey])
+      .accounts({
+        pendingInbox: pendingInboxPDA,
+        pendingMessage: pendingPDA,
+        config: configPDA,
+        recipientInbox: recipientInboxPDA,
+        senderBlock: blockPDA(recipient.publicKey, provider.wallet.publicKey),
+        sender: provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const pending = await program.account.pendingMessage.fetch(pendingPDA);
+    assert.ok(pending.recipient.equals(recipient.publicKey));
+    assert.deepEqual(Buffer.from(pending.ephemeralKey), Buffer.from(ephemeralKey));
+
+    await program.methods
+      .activateInbox([...inboxKey], p256, false)
+      .accounts({
+        inbox: recipientInboxPDA,
+        owner: recipient.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([recipient])
+      .rpc();
+
+    // The sender cannot claim the message into their own inbox
+    try {
+      await program.methods
+        .claimPendingMessage()
+        .accounts({
+          pendingMessage: pendingPDA,
+          recipientInbox: inboxPDA,
+          message: messagePDA((await program.account.inbox.fetch(inboxPDA)).messageCount.toNumber()),
+          sender: provider.wallet.publicKey,
+          recipient: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have rejected a claim by someone other than the recipient");
+    } catch (err) {
+      assert.include(String(err), "NotPendingRecipient");
+    }
+
+    await program.methods
+      .claimPendingMessage()
+      .accounts({
+        pendingMessage: pendingPDA,
+        recipientInbox: recipientInboxPDA,
+        message: claimedPDA,
+        sender: provider.wallet.publicKey,
+        recipient: recipient.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([recipient])
+      .rpc();
+
+    const msg = await program.account.directMessage.fetch(claimedPDA);
+    assert.ok(msg.sender.equals(provider.wallet.publicKey));
+    assert.ok(msg.recipient.equals(recipient.publicKey));
+    assert.deepEqual(Buffer.from(msg.ephemeralKey), Buffer.from(ephemeralKey));
+    assert.isNull(await provider.connection.getAccountInfo(pendingPDA));
+    const inbox = await program.account.inbox.fetch(recipientInboxPDA);
+    assert.equal(inbox.messageCount.toNumber(), 1);
+  });
+
+  it("Keeps every account at the address the seeds module derives", async () => {
+    // Mirrors seeds::{proof,org,submission,inbox,dm}_seeds in lib.rs
+    const pda = (...seeds: Buffer[]) =>
+      anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const le64 = (n: anchor.BN) => n.toArrayLike(Buffer, "le", 8);
+    // HashAlgo discriminants, which proof seeds carry as one byte
+    const algos = ["sha256", "sha512", "blake3"];
+    const expected: [keyof typeof program.account, (a: any) => anchor.web3.PublicKey][] = [
+      [
+        "proof",
+        // Namespaced proofs put the stamping wallet between the prefix and the algo
+        (a) =>
+          pda(
+            Buffer.from("proof"),
+            ...(a.namespace ? [a.namespace.toBuffer()] : []),
+            ...proofSeeds(Buffer.from(a.digest), algos.indexOf(Object.keys(a.algo)[0]))
+          ),
+      ],
+      ["organization", (a) => pda(Buffer.from("org"), Buffer.from(a.slug))],
+      ["submission", (a) => pda(Buffer.from("submission"), a.organization.toBuffer(), le64(a.id))],
+      ["submissionZc", (a) => pda(Buffer.from("submission"), a.organization.toBuffer(), le64(a.id))],
+      ["inbox", (a) => pda(Buffer.from("inbox"), a.owner.toBuffer())],
+      ["directMessage", (a) => pda(Buffer.from("dm"), a.recipient.toBuffer(), le64(a.id))],
+    ];
+
+    for (const [name, derive] of expected) {
+      const accounts = await program.account[name].all();
+      assert.isNotEmpty(accounts, `Suite should have created a ${name}`);
+      for (const { publicKey, account } of accounts) {
+        assert.ok(derive(account).equals(publicKey), `${name} ${publicKey.toBase58()} is off its seeds`);
+      }
     }
   });
 });